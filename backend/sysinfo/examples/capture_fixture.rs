@@ -0,0 +1,256 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+//! Copies the files read by the Linux implementation into a fixture directory, so that tests can
+//! run against them with [`SystemBuilder`](sysinfo::SystemBuilder).
+//!
+//! ```text
+//! cargo run --example capture_fixture -- <output directory> [steps] [interval in ms]
+//! ```
+//!
+//! Each step is written to `<output directory>/<step>/` with `proc`, `sys`, `etc` and `dev`
+//! directories in it. Capturing at least two steps allows testing values computed from the
+//! difference between two refreshes, like the CPU usage.
+//!
+//! Process environments are never captured, but command lines, user names and mount points are.
+//! Review the fixture before committing it.
+
+#![crate_type = "bin"]
+
+#[cfg(target_os = "linux")]
+fn main() {
+    use std::{path::PathBuf, thread::sleep, time::Duration};
+
+    let mut args = std::env::args().skip(1);
+    let output = match args.next() {
+        Some(output) => PathBuf::from(output),
+        None => {
+            eprintln!("usage: capture_fixture <output directory> [steps] [interval in ms]");
+            std::process::exit(1);
+        }
+    };
+    let steps: usize = args
+        .next()
+        .map_or(2, |arg| arg.parse().expect("invalid steps"));
+    let interval: u64 = args
+        .next()
+        .map_or(1000, |arg| arg.parse().expect("invalid interval"));
+
+    for step in 0..steps {
+        if step > 0 {
+            sleep(Duration::from_millis(interval));
+        }
+        let root = output.join(step.to_string());
+        if let Err(e) = capture::capture(&root) {
+            eprintln!("failed to capture step {}: {}", step, e);
+            std::process::exit(1);
+        }
+        println!("captured {}", root.display());
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {
+    eprintln!("capturing fixtures is only supported on Linux");
+}
+
+#[cfg(target_os = "linux")]
+mod capture {
+    use std::{
+        fs, io,
+        os::unix::fs::symlink,
+        path::{Path, PathBuf},
+    };
+
+    /// Files read for the whole system, relative to `/proc`.
+    const PROC_FILES: &[&str] = &[
+        "stat",
+        "meminfo",
+        "cpuinfo",
+        "uptime",
+        "loadavg",
+        "self/mountinfo",
+        "1/mountinfo",
+        "net/dev",
+        "1/net/dev",
+        "net/tcp",
+        "net/tcp6",
+        "net/udp",
+        "net/udp6",
+        "net/unix",
+        "net/snmp",
+        "net/snmp6",
+        "pressure/cpu",
+        "pressure/memory",
+        "pressure/io",
+        "vmstat",
+        "diskstats",
+    ];
+    /// Files read for each process and task, relative to `/proc/<pid>`.
+    const PROCESS_FILES: &[&str] = &["stat", "status", "cmdline", "io", "limits"];
+    /// Links read for each process, relative to `/proc/<pid>`.
+    const PROCESS_LINKS: &[&str] = &["exe", "cwd", "root"];
+    /// Files read for each network interface, relative to `/sys/class/net/<interface>`.
+    const NETWORK_FILES: &[&str] = &["address", "mtu", "operstate", "carrier", "duplex", "speed"];
+    /// Files read from `/etc`.
+    const ETC_FILES: &[&str] = &["os-release", "lsb-release", "passwd"];
+
+    pub fn capture(root: &Path) -> io::Result<()> {
+        fs::create_dir_all(root)?;
+        capture_proc(Path::new("/proc"), &root.join("proc"))?;
+        capture_sys(Path::new("/sys"), &root.join("sys"))?;
+        capture_etc(Path::new("/etc"), &root.join("etc"))?;
+        capture_dev(&root.join("proc/self/mountinfo"), &root.join("dev"))?;
+        Ok(())
+    }
+
+    fn capture_proc(src: &Path, dst: &Path) -> io::Result<()> {
+        for file in PROC_FILES {
+            copy_file(&src.join(file), &dst.join(file));
+        }
+
+        for entry in fs::read_dir(src)?.flatten() {
+            let name = entry.file_name();
+            if !name.to_string_lossy().bytes().all(|b| b.is_ascii_digit()) {
+                continue;
+            }
+
+            let src = entry.path();
+            let dst = dst.join(&name);
+            capture_process(&src, &dst);
+            for link in PROCESS_LINKS {
+                copy_link(&src.join(link), &dst.join(link));
+            }
+            for fd in read_dir(&src.join("fd")) {
+                copy_link(&fd.path(), &dst.join("fd").join(fd.file_name()));
+            }
+            if let Ok(tasks) = fs::read_dir(src.join("task")) {
+                for task in tasks.flatten() {
+                    capture_process(&task.path(), &dst.join("task").join(task.file_name()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn capture_process(src: &Path, dst: &Path) {
+        // Processes can exit at any time, so errors are ignored
+        for file in PROCESS_FILES {
+            copy_file(&src.join(file), &dst.join(file));
+        }
+    }
+
+    fn capture_sys(src: &Path, dst: &Path) -> io::Result<()> {
+        for interface in read_dir(&src.join("class/net")) {
+            let interface_dir = Path::new("class/net").join(interface.file_name());
+            let statistics = interface_dir.join("statistics");
+            for file in read_dir(&src.join(&statistics)) {
+                copy_file(&file.path(), &dst.join(&statistics).join(file.file_name()));
+            }
+            // Some can't be read depending on the interface
+            for file in NETWORK_FILES {
+                copy_file(
+                    &src.join(&interface_dir).join(file),
+                    &dst.join(&interface_dir).join(file),
+                );
+            }
+        }
+
+        for device in read_dir(&src.join("block")) {
+            let rotational = Path::new("block")
+                .join(device.file_name())
+                .join("queue/rotational");
+            copy_file(&src.join(&rotational), &dst.join(&rotational));
+        }
+
+        for cpu in read_dir(&src.join("devices/system/cpu")) {
+            let frequency = Path::new("devices/system/cpu")
+                .join(cpu.file_name())
+                .join("cpufreq/scaling_cur_freq");
+            copy_file(&src.join(&frequency), &dst.join(&frequency));
+        }
+
+        for hwmon in read_dir(&src.join("class/hwmon")) {
+            let hwmon_dir = Path::new("class/hwmon").join(hwmon.file_name());
+            for file in read_dir(&src.join(&hwmon_dir)) {
+                let name = file.file_name();
+                let name = name.to_string_lossy();
+                if name == "name" || name.starts_with("temp") {
+                    copy_file(&file.path(), &dst.join(&hwmon_dir).join(file.file_name()));
+                }
+            }
+        }
+        let thermal_zone = Path::new("class/thermal/thermal_zone0/temp");
+        copy_file(&src.join(thermal_zone), &dst.join(thermal_zone));
+
+        Ok(())
+    }
+
+    fn capture_etc(src: &Path, dst: &Path) -> io::Result<()> {
+        // `os-release` is often a link outside of `/etc`, so its content is copied instead
+        for file in ETC_FILES {
+            copy_file(&src.join(file), &dst.join(file));
+        }
+        Ok(())
+    }
+
+    /// Recreates the devices listed in the captured mounts and `/dev/disk/by-id` as empty files,
+    /// keeping the links between them.
+    fn capture_dev(mountinfo: &Path, dst: &Path) -> io::Result<()> {
+        // The device is the second field after the separator of the optional fields
+        let mut devices: Vec<PathBuf> = fs::read_to_string(mountinfo)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split(" - ").nth(1)?.split_whitespace().nth(1))
+            .filter(|device| device.starts_with("/dev/"))
+            .map(PathBuf::from)
+            .collect();
+        devices.extend(read_dir(Path::new("/dev/disk/by-id")).map(|entry| entry.path()));
+
+        for device in devices {
+            let relative = match device.strip_prefix("/dev") {
+                Ok(relative) => relative,
+                Err(_) => continue,
+            };
+            copy_link(&device, &dst.join(relative));
+            if let Ok(real) = fs::canonicalize(&device) {
+                if let Ok(relative) = real.strip_prefix("/dev") {
+                    create_file(&dst.join(relative), b"");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_dir(path: &Path) -> impl Iterator<Item = fs::DirEntry> {
+        fs::read_dir(path).into_iter().flatten().flatten()
+    }
+
+    fn copy_file(src: &Path, dst: &Path) {
+        if let Ok(content) = fs::read(src) {
+            create_file(dst, &content);
+        }
+    }
+
+    fn create_file(dst: &Path, content: &[u8]) {
+        if let Some(parent) = dst.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(dst, content);
+    }
+
+    /// Copies a link as is. Regular files are replaced by empty files instead.
+    fn copy_link(src: &Path, dst: &Path) {
+        if let Some(parent) = dst.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match fs::read_link(src) {
+            Ok(target) => {
+                let _ = symlink(target, dst);
+            }
+            Err(_) if src.exists() => create_file(dst, b""),
+            Err(_) => {}
+        }
+    }
+}
@@ -7,7 +7,8 @@ use crate::sys::{component::Component, cpu::*, disk::*, network::Networks, proce
 use core_foundation_sys::base::{kCFAllocatorDefault, CFRelease};
 
 use crate::{
-    CpuExt, CpuRefreshKind, LoadAvg, Pid, ProcessRefreshKind, RefreshKind, SystemExt, User,
+    CpuExt, CpuRefreshKind, LoadAvg, Pid, ProcessRefreshKind, RefreshKind, SocketStats, SystemExt,
+    User,
 };
 
 #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
@@ -88,6 +89,7 @@ pub struct System {
     networks: Networks,
     port: mach_port_t,
     users: Vec<User>,
+    sockets: SocketStats,
     boot_time: u64,
     // Used to get disk information, to be more specific, it's needed by the
     // DADiskCreateFromVolumePath function. Not supported on iOS.
@@ -183,6 +185,7 @@ impl SystemExt for System {
                 networks: Networks::new(),
                 port,
                 users: Vec::new(),
+                sockets: SocketStats::default(),
                 boot_time: boot_time(),
                 #[cfg(target_os = "macos")]
                 session: ffi::SessionWrap(::std::ptr::null_mut()),
@@ -523,6 +526,10 @@ impl SystemExt for System {
         &self.users
     }
 
+    fn sockets(&self) -> &SocketStats {
+        &self.sockets
+    }
+
     fn boot_time(&self) -> u64 {
         self.boot_time
     }
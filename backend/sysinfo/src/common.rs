@@ -2,7 +2,15 @@
 
 use crate::{NetworkData, Networks, NetworksExt, UserExt};
 
-use std::{convert::From, fmt, str::FromStr};
+use std::{
+    collections::BTreeMap,
+    convert::From,
+    fmt, io,
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
 /// Trait to have a common conversions for the [`Pid`][crate::Pid] type.
 ///
@@ -35,6 +43,8 @@ pub trait PidExt<T>: Copy + From<T> + FromStr + fmt::Display {
 macro_rules! pid_decl {
     ($typ:ty) => {
         #[doc = include_str!("../md_doc/pid.md")]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
         #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
         #[repr(transparent)]
         pub struct Pid(pub(crate) $typ);
@@ -229,6 +239,15 @@ pub struct ProcessRefreshKind {
     cpu: bool,
     disk_usage: bool,
     user: bool,
+    cmd: bool,
+    environ: bool,
+    exe: bool,
+    cwd: bool,
+    root: bool,
+    memory: bool,
+    status: bool,
+    open_files: bool,
+    limits: bool,
 }
 
 impl ProcessRefreshKind {
@@ -246,7 +265,9 @@ impl ProcessRefreshKind {
         Self::default()
     }
 
-    /// Creates a new `ProcessRefreshKind` with every refresh set to `true`.
+    /// Creates a new `ProcessRefreshKind` with every refresh set to `true`, except
+    /// [`open_files`][Self::open_files] which is much slower than the others and has to be
+    /// requested explicitly.
     ///
     /// ```
     /// use sysinfo::ProcessRefreshKind;
@@ -255,12 +276,22 @@ impl ProcessRefreshKind {
     ///
     /// assert_eq!(r.cpu(), true);
     /// assert_eq!(r.disk_usage(), true);
+    /// assert_eq!(r.open_files(), false);
     /// ```
     pub fn everything() -> Self {
         Self {
             cpu: true,
             disk_usage: true,
             user: true,
+            cmd: true,
+            environ: true,
+            exe: true,
+            cwd: true,
+            root: true,
+            memory: true,
+            status: true,
+            open_files: false,
+            limits: true,
         }
     }
 
@@ -279,6 +310,115 @@ impl ProcessRefreshKind {
         r#"This refresh is about `user_id` and `group_id`. Please note that it has an effect mostly
 on Windows as other platforms get this information alongside the Process information directly."#,
     );
+    impl_get_set!(
+        ProcessRefreshKind,
+        cmd,
+        with_cmd,
+        without_cmd,
+        r#"This refresh is about `cmd`. On Linux, it is only read once for each process, the first
+time it is requested."#,
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        environ,
+        with_environ,
+        without_environ,
+        r#"This refresh is about `environ`. On Linux, it is only read once for each process, the
+first time it is requested."#,
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        exe,
+        with_exe,
+        without_exe,
+        r#"This refresh is about `exe`. On Linux, it is only read once for each process, the first
+time it is requested."#,
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        cwd,
+        with_cwd,
+        without_cwd,
+        r#"This refresh is about `cwd`. On Linux, it is only read once for each process, the first
+time it is requested."#,
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        root,
+        with_root,
+        without_root,
+        r#"This refresh is about `root`. On Linux, it is only read once for each process, the first
+time it is requested."#,
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        memory,
+        with_memory,
+        without_memory,
+        "This refresh is about `memory` and `virtual_memory`."
+    );
+    impl_get_set!(ProcessRefreshKind, status, with_status, without_status);
+
+    /// Returns the value of the "open_files" refresh kind.
+    ///
+    /// This refresh is about `open_files`. On Linux, every file descriptor of every process is
+    /// read, so it is much slower than the other refreshes and isn't part of
+    /// [`ProcessRefreshKind::everything`].
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::everything();
+    /// assert_eq!(r.open_files(), false);
+    ///
+    /// let r = r.with_open_files();
+    /// assert_eq!(r.open_files(), true);
+    /// ```
+    pub fn open_files(&self) -> bool {
+        self.open_files
+    }
+
+    /// Sets the value of the "open_files" refresh kind to `true`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::new();
+    /// assert_eq!(r.open_files(), false);
+    ///
+    /// let r = r.with_open_files();
+    /// assert_eq!(r.open_files(), true);
+    /// ```
+    #[must_use]
+    pub fn with_open_files(mut self) -> Self {
+        self.open_files = true;
+        self
+    }
+
+    /// Sets the value of the "open_files" refresh kind to `false`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::everything().with_open_files();
+    /// assert_eq!(r.open_files(), true);
+    ///
+    /// let r = r.without_open_files();
+    /// assert_eq!(r.open_files(), false);
+    /// ```
+    #[must_use]
+    pub fn without_open_files(mut self) -> Self {
+        self.open_files = false;
+        self
+    }
+
+    impl_get_set!(
+        ProcessRefreshKind,
+        limits,
+        with_limits,
+        without_limits,
+        "This refresh is about `limits`."
+    );
 }
 
 /// Used to determine what you want to refresh specifically on the [`Cpu`] type.
@@ -305,6 +445,7 @@ on Windows as other platforms get this information alongside the Process informa
 pub struct CpuRefreshKind {
     cpu_usage: bool,
     frequency: bool,
+    kernel_activity: bool,
 }
 
 impl CpuRefreshKind {
@@ -334,11 +475,19 @@ impl CpuRefreshKind {
         Self {
             cpu_usage: true,
             frequency: true,
+            kernel_activity: true,
         }
     }
 
     impl_get_set!(CpuRefreshKind, cpu_usage, with_cpu_usage, without_cpu_usage);
     impl_get_set!(CpuRefreshKind, frequency, with_frequency, without_frequency);
+    impl_get_set!(
+        CpuRefreshKind,
+        kernel_activity,
+        with_kernel_activity,
+        without_kernel_activity,
+        "This refresh is about [`SystemExt::kernel_activity`][crate::SystemExt::kernel_activity]."
+    );
 }
 
 /// Used to determine what you want to refresh specifically on the [`System`] type.
@@ -372,6 +521,11 @@ pub struct RefreshKind {
     components: bool,
     components_list: bool,
     users_list: bool,
+    pressure: bool,
+    memory_activity: bool,
+    disks_io: bool,
+    sockets: bool,
+    socket_processes: bool,
 }
 
 impl RefreshKind {
@@ -397,7 +551,9 @@ impl RefreshKind {
         Self::default()
     }
 
-    /// Creates a new `RefreshKind` with every refresh set to `true`/`Some(...)`.
+    /// Creates a new `RefreshKind` with every refresh set to `true`/`Some(...)`, except
+    /// [`socket_processes`][Self::socket_processes] which is much slower than the others and has
+    /// to be requested explicitly.
     ///
     /// ```
     /// use sysinfo::RefreshKind;
@@ -414,6 +570,7 @@ impl RefreshKind {
     /// assert_eq!(r.components(), true);
     /// assert_eq!(r.components_list(), true);
     /// assert_eq!(r.users_list(), true);
+    /// assert_eq!(r.socket_processes(), false);
     /// ```
     pub fn everything() -> Self {
         Self {
@@ -427,6 +584,11 @@ impl RefreshKind {
             components: true,
             components_list: true,
             users_list: true,
+            pressure: true,
+            memory_activity: true,
+            disks_io: true,
+            sockets: true,
+            socket_processes: false,
         }
     }
 
@@ -456,6 +618,296 @@ impl RefreshKind {
         without_components_list
     );
     impl_get_set!(RefreshKind, users_list, with_users_list, without_users_list);
+    impl_get_set!(
+        RefreshKind,
+        pressure,
+        with_pressure,
+        without_pressure,
+        "This refresh is about [`SystemExt::pressure`][crate::SystemExt::pressure]."
+    );
+    impl_get_set!(
+        RefreshKind,
+        memory_activity,
+        with_memory_activity,
+        without_memory_activity,
+        "This refresh is about [`SystemExt::memory_activity`][crate::SystemExt::memory_activity]."
+    );
+    impl_get_set!(
+        RefreshKind,
+        disks_io,
+        with_disks_io,
+        without_disks_io,
+        "This refresh is about [`SystemExt::disks_io`][crate::SystemExt::disks_io]."
+    );
+    impl_get_set!(
+        RefreshKind,
+        sockets,
+        with_sockets,
+        without_sockets,
+        "This refresh is about [`SystemExt::sockets`][crate::SystemExt::sockets]."
+    );
+
+    /// Returns the value of the "socket_processes" refresh kind.
+    ///
+    /// This refresh is about [`ListeningSocket::pids`], see
+    /// [`SystemExt::refresh_socket_processes`][crate::SystemExt::refresh_socket_processes]. On
+    /// Linux, every file descriptor of every process is read, so it is much slower than the other
+    /// refreshes and isn't part of [`RefreshKind::everything`].
+    ///
+    /// ```
+    /// use sysinfo::RefreshKind;
+    ///
+    /// let r = RefreshKind::everything();
+    /// assert_eq!(r.socket_processes(), false);
+    ///
+    /// let r = r.with_socket_processes();
+    /// assert_eq!(r.socket_processes(), true);
+    /// ```
+    pub fn socket_processes(&self) -> bool {
+        self.socket_processes
+    }
+
+    /// Sets the value of the "socket_processes" refresh kind to `true`.
+    ///
+    /// ```
+    /// use sysinfo::RefreshKind;
+    ///
+    /// let r = RefreshKind::new();
+    /// assert_eq!(r.socket_processes(), false);
+    ///
+    /// let r = r.with_socket_processes();
+    /// assert_eq!(r.socket_processes(), true);
+    /// ```
+    #[must_use]
+    pub fn with_socket_processes(mut self) -> Self {
+        self.socket_processes = true;
+        self
+    }
+
+    /// Sets the value of the "socket_processes" refresh kind to `false`.
+    ///
+    /// ```
+    /// use sysinfo::RefreshKind;
+    ///
+    /// let r = RefreshKind::everything().with_socket_processes();
+    /// assert_eq!(r.socket_processes(), true);
+    ///
+    /// let r = r.without_socket_processes();
+    /// assert_eq!(r.socket_processes(), false);
+    /// ```
+    #[must_use]
+    pub fn without_socket_processes(mut self) -> Self {
+        self.socket_processes = false;
+        self
+    }
+}
+
+/// Used to create a [`System`] with options other than the information to refresh.
+///
+/// The paths are only used on Linux, where they replace `/proc`, `/sys`, `/etc` and `/dev`. This
+/// allows reading the information of a host from a container where its file systems are mounted
+/// somewhere else. Unlike [`SystemExt::new_with_specifics`], nothing is read from the environment
+/// unless [`SystemBuilder::from_env`] is used, so several [`System`]s with different options can
+/// exist in the same process.
+///
+/// ```no_run
+/// use sysinfo::{RefreshKind, SystemBuilder, SystemExt};
+///
+/// let s = SystemBuilder::new()
+///     .with_refresh_kind(RefreshKind::new().with_memory())
+///     .with_proc_path("/host/proc")
+///     .build();
+/// println!("{}", s.total_memory());
+/// ```
+///
+/// [`System`]: crate::System
+/// [`SystemExt::new_with_specifics`]: crate::SystemExt::new_with_specifics
+#[derive(Clone, Debug, Default)]
+pub struct SystemBuilder {
+    pub(crate) refresh_kind: RefreshKind,
+    pub(crate) proc_path: Option<PathBuf>,
+    pub(crate) sys_path: Option<PathBuf>,
+    pub(crate) etc_path: Option<PathBuf>,
+    pub(crate) dev_path: Option<PathBuf>,
+    pub(crate) host_root_path: Option<PathBuf>,
+    pub(crate) open_files_limit: Option<isize>,
+    pub(crate) disk_timeout: Option<Duration>,
+    pub(crate) skip_network_disks: bool,
+    pub(crate) network_source: NetworkSource,
+}
+
+impl SystemBuilder {
+    /// Creates a new `SystemBuilder` which refreshes nothing and uses the default paths.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `SystemBuilder` with the paths set by the `SYSINFO_PROC_PATH`,
+    /// `SYSINFO_SYS_PATH`, `SYSINFO_ETC_PATH`, `SYSINFO_DEV_PATH` and `SYSINFO_HOST_ROOT_PATH`
+    /// environment variables. This is what [`SystemExt::new_with_specifics`] uses.
+    ///
+    /// The `SYSINFO_NETWORK_SOURCE` environment variable sets the [`NetworkSource`], with `proc`
+    /// for [`NetworkSource::ProcNetDev`] and `host` for [`NetworkSource::HostProcNetDev`].
+    ///
+    /// [`SystemExt::new_with_specifics`]: crate::SystemExt::new_with_specifics
+    pub fn from_env() -> Self {
+        SystemBuilder {
+            proc_path: std::env::var_os("SYSINFO_PROC_PATH").map(PathBuf::from),
+            sys_path: std::env::var_os("SYSINFO_SYS_PATH").map(PathBuf::from),
+            etc_path: std::env::var_os("SYSINFO_ETC_PATH").map(PathBuf::from),
+            dev_path: std::env::var_os("SYSINFO_DEV_PATH").map(PathBuf::from),
+            host_root_path: std::env::var_os("SYSINFO_HOST_ROOT_PATH").map(PathBuf::from),
+            network_source: match std::env::var("SYSINFO_NETWORK_SOURCE").as_deref() {
+                Ok("proc") => NetworkSource::ProcNetDev,
+                Ok("host") => NetworkSource::HostProcNetDev,
+                _ => NetworkSource::Sysfs,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Sets the information refreshed when the [`System`] is created.
+    ///
+    /// [`System`]: crate::System
+    pub fn with_refresh_kind(self, refresh_kind: RefreshKind) -> Self {
+        SystemBuilder {
+            refresh_kind,
+            ..self
+        }
+    }
+
+    /// Reads from the given path instead of `/proc`.
+    pub fn with_proc_path(self, path: impl Into<PathBuf>) -> Self {
+        SystemBuilder {
+            proc_path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Reads from the given path instead of `/sys`.
+    pub fn with_sys_path(self, path: impl Into<PathBuf>) -> Self {
+        SystemBuilder {
+            sys_path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Reads from the given path instead of `/etc`.
+    pub fn with_etc_path(self, path: impl Into<PathBuf>) -> Self {
+        SystemBuilder {
+            etc_path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Reads from the given path instead of `/dev`.
+    pub fn with_dev_path(self, path: impl Into<PathBuf>) -> Self {
+        SystemBuilder {
+            dev_path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Lists the disks of the host whose root is mounted at the given path, for a process running
+    /// in a container.
+    ///
+    /// The mounts are read from the mount table of the host's init process, `1/mountinfo` under
+    /// the `/proc` path, which must be the host's. Each mount point is then queried under the
+    /// given path, while the disks still report the mount point of the host.
+    ///
+    /// ```no_run
+    /// use sysinfo::{RefreshKind, SystemBuilder, SystemExt};
+    ///
+    /// // Run with `-v /proc:/host/proc:ro -v /:/host/root:ro,rslave`
+    /// let s = SystemBuilder::new()
+    ///     .with_refresh_kind(RefreshKind::new().with_disks_list())
+    ///     .with_proc_path("/host/proc")
+    ///     .with_host_root_path("/host/root")
+    ///     .build();
+    /// println!("{} disks", s.disks().len());
+    /// ```
+    pub fn with_host_root_path(self, path: impl Into<PathBuf>) -> Self {
+        SystemBuilder {
+            host_root_path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of files the [`System`] keeps open, instead of sharing the budget
+    /// set by [`set_open_files_limit`] with the other [`System`]s. Like [`set_open_files_limit`],
+    /// this is only used on Linux and is capped to the system limit.
+    ///
+    /// [`System`]: crate::System
+    /// [`set_open_files_limit`]: crate::set_open_files_limit
+    pub fn with_open_files_limit(self, limit: isize) -> Self {
+        SystemBuilder {
+            open_files_limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Waits at most `timeout` for the space of each mount point when listing and refreshing the
+    /// disks, instead of blocking until it answers. This is only used on Linux.
+    ///
+    /// A mount point which doesn't answer in time, like a hung NFS or CIFS mount, is reported as
+    /// unreachable with its last known space, see [`DiskExt::is_unreachable`]. It is then queried
+    /// again after a delay which grows with each timeout in a row. Each query runs in its own
+    /// thread, and a mount point never has more than one blocked query.
+    ///
+    /// [`DiskExt::is_unreachable`]: crate::DiskExt::is_unreachable
+    pub fn with_disk_timeout(self, timeout: Duration) -> Self {
+        SystemBuilder {
+            disk_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Leaves the mounts of network file systems, like NFS and CIFS, out of the disks. This is
+    /// only used on Linux.
+    pub fn without_network_disks(self) -> Self {
+        SystemBuilder {
+            skip_network_disks: true,
+            ..self
+        }
+    }
+
+    /// Sets where the network interfaces and their counters are read from. This is only used on
+    /// Linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkSource, NetworksExt, RefreshKind, SystemBuilder, SystemExt};
+    ///
+    /// // Run with `-v /proc:/host/proc:ro -v /sys:/host/sys:ro`
+    /// let s = SystemBuilder::new()
+    ///     .with_refresh_kind(RefreshKind::new().with_networks_list())
+    ///     .with_proc_path("/host/proc")
+    ///     .with_sys_path("/host/sys")
+    ///     .with_network_source(NetworkSource::HostProcNetDev)
+    ///     .build();
+    /// for (name, _) in s.networks().iter() {
+    ///     println!("{}", name);
+    /// }
+    /// ```
+    pub fn with_network_source(self, network_source: NetworkSource) -> Self {
+        SystemBuilder {
+            network_source,
+            ..self
+        }
+    }
+
+    /// Creates the [`System`] and refreshes the information set with
+    /// [`SystemBuilder::with_refresh_kind`].
+    ///
+    /// [`System`]: crate::System
+    pub fn build(self) -> crate::System {
+        cfg_if::cfg_if! {
+            if #[cfg(all(not(feature = "unknown-ci"), any(target_os = "linux", target_os = "android")))] {
+                crate::System::new_with_builder(self)
+            } else {
+                <crate::System as crate::SystemExt>::new_with_specifics(self.refresh_kind)
+            }
+        }
+    }
 }
 
 /// Iterator over network interfaces.
@@ -507,6 +959,7 @@ impl<'a> IntoIterator for &'a Networks {
 ///     println!("{:?}: {:?}", disk.name(), disk.type_());
 /// }
 /// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum DiskType {
     /// HDD type.
@@ -517,6 +970,196 @@ pub enum DiskType {
     Unknown(isize),
 }
 
+/// The major and minor numbers identifying the device a file system is on.
+///
+/// It is returned by [`DiskExt::device_id`][crate::DiskExt::device_id].
+///
+/// ```no_run
+/// use sysinfo::{DiskExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for disk in s.disks() {
+///     if let Some(id) = disk.device_id() {
+///         println!("{:?}: {}:{}", disk.mount_point(), id.major, id.minor);
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct DeviceId {
+    /// The major number, which identifies the driver.
+    pub major: u32,
+    /// The minor number, which identifies the device among those of the driver.
+    pub minor: u32,
+}
+
+/// A MAC address.
+///
+/// It is returned by [`NetworkExt::mac_address`][crate::NetworkExt::mac_address].
+///
+/// ```no_run
+/// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (name, network) in s.networks() {
+///     println!("{}: {}", name, network.mac_address());
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    /// The address of interfaces which have none, like the loopback.
+    pub const UNSPECIFIED: MacAddr = MacAddr([0; 6]);
+
+    /// Returns `true` if this is [`MacAddr::UNSPECIFIED`].
+    pub fn is_unspecified(&self) -> bool {
+        *self == MacAddr::UNSPECIFIED
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// An IP address of a network interface, along with the length of the prefix of its network.
+///
+/// It is returned by [`NetworkExt::ip_networks`][crate::NetworkExt::ip_networks].
+///
+/// ```no_run
+/// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (name, network) in s.networks() {
+///     for ip_network in network.ip_networks() {
+///         println!("{}: {}", name, ip_network);
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct IpNetwork {
+    /// The address of the interface.
+    pub addr: IpAddr,
+    /// The number of leading bits of the address which identify the network, like 24 for
+    /// `192.168.1.10/24`.
+    pub prefix: u8,
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// The operational state of a network interface, as defined by
+/// [RFC 2863](https://www.rfc-editor.org/rfc/rfc2863#section-3.1.14).
+///
+/// It is returned by [`NetworkExt::operational_state`][crate::NetworkExt::operational_state].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum OperationalState {
+    /// The state isn't known, which is usual for virtual interfaces like the loopback.
+    Unknown,
+    /// A component of the interface, like its driver, is missing.
+    NotPresent,
+    /// The interface can't pass packets.
+    Down,
+    /// The interface is down because an interface it runs on is.
+    LowerLayerDown,
+    /// The interface is in test mode.
+    Testing,
+    /// The interface is waiting for an external event, like an authentication.
+    Dormant,
+    /// The interface can pass packets.
+    Up,
+}
+
+impl Default for OperationalState {
+    fn default() -> Self {
+        OperationalState::Unknown
+    }
+}
+
+impl fmt::Display for OperationalState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            OperationalState::Unknown => "unknown",
+            OperationalState::NotPresent => "not present",
+            OperationalState::Down => "down",
+            OperationalState::LowerLayerDown => "lower layer down",
+            OperationalState::Testing => "testing",
+            OperationalState::Dormant => "dormant",
+            OperationalState::Up => "up",
+        })
+    }
+}
+
+/// Whether a network interface can send and receive at the same time.
+///
+/// It is returned by [`NetworkExt::duplex`][crate::NetworkExt::duplex].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Duplex {
+    /// The duplex mode isn't known, like for virtual interfaces or interfaces which are down.
+    Unknown,
+    /// The interface sends and receives in turn, sharing its speed between the two.
+    Half,
+    /// The interface sends and receives at the same time, each at its full speed.
+    Full,
+}
+
+impl Default for Duplex {
+    fn default() -> Self {
+        Duplex::Unknown
+    }
+}
+
+impl fmt::Display for Duplex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            Duplex::Unknown => "unknown",
+            Duplex::Half => "half",
+            Duplex::Full => "full",
+        })
+    }
+}
+
+/// Where the network interfaces and their counters are read from on Linux.
+///
+/// It is set with [`SystemBuilder::with_network_source`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum NetworkSource {
+    /// `/sys/class/net`, which lists the interfaces of the network namespace `/sys` was mounted
+    /// in. This is the default.
+    Sysfs,
+    /// `/proc/net/dev`, which lists the interfaces of the network namespace of the process.
+    ProcNetDev,
+    /// `/proc/1/net/dev`, which lists the interfaces of the network namespace of the init process.
+    /// In a container, it is the host's when the `/proc` path is the host's. The sockets are read
+    /// from `/proc/1/net` as well.
+    ///
+    /// The link attributes, like the speed, are still read from `/sys/class/net`, so the `/sys`
+    /// path must be the host's as well. The addresses are only known for the network namespace
+    /// of the process, so none are reported.
+    HostProcNetDev,
+}
+
+impl Default for NetworkSource {
+    fn default() -> Self {
+        NetworkSource::Sysfs
+    }
+}
+
 /// An enum representing signals on UNIX-like systems.
 ///
 /// On non-unix systems, this enum is mostly useless and is only there to keep coherency between
@@ -524,6 +1167,7 @@ pub enum DiskType {
 ///
 /// If you want the list of the supported signals on the current system, use
 /// [`SystemExt::SUPPORTED_SIGNALS`][crate::SystemExt::SUPPORTED_SIGNALS].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Debug)]
 pub enum Signal {
     /// Hangup detected on controlling terminal or death of controlling process.
@@ -651,7 +1295,8 @@ impl std::fmt::Display for Signal {
 /// );
 /// ```
 #[repr(C)]
-#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct LoadAvg {
     /// Average load within one minute.
     pub one: f64,
@@ -661,10 +1306,398 @@ pub struct LoadAvg {
     pub fifteen: f64,
 }
 
+/// The activity of the kernel scheduler.
+///
+/// It is returned by [`SystemExt::kernel_activity`][crate::SystemExt::kernel_activity]. The rates
+/// are per second since the previous refresh, and are zero after the first one.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// let activity = s.kernel_activity();
+/// println!(
+///     "{} context switches/s, {} blocked processes",
+///     activity.context_switches, activity.blocked_processes,
+/// );
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct KernelActivity {
+    /// Context switches per second.
+    pub context_switches: f64,
+    /// Interrupts serviced per second.
+    pub interrupts: f64,
+    /// Processes and threads created per second.
+    pub forks: f64,
+    /// The number of threads currently running or ready to run.
+    pub running_processes: u64,
+    /// The number of threads currently blocked waiting for I/O to complete.
+    pub blocked_processes: u64,
+}
+
+/// Pressure stall information: how much time tasks were stalled waiting for a resource.
+///
+/// It is returned by [`SystemExt::pressure`][crate::SystemExt::pressure]. Each resource is `None`
+/// when the kernel doesn't report its pressure, which is the case for kernels before 4.20 or
+/// without `CONFIG_PSI`.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// if let Some(memory) = s.pressure().memory {
+///     println!("some tasks stalled on memory {}% of the last 10s", memory.some.avg10);
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Pressure {
+    /// Tasks waiting for a CPU.
+    pub cpu: Option<ResourcePressure>,
+    /// Tasks waiting for memory, for example while reclaiming pages or swapping.
+    pub memory: Option<ResourcePressure>,
+    /// Tasks waiting for I/O.
+    pub io: Option<ResourcePressure>,
+}
+
+/// The pressure on one resource. See [`Pressure`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct ResourcePressure {
+    /// The time at least one task was stalled.
+    pub some: PressureStall,
+    /// The time all non-idle tasks were stalled at once. It is `None` for the CPU on kernels
+    /// before 5.13.
+    pub full: Option<PressureStall>,
+}
+
+/// How long tasks were stalled. See [`Pressure`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct PressureStall {
+    /// The percentage of the last 10 seconds.
+    pub avg10: f32,
+    /// The percentage of the last 60 seconds.
+    pub avg60: f32,
+    /// The percentage of the last 300 seconds.
+    pub avg300: f32,
+    /// The total stall time, in microseconds.
+    pub total: u64,
+}
+
+/// The I/O activity of a block device, like a disk or one of its partitions.
+///
+/// It is returned by [`SystemExt::disks_io`][crate::SystemExt::disks_io]. The rates are per
+/// second since the previous refresh, and are zero after the first one.
+///
+/// ```no_run
+/// use sysinfo::{RefreshKind, System, SystemExt};
+///
+/// let s = System::new_with_specifics(RefreshKind::new().with_disks_list().with_disks_io());
+/// for io in s.disks_io() {
+///     println!("{}: {}% busy, {} ms per I/O", io.name, io.utilization, io.await_time);
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DiskIo {
+    /// The name of the device, like `sda`, `nvme0n1p1` or `dm-0`.
+    pub name: String,
+    /// Where the device is mounted, among the [`Disk`][crate::Disk]s listed by the last call to
+    /// [`SystemExt::refresh_disks_list`][crate::SystemExt::refresh_disks_list].
+    pub mount_points: Vec<PathBuf>,
+    /// Read operations completed per second.
+    pub reads: f64,
+    /// Write operations completed per second.
+    pub writes: f64,
+    /// Bytes read per second.
+    pub read_bytes: f64,
+    /// Bytes written per second.
+    pub written_bytes: f64,
+    /// The average time an operation took to complete, including the time it waited in the
+    /// queue, in milliseconds.
+    pub await_time: f64,
+    /// The percentage of time the device was busy with at least one operation.
+    pub utilization: f64,
+}
+
+/// The parts of `/proc/meminfo` which aren't covered by the other memory methods.
+///
+/// It is returned by [`SystemExt::memory_details`][crate::SystemExt::memory_details]. Like the
+/// other memory values, the amounts are in KB, except for the size of a huge page.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// let details = s.memory_details();
+/// println!("{} KB dirty, {} KB committed", details.dirty, details.committed);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryDetails {
+    /// Memory waiting to be written back to disk.
+    pub dirty: u64,
+    /// Memory being written back to disk.
+    pub writeback: u64,
+    /// Memory used by shared memory and `tmpfs`.
+    pub shared: u64,
+    /// Memory allocated by processes, even if it isn't backed by RAM yet (`Committed_AS`).
+    pub committed: u64,
+    /// The number of huge pages in the pool.
+    pub huge_pages_total: u64,
+    /// The number of huge pages in the pool which aren't allocated.
+    pub huge_pages_free: u64,
+    /// The size of a huge page, in bytes.
+    pub huge_page_size_bytes: u64,
+}
+
+/// The activity of the virtual memory subsystem, from `/proc/vmstat`.
+///
+/// It is returned by [`SystemExt::memory_activity`][crate::SystemExt::memory_activity]. The rates
+/// are per second since the previous refresh, and are zero after the first one.
+///
+/// ```no_run
+/// use sysinfo::{RefreshKind, System, SystemExt};
+///
+/// let s = System::new_with_specifics(RefreshKind::new().with_memory_activity());
+/// let activity = s.memory_activity();
+/// println!(
+///     "{} major page faults/s, {} OOM kills",
+///     activity.major_page_faults, activity.oom_kills,
+/// );
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct MemoryActivity {
+    /// Page faults per second, major or not.
+    pub page_faults: f64,
+    /// Page faults which required reading from disk, per second.
+    pub major_page_faults: f64,
+    /// Pages read from swap per second.
+    pub pages_swapped_in: f64,
+    /// Pages written to swap per second.
+    pub pages_swapped_out: f64,
+    /// Pages scanned for reclaim per second, by `kswapd` and direct reclaim.
+    pub pages_scanned: f64,
+    /// Pages reclaimed per second, by `kswapd` and direct reclaim.
+    pub pages_stolen: f64,
+    /// The number of processes killed by the OOM killer since boot.
+    pub oom_kills: u64,
+}
+
+/// The sockets of the system and the activity of the TCP and UDP protocols, from `/proc/net`.
+///
+/// It is returned by [`SystemExt::sockets`][crate::SystemExt::sockets]. The rates are per second
+/// since the previous refresh, and are zero after the first one.
+///
+/// ```no_run
+/// use sysinfo::{RefreshKind, System, SystemExt, TcpState};
+///
+/// let s = System::new_with_specifics(RefreshKind::new().with_sockets());
+/// let sockets = s.sockets();
+/// println!(
+///     "{:?} connections in TIME_WAIT",
+///     sockets.tcp_states.get(&TcpState::TimeWait),
+/// );
+/// for socket in &sockets.listening {
+///     println!("{:?} {:?} {:?}", socket.address, socket.path, socket.pids);
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SocketStats {
+    /// The number of IPv4 and IPv6 TCP sockets in each state. States without sockets are left
+    /// out.
+    pub tcp_states: BTreeMap<TcpState, usize>,
+    /// The number of IPv4 and IPv6 UDP sockets.
+    pub udp_sockets: usize,
+    /// The number of Unix domain sockets.
+    pub unix_sockets: usize,
+    /// The sockets waiting for connections or datagrams: TCP sockets in the
+    /// [`TcpState::Listen`] state, UDP sockets which aren't connected to a remote address and
+    /// Unix stream sockets accepting connections.
+    pub listening: Vec<ListeningSocket>,
+    /// The activity of TCP, over IPv4 and IPv6.
+    pub tcp: TcpActivity,
+    /// The activity of UDP, over IPv4 and IPv6.
+    pub udp: UdpActivity,
+}
+
+/// The state of a TCP socket.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+pub enum TcpState {
+    /// The connection is open.
+    Established,
+    /// A connection request was sent, waiting for the answer.
+    SynSent,
+    /// A connection request was received and answered, waiting for the acknowledgment.
+    SynReceived,
+    /// The socket was closed, waiting for the remote end to acknowledge it.
+    FinWait1,
+    /// The socket was closed and acknowledged, waiting for the remote end to close too.
+    FinWait2,
+    /// Both ends closed the connection, waiting for the delayed packets to be dropped.
+    TimeWait,
+    /// The socket is closed.
+    Close,
+    /// The remote end closed the connection, waiting for the socket to be closed.
+    CloseWait,
+    /// Both ends closed the connection, waiting for the last acknowledgment.
+    LastAck,
+    /// The socket waits for connections.
+    Listen,
+    /// Both ends closed the connection at the same time.
+    Closing,
+    /// A connection request was received, before a socket is created for it.
+    NewSynReceived,
+}
+
+impl fmt::Display for TcpState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            TcpState::Established => "established",
+            TcpState::SynSent => "syn sent",
+            TcpState::SynReceived => "syn received",
+            TcpState::FinWait1 => "fin wait 1",
+            TcpState::FinWait2 => "fin wait 2",
+            TcpState::TimeWait => "time wait",
+            TcpState::Close => "close",
+            TcpState::CloseWait => "close wait",
+            TcpState::LastAck => "last ack",
+            TcpState::Listen => "listen",
+            TcpState::Closing => "closing",
+            TcpState::NewSynReceived => "new syn received",
+        })
+    }
+}
+
+/// The protocol of a [`ListeningSocket`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+pub enum SocketProtocol {
+    /// TCP, over IPv4 or IPv6.
+    Tcp,
+    /// UDP, over IPv4 or IPv6.
+    Udp,
+    /// A Unix domain socket.
+    Unix,
+}
+
+impl fmt::Display for SocketProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            SocketProtocol::Tcp => "tcp",
+            SocketProtocol::Udp => "udp",
+            SocketProtocol::Unix => "unix",
+        })
+    }
+}
+
+/// A socket waiting for connections or datagrams, see [`SocketStats::listening`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListeningSocket {
+    /// The protocol of the socket.
+    pub protocol: SocketProtocol,
+    /// The local address of a TCP or UDP socket, whose IP address is unspecified when it listens
+    /// on every address.
+    pub address: Option<SocketAddr>,
+    /// The path of a Unix socket, which starts with `@` for an abstract socket.
+    pub path: Option<String>,
+    /// The inode of the socket, which identifies it.
+    pub inode: u64,
+    /// The processes which have the socket open, found by
+    /// [`SystemExt::refresh_socket_processes`][crate::SystemExt::refresh_socket_processes]. It is
+    /// empty until then. Only the processes whose file descriptors can be read are found, which
+    /// usually requires running as root to find those of other users.
+    pub pids: Vec<Pid>,
+}
+
+/// The activity of TCP, from `/proc/net/snmp`, in events per second.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct TcpActivity {
+    /// Connections opened by the system (`ActiveOpens`).
+    pub active_opens: f64,
+    /// Connections accepted by the system (`PassiveOpens`).
+    pub passive_opens: f64,
+    /// Connection attempts which failed (`AttemptFails`).
+    pub failed_connection_attempts: f64,
+    /// Established connections which were reset (`EstabResets`).
+    pub established_resets: f64,
+    /// Segments sent (`OutSegs`).
+    pub segments_sent: f64,
+    /// Segments sent again because they weren't acknowledged in time (`RetransSegs`).
+    pub segments_retransmitted: f64,
+    /// Segments received with an error, like a bad checksum (`InErrs`).
+    pub receive_errors: f64,
+    /// Segments sent with the reset flag (`OutRsts`).
+    pub resets_sent: f64,
+}
+
+/// The activity of UDP, from `/proc/net/snmp` and `/proc/net/snmp6`, in events per second.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct UdpActivity {
+    /// Datagrams delivered to sockets (`InDatagrams`).
+    pub datagrams_received: f64,
+    /// Datagrams sent (`OutDatagrams`).
+    pub datagrams_sent: f64,
+    /// Datagrams received for a port without socket (`NoPorts`).
+    pub no_port: f64,
+    /// Datagrams which couldn't be delivered for another reason, like a full buffer (`InErrors`).
+    pub receive_errors: f64,
+    /// Datagrams dropped because the receive buffer of their socket was full (`RcvbufErrors`).
+    pub receive_buffer_errors: f64,
+    /// Datagrams dropped because the send buffer of their socket was full (`SndbufErrors`).
+    pub send_buffer_errors: f64,
+}
+
+/// How a CPU's time was spent since the previous refresh, in percent of the elapsed time.
+///
+/// It is returned by [`CpuExt::usage_breakdown`][crate::CpuExt::usage_breakdown].
+///
+/// ```no_run
+/// use sysinfo::{CpuExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// let usage = s.global_cpu_info().usage_breakdown();
+/// println!("iowait: {}%, steal: {}%", usage.iowait, usage.steal);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct CpuUsageBreakdown {
+    /// Time spent running processes in user mode, including guests.
+    pub user: f32,
+    /// Time spent running niced processes in user mode, including niced guests.
+    pub nice: f32,
+    /// Time spent in kernel mode.
+    pub system: f32,
+    /// Time spent idle, without waiting for I/O.
+    pub idle: f32,
+    /// Time spent idle while waiting for I/O to complete.
+    pub iowait: f32,
+    /// Time spent servicing hardware interrupts.
+    pub irq: f32,
+    /// Time spent servicing software interrupts.
+    pub softirq: f32,
+    /// Time stolen by the hypervisor to run other virtual machines.
+    pub steal: f32,
+    /// Time spent running guest virtual machines. Already counted in `user`.
+    pub guest: f32,
+    /// Time spent running niced guest virtual machines. Already counted in `nice`.
+    pub guest_nice: f32,
+}
+
 macro_rules! xid {
     ($(#[$outer:meta])+ $name:ident, $type:ty) => {
         $(#[$outer])+
         #[repr(transparent)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
         #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
         pub struct $name(pub(crate) $type);
 
@@ -741,7 +1774,8 @@ cfg_if::cfg_if! {
 /// let s = System::new_all();
 /// println!("users: {:?}", s.users());
 /// ```
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct User {
     pub(crate) uid: Uid,
     pub(crate) gid: Gid,
@@ -789,6 +1823,7 @@ impl UserExt for User {
 ///     );
 /// }
 /// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd)]
 pub struct DiskUsage {
     /// Total number of written bytes.
@@ -801,7 +1836,145 @@ pub struct DiskUsage {
     pub read_bytes: u64,
 }
 
+/// A file descriptor opened by a process.
+///
+/// It is returned by [`ProcessExt::open_files`][crate::ProcessExt::open_files].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, ProcessRefreshKind, RefreshKind, System, SystemExt};
+///
+/// let s = System::new_with_specifics(
+///     RefreshKind::new().with_processes(ProcessRefreshKind::new().with_open_files()),
+/// );
+/// for (pid, process) in s.processes() {
+///     for file in process.open_files().unwrap_or_default() {
+///         println!("[{}] {} {:?}: {}", pid, file.fd, file.kind, file.target.display());
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenFile {
+    /// The number of the file descriptor.
+    pub fd: u32,
+    /// What the file descriptor refers to.
+    pub kind: OpenFileKind,
+    /// The target of the file descriptor, like `/var/log/syslog`, `socket:[1234]`, `pipe:[5678]`
+    /// or `anon_inode:[eventfd]`.
+    pub target: PathBuf,
+}
+
+/// What a file descriptor refers to.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OpenFileKind {
+    /// A file, a directory or a device.
+    File,
+    /// A socket.
+    Socket,
+    /// A pipe or a FIFO.
+    Pipe,
+    /// A file without inode on a file system, like an `eventfd`, an `epoll` instance or a
+    /// `timerfd`.
+    AnonInode,
+    /// Anything else, like a namespace or a file of a BPF program.
+    Other,
+}
+
+impl OpenFileKind {
+    /// Gets the kind of a file descriptor from its target.
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use sysinfo::OpenFileKind;
+    ///
+    /// assert_eq!(OpenFileKind::of(Path::new("/dev/null")), OpenFileKind::File);
+    /// assert_eq!(OpenFileKind::of(Path::new("socket:[1234]")), OpenFileKind::Socket);
+    /// ```
+    pub fn of(target: &Path) -> Self {
+        let target = target.to_string_lossy();
+        if target.starts_with('/') {
+            OpenFileKind::File
+        } else if target.starts_with("socket:") {
+            OpenFileKind::Socket
+        } else if target.starts_with("pipe:") {
+            OpenFileKind::Pipe
+        } else if target.starts_with("anon_inode:") {
+            OpenFileKind::AnonInode
+        } else {
+            OpenFileKind::Other
+        }
+    }
+}
+
+/// The soft and hard limit of a resource. The soft limit is the one enforced, and a process can
+/// raise it up to the hard limit.
+///
+/// `None` means that there is no limit.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimit {
+    /// The limit enforced.
+    pub soft: Option<u64>,
+    /// The highest value the soft limit can be raised to.
+    pub hard: Option<u64>,
+}
+
+/// The resource limits of a process, as set with `setrlimit`.
+///
+/// It is returned by [`ProcessExt::limits`][crate::ProcessExt::limits].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, ProcessRefreshKind, RefreshKind, System, SystemExt};
+///
+/// let s = System::new_with_specifics(
+///     RefreshKind::new().with_processes(ProcessRefreshKind::new().with_limits()),
+/// );
+/// for (pid, process) in s.processes() {
+///     if let Some(limits) = process.limits() {
+///         println!("[{}] open files: {:?}", pid, limits.open_files);
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProcessLimits {
+    /// The CPU time, in seconds.
+    pub cpu_time: ResourceLimit,
+    /// The size of a file the process writes, in bytes.
+    pub file_size: ResourceLimit,
+    /// The size of the data segment, in bytes.
+    pub data_size: ResourceLimit,
+    /// The size of the stack, in bytes.
+    pub stack_size: ResourceLimit,
+    /// The size of a core dump, in bytes.
+    pub core_file_size: ResourceLimit,
+    /// The resident set size, in bytes. Current kernels ignore it.
+    pub resident_set: ResourceLimit,
+    /// The number of processes and threads of the user the process runs as.
+    pub processes: ResourceLimit,
+    /// The number of file descriptors. Opening more fails with `EMFILE`.
+    pub open_files: ResourceLimit,
+    /// The memory locked in RAM, in bytes.
+    pub locked_memory: ResourceLimit,
+    /// The virtual memory, in bytes.
+    pub address_space: ResourceLimit,
+    /// The number of file locks.
+    pub file_locks: ResourceLimit,
+    /// The number of signals queued for the user the process runs as.
+    pub pending_signals: ResourceLimit,
+    /// The size of the POSIX message queues of the user the process runs as, in bytes.
+    pub message_queue_size: ResourceLimit,
+    /// The ceiling of the nice value, as `20 - nice`.
+    pub nice_priority: ResourceLimit,
+    /// The realtime priority.
+    pub realtime_priority: ResourceLimit,
+    /// The CPU time a realtime process can use without blocking, in microseconds.
+    pub realtime_timeout: ResourceLimit,
+}
+
 /// Enum describing the different status of a process.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProcessStatus {
     /// ## Linux/FreeBSD
@@ -902,6 +2075,94 @@ pub enum ProcessStatus {
     Unknown(u32),
 }
 
+/// The information being refreshed when a [`RefreshError`] happened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefreshArea {
+    /// CPU usage and frequencies.
+    Cpu,
+    /// Memory and swap usage.
+    Memory,
+    /// The process list and the information about each process.
+    Processes,
+    /// The disk list.
+    Disks,
+    /// The network interface list.
+    Networks,
+    /// The user list.
+    Users,
+    /// Information about the whole system, like the uptime and load average.
+    System,
+    /// Pressure stall information.
+    Pressure,
+    /// The sockets and the counters of the network protocols.
+    Sockets,
+    /// The activity of the disks.
+    DisksIo,
+    /// The activity of the virtual memory subsystem.
+    MemoryActivity,
+}
+
+/// An error which happened while reading the system's information.
+///
+/// Refreshes keep going when a file can't be read, and leave the information it contains at its
+/// previous or default value. The errors are kept so they can be inspected with
+/// [`SystemExt::refresh_errors`][crate::SystemExt::refresh_errors]. They are only collected on
+/// Linux.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// for error in s.refresh_errors() {
+///     eprintln!("{}", error);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefreshError {
+    /// What was being refreshed.
+    pub area: RefreshArea,
+    /// The file or directory which couldn't be read.
+    pub path: PathBuf,
+    /// The process the file belongs to, if any.
+    pub pid: Option<Pid>,
+    /// The kind of error.
+    pub kind: io::ErrorKind,
+    /// The error number returned by the OS (`errno`), if the error came from the OS.
+    pub raw_os_error: Option<i32>,
+}
+
+impl RefreshError {
+    #[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+    pub(crate) fn new(
+        area: RefreshArea,
+        path: PathBuf,
+        pid: Option<Pid>,
+        error: &io::Error,
+    ) -> RefreshError {
+        RefreshError {
+            area,
+            path,
+            pid,
+            kind: error.kind(),
+            raw_os_error: error.raw_os_error(),
+        }
+    }
+}
+
+impl fmt::Display for RefreshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot read {}", self.path.display())?;
+        if let Some(pid) = self.pid {
+            write!(f, " for process {}", pid)?;
+        }
+        match self.raw_os_error {
+            Some(code) => write!(f, ": {}", io::Error::from_raw_os_error(code)),
+            None => write!(f, ": {:?}", self.kind),
+        }
+    }
+}
+
 /// Returns the pid for the current process.
 ///
 /// `Err` is returned in case the platform isn't supported.
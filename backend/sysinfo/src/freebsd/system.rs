@@ -2,7 +2,7 @@
 
 use crate::{
     sys::{component::Component, Cpu, Disk, Networks, Process},
-    CpuRefreshKind, LoadAvg, Pid, ProcessRefreshKind, RefreshKind, SystemExt, User,
+    CpuRefreshKind, LoadAvg, Pid, ProcessRefreshKind, RefreshKind, SocketStats, SystemExt, User,
 };
 
 use std::{
@@ -71,6 +71,7 @@ pub struct System {
     disks: Vec<Disk>,
     networks: Networks,
     users: Vec<User>,
+    sockets: SocketStats,
     boot_time: u64,
     system_info: SystemInfo,
     got_cpu_frequency: bool,
@@ -96,6 +97,7 @@ impl SystemExt for System {
             disks: Vec::with_capacity(1),
             networks: Networks::new(),
             users: Vec::new(),
+            sockets: SocketStats::default(),
             boot_time: boot_time(),
             system_info,
             got_cpu_frequency: false,
@@ -221,7 +223,8 @@ impl SystemExt for System {
     }
 
     fn refresh_users_list(&mut self) {
-        self.users = crate::users::get_users_list();
+        self.users = crate::users::get_users_list_from(std::path::Path::new("/etc/passwd"))
+            .unwrap_or_default();
     }
 
     // COMMON PART
@@ -344,6 +347,10 @@ impl SystemExt for System {
         &self.users
     }
 
+    fn sockets(&self) -> &SocketStats {
+        &self.sockets
+    }
+
     fn name(&self) -> Option<String> {
         self.system_info.get_os_name()
     }
@@ -59,8 +59,15 @@ cfg_if::cfg_if! {
 }
 
 pub use common::{
-    get_current_pid, CpuRefreshKind, DiskType, DiskUsage, Gid, LoadAvg, NetworksIter, Pid, PidExt,
-    ProcessRefreshKind, ProcessStatus, RefreshKind, Signal, Uid, User,
+    get_current_pid, CpuRefreshKind, CpuUsageBreakdown, DeviceId, DiskIo, DiskType, DiskUsage,
+    Duplex, Gid, IpNetwork, KernelActivity, ListeningSocket, LoadAvg, MacAddr, MemoryActivity,
+    MemoryDetails, NetworkSource, NetworksIter, OpenFile, OpenFileKind, OperationalState, Pid,
+    PidExt, Pressure, PressureStall, ProcessLimits, ProcessRefreshKind, ProcessStatus, RefreshArea,
+    RefreshError, RefreshKind, ResourceLimit, ResourcePressure, Signal, SocketProtocol,
+    SocketStats, SystemBuilder, TcpActivity, TcpState, UdpActivity, Uid, User,
+};
+pub use snapshot::{
+    ComponentSnapshot, CpuSnapshot, DiskSnapshot, NetworkSnapshot, ProcessSnapshot, SystemSnapshot,
 };
 pub use sys::{Component, Cpu, Disk, NetworkData, Networks, Process, System};
 pub use traits::{
@@ -74,6 +81,7 @@ pub use c_interface::*;
 mod c_interface;
 mod common;
 mod debug;
+mod snapshot;
 mod system;
 mod traits;
 mod utils;
@@ -160,9 +160,9 @@ impl ComponentExt for Component {
     }
 }
 
-pub(crate) fn get_components() -> Vec<Component> {
+pub(crate) fn get_components(sys_path: &Path) -> Vec<Component> {
     let mut components = Vec::with_capacity(10);
-    if let Ok(dir) = read_dir(&Path::new("/sys/class/hwmon/")) {
+    if let Ok(dir) = read_dir(sys_path.join("class/hwmon")) {
         for entry in dir.flatten() {
             let entry = entry.path();
             if !entry.is_dir()
@@ -178,14 +178,10 @@ pub(crate) fn get_components() -> Vec<Component> {
         }
         components.sort_by(|c1, c2| c1.label.to_lowercase().cmp(&c2.label.to_lowercase()));
     }
-    if is_file("/sys/class/thermal/thermal_zone0/temp") {
+    let thermal_zone = sys_path.join("class/thermal/thermal_zone0/temp");
+    if is_file(&thermal_zone) {
         // Specfic to raspberry pi.
-        components.push(Component::new(
-            "CPU".to_owned(),
-            Path::new("/sys/class/thermal/thermal_zone0/temp"),
-            None,
-            None,
-        ));
+        components.push(Component::new("CPU".to_owned(), &thermal_zone, None, None));
     }
     components
 }
@@ -4,7 +4,7 @@
 
 use std::{collections::HashSet, fs::File, io::Read, path::Path};
 
-use crate::CpuExt;
+use crate::{sys::utils::SystemPaths, CpuExt, CpuUsageBreakdown, KernelActivity};
 
 /// Struct containing values to compute a CPU usage.
 #[derive(Clone, Copy)]
@@ -17,8 +17,8 @@ pub(crate) struct CpuValues {
     irq: u64,
     softirq: u64,
     steal: u64,
-    _guest: u64,
-    _guest_nice: u64,
+    guest: u64,
+    guest_nice: u64,
 }
 
 impl CpuValues {
@@ -33,8 +33,8 @@ impl CpuValues {
             irq: 0,
             softirq: 0,
             steal: 0,
-            _guest: 0,
-            _guest_nice: 0,
+            guest: 0,
+            guest_nice: 0,
         }
     }
 
@@ -60,8 +60,8 @@ impl CpuValues {
             irq,
             softirq,
             steal,
-            _guest: guest,
-            _guest_nice: guest_nice,
+            guest,
+            guest_nice,
         }
     }
 
@@ -93,8 +93,8 @@ impl CpuValues {
         self.irq = irq;
         self.softirq = softirq;
         self.steal = steal;
-        self._guest = guest;
-        self._guest_nice = guest_nice;
+        self.guest = guest;
+        self.guest_nice = guest_nice;
     }
 
     /// Returns work time.
@@ -115,6 +115,30 @@ impl CpuValues {
             .saturating_add(self.idle)
             .saturating_add(self.iowait)
     }
+
+    /// Returns how the time elapsed since `old` was spent.
+    fn usage_breakdown(&self, old: &CpuValues) -> CpuUsageBreakdown {
+        let total = self.total_time().saturating_sub(old.total_time());
+        if total == 0 {
+            return CpuUsageBreakdown::default();
+        }
+        let percent = |new: u64, old: u64| {
+            // Counters going backwards (for example after a CPU went offline) count as nothing
+            (new.saturating_sub(old) as f32 / total as f32 * 100.).min(100.)
+        };
+        CpuUsageBreakdown {
+            user: percent(self.user, old.user),
+            nice: percent(self.nice, old.nice),
+            system: percent(self.system, old.system),
+            idle: percent(self.idle, old.idle),
+            iowait: percent(self.iowait, old.iowait),
+            irq: percent(self.irq, old.irq),
+            softirq: percent(self.softirq, old.softirq),
+            steal: percent(self.steal, old.steal),
+            guest: percent(self.guest, old.guest),
+            guest_nice: percent(self.guest_nice, old.guest_nice),
+        }
+    }
 }
 
 #[doc = include_str!("../../md_doc/cpu.md")]
@@ -123,6 +147,7 @@ pub struct Cpu {
     new_values: CpuValues,
     pub(crate) name: String,
     cpu_usage: f32,
+    usage_breakdown: CpuUsageBreakdown,
     total_time: u64,
     old_total_time: u64,
     pub(crate) frequency: u64,
@@ -154,6 +179,7 @@ impl Cpu {
                 user, nice, system, idle, iowait, irq, softirq, steal, guest, guest_nice,
             ),
             cpu_usage: 0f32,
+            usage_breakdown: CpuUsageBreakdown::default(),
             total_time: 0,
             old_total_time: 0,
             frequency,
@@ -196,6 +222,7 @@ impl Cpu {
         if self.cpu_usage > 100. {
             self.cpu_usage = 100.; // to prevent the percentage to go above 100%
         }
+        self.usage_breakdown = self.new_values.usage_breakdown(&self.old_values);
     }
 }
 
@@ -220,18 +247,81 @@ impl CpuExt for Cpu {
     fn brand(&self) -> &str {
         &self.brand
     }
+
+    fn usage_breakdown(&self) -> CpuUsageBreakdown {
+        self.usage_breakdown
+    }
 }
 
 pub(crate) fn get_raw_times(p: &Cpu) -> (u64, u64) {
     (p.total_time, p.old_total_time)
 }
 
-pub(crate) fn get_cpu_frequency(proc_path: &Path, cpu_core_index: usize) -> u64 {
+/// The cumulative counters of `/proc/stat` which are reported as rates.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct KernelCounters {
+    /// The total time of all CPUs, in clock ticks, which tells how much time elapsed.
+    total_time: u64,
+    context_switches: u64,
+    interrupts: u64,
+    forks: u64,
+}
+
+/// Reads the kernel activity from the lines of `/proc/stat` following the CPU lines.
+///
+/// `total_time` and `cpu_count` come from the CPU lines, and the rates are computed since `old`.
+pub(crate) fn parse_kernel_activity<I: Iterator<Item = Vec<u8>>>(
+    lines: I,
+    total_time: u64,
+    cpu_count: usize,
+    clock_cycle: u64,
+    old: Option<&KernelCounters>,
+) -> (KernelCounters, KernelActivity) {
+    let mut counters = KernelCounters {
+        total_time,
+        ..KernelCounters::default()
+    };
+    let mut activity = KernelActivity::default();
+    for line in lines {
+        let mut parts = line.split(|x| *x == b' ').filter(|s| !s.is_empty());
+        let field = parts.next().unwrap_or(&[]);
+        // For `intr`, the first value is the total and the others are per interrupt.
+        let value = parts
+            .next()
+            .and_then(|value| std::str::from_utf8(value).ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+        match field {
+            b"ctxt" => counters.context_switches = value,
+            b"intr" => counters.interrupts = value,
+            b"processes" => counters.forks = value,
+            b"procs_running" => activity.running_processes = value,
+            b"procs_blocked" => activity.blocked_processes = value,
+            _ => {}
+        }
+    }
+
+    if let Some(old) = old {
+        // Each CPU adds a clock tick to the total for every tick elapsed.
+        let elapsed = counters.total_time.saturating_sub(old.total_time) as f64
+            / cpu_count.max(1) as f64
+            / clock_cycle.max(1) as f64;
+        if elapsed > 0. {
+            let rate = |new: u64, old: u64| new.saturating_sub(old) as f64 / elapsed;
+            activity.context_switches = rate(counters.context_switches, old.context_switches);
+            activity.interrupts = rate(counters.interrupts, old.interrupts);
+            activity.forks = rate(counters.forks, old.forks);
+        }
+    }
+    (counters, activity)
+}
+
+pub(crate) fn get_cpu_frequency(paths: &SystemPaths, cpu_core_index: usize) -> u64 {
     let mut s = String::new();
-    if File::open(format!(
-        "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq",
+    if File::open(paths.sys.join(format!(
+        "devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq",
         cpu_core_index
-    ))
+    )))
     .and_then(|mut f| f.read_to_string(&mut s))
     .is_ok()
     {
@@ -243,7 +333,7 @@ pub(crate) fn get_cpu_frequency(proc_path: &Path, cpu_core_index: usize) -> u64
         }
     }
     s.clear();
-    if File::open(proc_path.join("cpuinfo"))
+    if File::open(paths.proc.join("cpuinfo"))
         .and_then(|mut f| f.read_to_string(&mut s))
         .is_err()
     {
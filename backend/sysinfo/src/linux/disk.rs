@@ -1,13 +1,20 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::{sys::utils::get_all_data, utils, DiskExt, DiskType};
+use crate::{
+    sys::utils::{get_all_data, RefreshErrors, SystemPaths},
+    utils, DeviceId, DiskExt, DiskType, RefreshArea, SystemBuilder,
+};
 
 use libc::statvfs;
 use std::{
+    collections::{HashMap, HashSet},
     ffi::{OsStr, OsString},
     fs, mem,
     os::unix::ffi::OsStrExt,
     path::{Path, PathBuf},
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, Instant},
 };
 
 macro_rules! cast {
@@ -16,6 +23,30 @@ macro_rules! cast {
     };
 }
 
+/// How long to wait before querying a mount point which didn't answer in time again. It doubles
+/// with each timeout in a row.
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+/// The longest time to wait before querying a mount point which didn't answer in time again.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(600);
+
+/// How the disks are listed and queried, see [`SystemBuilder::with_disk_timeout`] and
+/// [`SystemBuilder::without_network_disks`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DiskOptions {
+    /// How long to wait for the space of a mount point, forever if `None`.
+    timeout: Option<Duration>,
+    skip_network: bool,
+}
+
+impl DiskOptions {
+    pub(crate) fn new(builder: &SystemBuilder) -> Self {
+        DiskOptions {
+            timeout: builder.disk_timeout,
+            skip_network: builder.skip_network_disks,
+        }
+    }
+}
+
 #[doc = include_str!("../../md_doc/disk.md")]
 #[derive(PartialEq, Eq)]
 pub struct Disk {
@@ -23,9 +54,14 @@ pub struct Disk {
     device_name: OsString,
     file_system: Vec<u8>,
     mount_point: PathBuf,
-    total_space: u64,
-    available_space: u64,
+    /// Where the mount point can be queried, see [`SystemPaths::host_path`].
+    query_path: PathBuf,
+    space: Space,
     is_removable: bool,
+    mount_options: Vec<String>,
+    device_id: Option<DeviceId>,
+    is_unreachable: bool,
+    query: SpaceQuery,
 }
 
 impl DiskExt for Disk {
@@ -46,72 +82,306 @@ impl DiskExt for Disk {
     }
 
     fn total_space(&self) -> u64 {
-        self.total_space
+        self.space.total
     }
 
     fn available_space(&self) -> u64 {
-        self.available_space
+        self.space.available
     }
 
     fn is_removable(&self) -> bool {
         self.is_removable
     }
 
+    fn total_inodes(&self) -> u64 {
+        self.space.total_inodes
+    }
+
+    fn available_inodes(&self) -> u64 {
+        self.space.available_inodes
+    }
+
+    fn mount_options(&self) -> &[String] {
+        &self.mount_options
+    }
+
+    fn device_id(&self) -> Option<DeviceId> {
+        self.device_id
+    }
+
+    fn is_unreachable(&self) -> bool {
+        self.is_unreachable
+    }
+
     fn refresh(&mut self) -> bool {
-        unsafe {
-            let mut stat: statvfs = mem::zeroed();
-            let mount_point_cpath = utils::to_cpath(&self.mount_point);
-            if statvfs(mount_point_cpath.as_ptr() as *const _, &mut stat) == 0 {
-                let tmp = cast!(stat.f_bsize).saturating_mul(cast!(stat.f_bavail));
-                self.available_space = cast!(tmp);
+        match self.query.query(&self.query_path) {
+            Ok(Some(space)) => {
+                self.space.available = space.available;
+                self.space.available_inodes = space.available_inodes;
+                self.is_unreachable = false;
                 true
-            } else {
+            }
+            Ok(None) => {
+                self.is_unreachable = false;
+                false
+            }
+            // The last known space is kept
+            Err(Unreachable) => {
+                self.is_unreachable = true;
                 false
             }
         }
     }
 }
 
-fn new_disk(
-    device_name: &OsStr,
-    mount_point: &Path,
-    file_system: &[u8],
-    removable_entries: &[PathBuf],
-) -> Option<Disk> {
-    let mount_point_cpath = utils::to_cpath(mount_point);
-    let type_ = find_type_for_device_name(device_name);
-    let mut total = 0;
-    let mut available = 0;
-    unsafe {
-        let mut stat: statvfs = mem::zeroed();
-        if statvfs(mount_point_cpath.as_ptr() as *const _, &mut stat) == 0 {
+/// The space of a file system.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+struct Space {
+    total: u64,
+    available: u64,
+    total_inodes: u64,
+    available_inodes: u64,
+}
+
+impl Space {
+    /// Queries the file system mounted at `path`, which blocks for as long as it doesn't answer.
+    fn query(path: &Path) -> Option<Space> {
+        unsafe {
+            let mut stat: statvfs = mem::zeroed();
+            let cpath = utils::to_cpath(path);
+            if statvfs(cpath.as_ptr() as *const _, &mut stat) != 0 {
+                return None;
+            }
             let bsize = cast!(stat.f_bsize);
-            let blocks = cast!(stat.f_blocks);
-            let bavail = cast!(stat.f_bavail);
-            total = bsize.saturating_mul(blocks);
-            available = bsize.saturating_mul(bavail);
+            Some(Space {
+                total: bsize.saturating_mul(cast!(stat.f_blocks)),
+                available: bsize.saturating_mul(cast!(stat.f_bavail)),
+                total_inodes: cast!(stat.f_files),
+                available_inodes: cast!(stat.f_favail),
+            })
         }
-        if total == 0 {
-            return None;
+    }
+}
+
+/// A mount point which didn't answer in time.
+struct Unreachable;
+
+/// Queries the space of a mount point in another thread, so that a hung mount, like a network
+/// file system whose server is gone, doesn't block the refresh.
+///
+/// A mount point which doesn't answer in time is queried again after [`RETRY_DELAY`], doubled
+/// with each timeout in a row, and never while its previous query is still blocked, so it keeps
+/// at most one thread.
+struct SpaceQuery {
+    timeout: Option<Duration>,
+    /// The query which didn't answer in time. The mutex only makes the [`Disk`] `Sync`.
+    pending: Option<Mutex<mpsc::Receiver<Option<Space>>>>,
+    /// The number of timeouts in a row.
+    timeouts: u32,
+    retry_at: Option<Instant>,
+}
+
+// The state of the queries isn't part of the disk.
+impl PartialEq for SpaceQuery {
+    fn eq(&self, other: &Self) -> bool {
+        self.timeout == other.timeout
+    }
+}
+
+impl Eq for SpaceQuery {}
+
+impl SpaceQuery {
+    fn new(timeout: Option<Duration>) -> Self {
+        SpaceQuery {
+            timeout,
+            pending: None,
+            timeouts: 0,
+            retry_at: None,
+        }
+    }
+
+    fn query(&mut self, path: &Path) -> Result<Option<Space>, Unreachable> {
+        self.query_with(path, Space::query)
+    }
+
+    /// Same as [`SpaceQuery::query`], with `query` asking the file system for its space.
+    fn query_with<F>(&mut self, path: &Path, query: F) -> Result<Option<Space>, Unreachable>
+    where
+        F: FnOnce(&Path) -> Option<Space> + Send + 'static,
+    {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return Ok(query(path)),
+        };
+        if let Some(mut pending) = self.pending.take() {
+            match pending.get_mut().map(|receiver| receiver.try_recv()) {
+                // A late answer is still an answer
+                Ok(Ok(space)) => {
+                    self.timeouts = 0;
+                    self.retry_at = None;
+                    return Ok(space);
+                }
+                Ok(Err(mpsc::TryRecvError::Empty)) => {
+                    self.pending = Some(pending);
+                    return Err(Unreachable);
+                }
+                _ => {}
+            }
+        }
+        if self
+            .retry_at
+            .map_or(false, |retry_at| Instant::now() < retry_at)
+        {
+            return Err(Unreachable);
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let path = path.to_owned();
+        let spawned = thread::Builder::new()
+            .name("sysinfo-statvfs".to_owned())
+            .spawn(move || {
+                let _ = sender.send(query(&path));
+            });
+        if spawned.is_err() {
+            sysinfo_debug!("Cannot spawn a thread to query the space of a disk");
+            return Err(Unreachable);
+        }
+        match receiver.recv_timeout(timeout) {
+            Ok(space) => {
+                self.timeouts = 0;
+                self.retry_at = None;
+                Ok(space)
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                self.pending = Some(Mutex::new(receiver));
+                self.timeouts = self.timeouts.saturating_add(1);
+                self.retry_at = Some(Instant::now() + retry_delay(self.timeouts));
+                Err(Unreachable)
+            }
+            // The query panicked
+            Err(mpsc::RecvTimeoutError::Disconnected) => Ok(None),
         }
-        let mount_point = mount_point.to_owned();
-        let is_removable = removable_entries
-            .iter()
-            .any(|e| e.as_os_str() == device_name);
-        Some(Disk {
-            type_,
-            device_name: device_name.to_owned(),
-            file_system: file_system.to_owned(),
-            mount_point,
-            total_space: cast!(total),
-            available_space: cast!(available),
-            is_removable,
-        })
     }
 }
 
+/// The delay before querying a mount point again after the given number of timeouts in a row.
+fn retry_delay(timeouts: u32) -> Duration {
+    RETRY_DELAY
+        .checked_mul(1 << timeouts.saturating_sub(1).min(16))
+        .map_or(MAX_RETRY_DELAY, |delay| delay.min(MAX_RETRY_DELAY))
+}
+
+/// A line of `/proc/<pid>/mountinfo`.
+struct Mount<'a> {
+    spec: &'a str,
+    mount_point: String,
+    file_system: &'a str,
+    options: &'a str,
+    /// The device of the file system, as known to the kernel rather than to the `stat` of the
+    /// mount point, which depends on what is mounted over it.
+    device_id: DeviceId,
+}
+
+/// Parses a line of `/proc/<pid>/mountinfo`, see
+/// https://man7.org/linux/man-pages/man5/proc.5.html
+///
+/// ```text
+/// 36 35 98:0 /mnt1 /mnt/parent rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+/// ```
+fn parse_mountinfo_line(line: &str) -> Option<Mount<'_>> {
+    let mut fields = line.split_whitespace().skip(2);
+    let mut device_id = fields.next()?.split(':');
+    let device_id = DeviceId {
+        major: device_id.next()?.parse().ok()?,
+        minor: device_id.next()?.parse().ok()?,
+    };
+    // The root of the mount within the file system, which is only interesting for bind mounts
+    let _root = fields.next()?;
+    let mount_point = unescape(fields.next()?);
+    let options = fields.next()?;
+    // Skip the optional fields, whose end is marked by a single hyphen
+    let mut fields = fields.skip_while(|field| *field != "-").skip(1);
+    Some(Mount {
+        file_system: fields.next()?,
+        spec: fields.next()?,
+        mount_point,
+        options,
+        device_id,
+    })
+}
+
+/// Mount points have their spaces, tabs, new lines and backslashes escaped.
+fn unescape(mount_point: &str) -> String {
+    mount_point
+        .replace("\\134", "\\")
+        .replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+}
+
+/// File systems whose mounts are served by another machine.
+fn is_network_file_system(file_system: &str) -> bool {
+    matches!(
+        file_system,
+        "nfs"
+            | "nfs4"
+            | "cifs"
+            | "smb3"
+            | "smbfs"
+            | "ncpfs"
+            | "afs"
+            | "ceph"
+            | "glusterfs"
+            | "lustre"
+            | "davfs"
+            | "fuse.sshfs"
+            | "fuse.glusterfs"
+            | "fuse.s3fs"
+    )
+}
+
+/// Creates the disk mounted by `mount`. The disk previously listed at the same mount point, if
+/// any, gives the state of its queries and its last known space in case it doesn't answer.
+fn new_disk(
+    paths: &SystemPaths,
+    options: &DiskOptions,
+    mount: &Mount,
+    removable_entries: &[PathBuf],
+    previous: Option<Disk>,
+) -> Option<Disk> {
+    let device_name = OsStr::new(mount.spec);
+    let mount_point = Path::new(&mount.mount_point);
+    let query_path = paths.host_path(mount_point);
+    let (mut query, last_space) = match previous {
+        Some(previous) => (previous.query, previous.space),
+        None => (SpaceQuery::new(options.timeout), Space::default()),
+    };
+    let (space, is_unreachable) = match query.query(&query_path) {
+        Ok(Some(space)) if space.total > 0 => (space, false),
+        Ok(_) => return None,
+        // It is kept so that it shows up as unreachable
+        Err(Unreachable) => (last_space, true),
+    };
+    let is_removable = removable_entries
+        .iter()
+        .any(|e| e.as_os_str() == device_name);
+    Some(Disk {
+        type_: find_type_for_device_name(paths, device_name),
+        device_name: device_name.to_owned(),
+        file_system: mount.file_system.as_bytes().to_owned(),
+        mount_point: mount_point.to_owned(),
+        query_path,
+        space,
+        is_removable,
+        mount_options: mount.options.split(',').map(str::to_owned).collect(),
+        device_id: Some(mount.device_id),
+        is_unreachable,
+        query,
+    })
+}
+
 #[allow(clippy::manual_range_contains)]
-fn find_type_for_device_name(device_name: &OsStr) -> DiskType {
+fn find_type_for_device_name(paths: &SystemPaths, device_name: &OsStr) -> DiskType {
     // The format of devices are as follows:
     //  - device_name is symbolic link in the case of /dev/mapper/
     //     and /dev/root, and the target is corresponding device under
@@ -123,12 +393,14 @@ fn find_type_for_device_name(device_name: &OsStr) -> DiskType {
     //  - In the case of /dev/mmcblk, the format is /dev/mmcblk[0-9]p[0-9],
     //     corresponding to /sys/block/mmcblk[0-9]
     let device_name_path = device_name.to_str().unwrap_or_default();
-    let real_path = fs::canonicalize(device_name).unwrap_or_else(|_| PathBuf::from(device_name));
+    let real_path = paths
+        .canonicalize_dev(Path::new(device_name))
+        .unwrap_or_else(|| PathBuf::from(device_name));
     let mut real_path = real_path.to_str().unwrap_or_default();
     if device_name_path.starts_with("/dev/mapper/") {
         // Recursively solve, for example /dev/dm-0
         if real_path != device_name_path {
-            return find_type_for_device_name(OsStr::new(&real_path));
+            return find_type_for_device_name(paths, OsStr::new(&real_path));
         }
     } else if device_name_path.starts_with("/dev/sd") || device_name_path.starts_with("/dev/vd") {
         // Turn "sda1" into "sda" or "vda1" into "vda"
@@ -142,7 +414,7 @@ fn find_type_for_device_name(device_name: &OsStr) -> DiskType {
     } else if device_name_path.starts_with("/dev/root") {
         // Recursively solve, for example /dev/mmcblk0p1
         if real_path != device_name_path {
-            return find_type_for_device_name(OsStr::new(&real_path));
+            return find_type_for_device_name(paths, OsStr::new(&real_path));
         }
     } else if device_name_path.starts_with("/dev/mmcblk") {
         // Turn "mmcblk0p1" into "mmcblk0"
@@ -157,8 +429,9 @@ fn find_type_for_device_name(device_name: &OsStr) -> DiskType {
 
     let trimmed: &OsStr = OsStrExt::from_bytes(real_path.as_bytes());
 
-    let path = Path::new("/sys/block/")
-        .to_owned()
+    let path = paths
+        .sys
+        .join("block")
         .join(trimmed)
         .join("queue/rotational");
     // Normally, this file only contains '0' or '1' but just in case, we get 8 bytes...
@@ -179,10 +452,15 @@ fn find_type_for_device_name(device_name: &OsStr) -> DiskType {
     }
 }
 
-fn get_all_disks_inner(content: &str) -> Vec<Disk> {
+fn get_all_disks_inner<'a>(
+    paths: &SystemPaths,
+    options: &DiskOptions,
+    mounts: impl Iterator<Item = Mount<'a>>,
+    previous: Vec<Disk>,
+) -> Vec<Disk> {
     // The goal of this array is to list all removable devices (the ones whose name starts with
     // "usb-"). Then we check if
-    let removable_entries = match fs::read_dir("/dev/disk/by-id/") {
+    let removable_entries = match fs::read_dir(paths.dev.join("disk/by-id")) {
         Ok(r) => r
             .filter_map(|res| Some(res.ok()?.path()))
             .filter_map(|e| {
@@ -190,7 +468,7 @@ fn get_all_disks_inner(content: &str) -> Vec<Disk> {
                     .and_then(|x| Some(x.to_str()?.starts_with("usb-")))
                     .unwrap_or_default()
                 {
-                    e.canonicalize().ok()
+                    paths.canonicalize_dev(&Path::new("/dev/disk/by-id").join(e.file_name()?))
                 } else {
                     None
                 }
@@ -198,30 +476,19 @@ fn get_all_disks_inner(content: &str) -> Vec<Disk> {
             .collect::<Vec<PathBuf>>(),
         _ => Vec::new(),
     };
+    let mut previous: HashMap<PathBuf, Disk> = previous
+        .into_iter()
+        .map(|disk| (disk.mount_point.clone(), disk))
+        .collect();
+    let mut seen = HashSet::new();
 
-    content
-        .lines()
-        .map(|line| {
-            let line = line.trim_start();
-            // mounts format
-            // http://man7.org/linux/man-pages/man5/fstab.5.html
-            // fs_spec<tab>fs_file<tab>fs_vfstype<tab>other fields
-            let mut fields = line.split_whitespace();
-            let fs_spec = fields.next().unwrap_or("");
-            let fs_file = fields
-                .next()
-                .unwrap_or("")
-                .replace("\\134", "\\")
-                .replace("\\040", " ")
-                .replace("\\011", "\t")
-                .replace("\\012", "\n");
-            let fs_vfstype = fields.next().unwrap_or("");
-            (fs_spec, fs_file, fs_vfstype)
-        })
-        .filter(|(fs_spec, fs_file, fs_vfstype)| {
+    mounts
+        .filter(|mount| {
+            let fs_spec = mount.spec;
+            let fs_file = &mount.mount_point;
             // Check if fs_vfstype is one of our 'ignored' file systems.
             let filtered = matches!(
-                *fs_vfstype,
+                mount.file_system,
                 "rootfs" | // https://www.kernel.org/doc/Documentation/filesystems/ramfs-rootfs-initramfs.txt
                 "sysfs" | // pseudo file system for kernel objects
                 "proc" |  // another pseudo file system
@@ -239,21 +506,39 @@ fn get_all_disks_inner(content: &str) -> Vec<Disk> {
                fs_file.starts_with("/sys") || // check if fs_file is an 'ignored' mount point
                fs_file.starts_with("/proc") ||
                (fs_file.starts_with("/run") && !fs_file.starts_with("/run/media")) ||
-               fs_spec.starts_with("sunrpc"))
+               fs_spec.starts_with("sunrpc") ||
+               (options.skip_network && is_network_file_system(mount.file_system)))
         })
-        .filter_map(|(fs_spec, fs_file, fs_vfstype)| {
-            new_disk(
-                fs_spec.as_ref(),
-                Path::new(&fs_file),
-                fs_vfstype.as_bytes(),
-                &removable_entries,
-            )
+        .filter_map(|mount| {
+            let disk = previous.remove(Path::new(&mount.mount_point));
+            new_disk(paths, options, &mount, &removable_entries, disk)
         })
+        // Bind mounts and mounts of the same file system in several places would be counted more
+        // than once. The first one is kept, which is usually the original mount.
+        .filter(|disk| seen.insert((disk.device_name.clone(), disk.device_id)))
         .collect()
 }
 
-pub(crate) fn get_all_disks(proc_path: &Path) -> Vec<Disk> {
-    get_all_disks_inner(&get_all_data(proc_path.join("mounts"), 16_385).unwrap_or_default())
+/// Lists the disks, reusing the state of the `previous` ones.
+pub(crate) fn get_all_disks(
+    paths: &SystemPaths,
+    options: &DiskOptions,
+    errors: &RefreshErrors,
+    previous: Vec<Disk>,
+) -> Vec<Disk> {
+    // Inside a container, the mount table of the current process is the one of the container.
+    // The one of the host is the mount table of its init process.
+    let path = if paths.host_root.is_some() {
+        paths.proc.join("1/mountinfo")
+    } else {
+        paths.proc.join("self/mountinfo")
+    };
+    let content = get_all_data(&path, 16_385).unwrap_or_else(|e| {
+        errors.record(RefreshArea::Disks, &path, None, &e);
+        String::new()
+    });
+    let mounts = content.lines().filter_map(parse_mountinfo_line);
+    get_all_disks_inner(paths, options, mounts, previous)
 }
 
 // #[test]
@@ -290,3 +575,86 @@ pub(crate) fn get_all_disks(proc_path: &Path) -> Vec<Disk> {
 //         }
 //     );
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACE: Space = Space {
+        total: 1000,
+        available: 500,
+        total_inodes: 100,
+        available_inodes: 50,
+    };
+
+    #[test]
+    fn retry_delay_doubles_up_to_max() {
+        assert_eq!(retry_delay(1), Duration::from_secs(10));
+        assert_eq!(retry_delay(2), Duration::from_secs(20));
+        assert_eq!(retry_delay(3), Duration::from_secs(40));
+        assert_eq!(retry_delay(6), Duration::from_secs(320));
+        assert_eq!(retry_delay(7), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn space_query_timeout_then_late_answer() {
+        let mut query = SpaceQuery::new(Some(Duration::from_millis(10)));
+        let path = Path::new("/mnt/nas");
+
+        // The file system hangs until `answer` is sent to
+        let (answer, blocked) = mpsc::channel::<()>();
+        let result = query.query_with(path, move |_| {
+            let _ = blocked.recv();
+            Some(SPACE)
+        });
+        assert!(matches!(result, Err(Unreachable)));
+        assert_eq!(query.timeouts, 1);
+        assert!(query.retry_at.is_some());
+
+        // Still blocked, so it isn't queried again
+        let result = query.query_with(path, |_| panic!("queried while the query is blocked"));
+        assert!(matches!(result, Err(Unreachable)));
+
+        // The late answer makes the mount point reachable again
+        answer.send(()).unwrap();
+        let mut result = Err(Unreachable);
+        for _ in 0..100 {
+            result = query.query_with(path, |_| panic!("queried before reading the late answer"));
+            if result.is_ok() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(result.ok(), Some(Some(SPACE)));
+        assert_eq!(query.timeouts, 0);
+        assert_eq!(query.retry_at, None);
+
+        // And it is queried on the next refresh instead of waiting for the retry delay
+        let result = query.query_with(path, |_| Some(SPACE));
+        assert_eq!(result.ok(), Some(Some(SPACE)));
+    }
+
+    #[test]
+    fn space_query_waits_before_retrying() {
+        let mut query = SpaceQuery::new(Some(Duration::from_millis(10)));
+        let path = Path::new("/mnt/nas");
+
+        let (_answer, blocked) = mpsc::channel::<()>();
+        let result = query.query_with(path, move |_| {
+            let _ = blocked.recv();
+            None
+        });
+        assert!(matches!(result, Err(Unreachable)));
+
+        // Once the hung query is dropped, the mount point is only queried again after the delay
+        query.pending = None;
+        let result = query.query_with(path, |_| panic!("queried before the retry delay"));
+        assert!(matches!(result, Err(Unreachable)));
+
+        query.retry_at = Some(Instant::now());
+        let result = query.query_with(path, |_| Some(SPACE));
+        assert_eq!(result.ok(), Some(Some(SPACE)));
+        assert_eq!(query.timeouts, 0);
+    }
+}
@@ -0,0 +1,150 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    sys::utils::{get_all_data, get_precise_uptime, RefreshErrors, SystemPaths},
+    Disk, DiskExt, DiskIo, RefreshArea,
+};
+
+/// `/proc/diskstats` counts sectors of 512 bytes, whatever the sector size of the device is.
+const SECTOR_SIZE: u64 = 512;
+
+/// The counters of `/proc/diskstats` used to compute the [`DiskIo`] of each device, along with
+/// the uptime they were read at.
+#[derive(Default, Debug, Clone)]
+pub(crate) struct DiskCounters {
+    uptime: f64,
+    devices: HashMap<String, DeviceCounters>,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct DeviceCounters {
+    reads: u64,
+    read_sectors: u64,
+    read_time: u64,
+    writes: u64,
+    written_sectors: u64,
+    write_time: u64,
+    io_time: u64,
+}
+
+/// Reads `/proc/diskstats` and computes the rates since the `old` counters, which are replaced
+/// by the new ones. The mount points are taken from `disks`.
+pub(crate) fn get_disks_io(
+    paths: &SystemPaths,
+    errors: &RefreshErrors,
+    disks: &[Disk],
+    old: &mut Option<DiskCounters>,
+) -> Vec<DiskIo> {
+    let uptime = get_precise_uptime(&paths.proc).map_err(|e| {
+        errors.record(RefreshArea::DisksIo, &paths.proc.join("uptime"), None, &e);
+    });
+    let path = paths.proc.join("diskstats");
+    let data = get_all_data(&path, 16_385).map_err(|e| {
+        errors.record(RefreshArea::DisksIo, &path, None, &e);
+    });
+    let (uptime, data) = match (uptime, data) {
+        (Ok(uptime), Ok(data)) => (uptime, data),
+        _ => return Vec::new(),
+    };
+
+    let mount_points: Vec<(String, &Path)> = disks
+        .iter()
+        .filter_map(|disk| Some((block_device_name(paths, disk.name())?, disk.mount_point())))
+        .collect();
+    let elapsed = old.as_ref().map_or(0., |old| uptime - old.uptime);
+    let mut counters = DiskCounters {
+        uptime,
+        devices: HashMap::new(),
+    };
+    let mut disks_io = Vec::new();
+    for (name, device) in data.lines().filter_map(parse_device) {
+        // Devices which were never used, like most loop and RAM devices, are only noise
+        if device.reads == 0 && device.writes == 0 {
+            continue;
+        }
+        let mut io = DiskIo {
+            name: name.to_owned(),
+            mount_points: mount_points
+                .iter()
+                .filter(|(device_name, _)| *device_name == name)
+                .map(|(_, mount_point)| mount_point.to_path_buf())
+                .collect(),
+            ..DiskIo::default()
+        };
+        if elapsed > 0. {
+            if let Some(old) = old.as_ref().and_then(|old| old.devices.get(name)) {
+                set_rates(&mut io, &device, old, elapsed);
+            }
+        }
+        counters.devices.insert(io.name.clone(), device);
+        disks_io.push(io);
+    }
+    *old = Some(counters);
+    disks_io
+}
+
+fn set_rates(io: &mut DiskIo, new: &DeviceCounters, old: &DeviceCounters, elapsed: f64) {
+    let delta = |new: u64, old: u64| new.saturating_sub(old);
+    let reads = delta(new.reads, old.reads);
+    let writes = delta(new.writes, old.writes);
+    io.reads = reads as f64 / elapsed;
+    io.writes = writes as f64 / elapsed;
+    io.read_bytes =
+        delta(new.read_sectors, old.read_sectors).saturating_mul(SECTOR_SIZE) as f64 / elapsed;
+    io.written_bytes = delta(new.written_sectors, old.written_sectors).saturating_mul(SECTOR_SIZE)
+        as f64
+        / elapsed;
+    if reads + writes > 0 {
+        let time = delta(new.read_time, old.read_time) + delta(new.write_time, old.write_time);
+        io.await_time = time as f64 / (reads + writes) as f64;
+    }
+    // The time spent doing I/O is in milliseconds. It can go slightly over the elapsed time
+    // since the two aren't read at the same instant.
+    io.utilization = (delta(new.io_time, old.io_time) as f64 / elapsed / 10.).min(100.);
+}
+
+/// Parses a line like:
+///
+/// ```text
+///    8       0 sda 1000 10 40000 2000 500 20 16000 3000 0 4000 5000
+/// ```
+///
+/// Newer kernels add discard and flush counters at the end, which aren't used.
+fn parse_device(line: &str) -> Option<(&str, DeviceCounters)> {
+    let mut fields = line.split_whitespace().skip(2);
+    let name = fields.next()?;
+    let values = fields
+        .take(10)
+        .map(|value| value.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if values.len() < 10 {
+        return None;
+    }
+    Some((
+        name,
+        DeviceCounters {
+            reads: values[0],
+            read_sectors: values[2],
+            read_time: values[3],
+            writes: values[4],
+            written_sectors: values[6],
+            write_time: values[7],
+            io_time: values[9],
+        },
+    ))
+}
+
+/// Turns a device path like `/dev/mapper/root` into the name of the block device in
+/// `/proc/diskstats`, like `dm-0`.
+fn block_device_name(paths: &SystemPaths, device: &OsStr) -> Option<String> {
+    let path = paths
+        .canonicalize_dev(Path::new(device))
+        .unwrap_or_else(|| PathBuf::from(device));
+    Some(path.strip_prefix("/dev").ok()?.to_str()?.to_owned())
+}
@@ -0,0 +1,114 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::path::Path;
+
+use crate::{
+    sys::utils::{get_all_data, get_precise_uptime, RefreshErrors},
+    MemoryActivity, RefreshArea,
+};
+
+/// The counters of `/proc/vmstat` used to compute a [`MemoryActivity`], along with the uptime
+/// they were read at.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct VmCounters {
+    uptime: f64,
+    page_faults: u64,
+    major_page_faults: u64,
+    pages_swapped_in: u64,
+    pages_swapped_out: u64,
+    pages_scanned: u64,
+    pages_stolen: u64,
+}
+
+/// Reads `/proc/vmstat` and computes the rates since the `old` counters, which are replaced by
+/// the new ones.
+pub(crate) fn get_memory_activity(
+    proc_path: &Path,
+    errors: &RefreshErrors,
+    old: &mut Option<VmCounters>,
+) -> MemoryActivity {
+    let uptime = get_precise_uptime(proc_path).map_err(|e| {
+        errors.record(
+            RefreshArea::MemoryActivity,
+            &proc_path.join("uptime"),
+            None,
+            &e,
+        );
+    });
+    let path = proc_path.join("vmstat");
+    let data = get_all_data(&path, 8_192).map_err(|e| {
+        errors.record(RefreshArea::MemoryActivity, &path, None, &e);
+    });
+    let (uptime, data) = match (uptime, data) {
+        (Ok(uptime), Ok(data)) => (uptime, data),
+        _ => return MemoryActivity::default(),
+    };
+    let (counters, oom_kills) = parse_vmstat(&data, uptime);
+    let mut activity = MemoryActivity {
+        oom_kills,
+        ..MemoryActivity::default()
+    };
+    if let Some(old) = old.as_ref() {
+        let elapsed = counters.uptime - old.uptime;
+        if elapsed > 0. {
+            let rate = |new: u64, old: u64| new.saturating_sub(old) as f64 / elapsed;
+            activity.page_faults = rate(counters.page_faults, old.page_faults);
+            activity.major_page_faults = rate(counters.major_page_faults, old.major_page_faults);
+            activity.pages_swapped_in = rate(counters.pages_swapped_in, old.pages_swapped_in);
+            activity.pages_swapped_out = rate(counters.pages_swapped_out, old.pages_swapped_out);
+            activity.pages_scanned = rate(counters.pages_scanned, old.pages_scanned);
+            activity.pages_stolen = rate(counters.pages_stolen, old.pages_stolen);
+        }
+    }
+    *old = Some(counters);
+    activity
+}
+
+/// Parses a file of `name value` lines like:
+///
+/// ```text
+/// pgfault 123456
+/// pgmajfault 789
+/// pgscan_kswapd 1000
+/// oom_kill 0
+/// ```
+///
+/// It returns the counters and the number of OOM kills.
+fn parse_vmstat(data: &str, uptime: f64) -> (VmCounters, u64) {
+    let mut counters = VmCounters {
+        uptime,
+        ..VmCounters::default()
+    };
+    let mut oom_kills = 0;
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let (name, value) = match (parts.next(), parts.next().map(str::parse::<u64>)) {
+            (Some(name), Some(Ok(value))) => (name, value),
+            _ => continue,
+        };
+        match name {
+            "pgfault" => counters.page_faults = value,
+            "pgmajfault" => counters.major_page_faults = value,
+            "pswpin" => counters.pages_swapped_in = value,
+            "pswpout" => counters.pages_swapped_out = value,
+            "oom_kill" => oom_kills = value,
+            // It counts throttling events, not pages.
+            "pgscan_direct_throttle" => {}
+            // Older kernels have one counter per memory zone, like `pgscan_kswapd_normal`. Newer
+            // ones also count the same pages in `pgscan_anon` and `pgscan_file`, which are skipped
+            // to not count them twice.
+            _ if is_reclaimer(name, "pgscan_") => counters.pages_scanned += value,
+            _ if is_reclaimer(name, "pgsteal_") => counters.pages_stolen += value,
+            _ => {}
+        }
+    }
+    (counters, oom_kills)
+}
+
+fn is_reclaimer(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix).map_or(false, |reclaimer| {
+        reclaimer.starts_with("kswapd")
+            || reclaimer.starts_with("direct")
+            || reclaimer.starts_with("khugepaged")
+    })
+}
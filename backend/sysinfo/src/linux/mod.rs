@@ -3,8 +3,12 @@
 pub mod component;
 pub mod cpu;
 pub mod disk;
+pub(crate) mod disk_io;
+pub(crate) mod memory;
 pub mod network;
+pub(crate) mod pressure;
 pub mod process;
+pub(crate) mod socket;
 pub mod system;
 pub(crate) mod utils;
 
@@ -1,20 +1,42 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{fs::File, io::Read, path::Path};
-
-use crate::{NetworkExt, NetworksExt, NetworksIter};
+use std::{
+    convert::TryFrom,
+    ffi::CStr,
+    fs::File,
+    io::{self, Read},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::{Path, PathBuf},
+    ptr,
+    sync::Arc,
+};
+
+use crate::{
+    sys::utils::{get_all_data, get_precise_uptime, RefreshErrors},
+    Duplex, IpNetwork, MacAddr, NetworkExt, NetworkSource, NetworksExt, NetworksIter,
+    OperationalState, RefreshArea,
+};
 use std::collections::{hash_map, HashMap};
 
 #[doc = include_str!("../../md_doc/networks.md")]
 pub struct Networks {
     interfaces: HashMap<String, NetworkData>,
+    /// Usually `/sys/class/net`.
+    sysfs_net: PathBuf,
+    /// `net/dev` under the `/proc` path when the interfaces are read from there instead of
+    /// `sysfs_net`.
+    net_dev: Option<PathBuf>,
+    /// Whether the interfaces are those of the network namespace of the process, whose addresses
+    /// are known.
+    own_namespace: bool,
+    /// Usually `/proc`, where the uptime used to compute the rates is read.
+    proc: PathBuf,
+    /// The uptime of the previous refresh.
+    uptime: Option<f64>,
+    errors: Arc<RefreshErrors>,
 }
 
 macro_rules! old_and_new {
-    ($ty_:expr, $name:ident, $old:ident) => {{
-        $ty_.$old = $ty_.$name;
-        $ty_.$name = $name;
-    }};
     ($ty_:expr, $name:ident, $old:ident, $path:expr) => {{
         let _tmp = $path;
         $ty_.$old = $ty_.$name;
@@ -41,78 +63,326 @@ fn read<P: AsRef<Path>>(parent: P, path: &str, data: &mut Vec<u8>) -> u64 {
 }
 
 impl Networks {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(
+        sys_path: &Path,
+        proc_path: &Path,
+        source: NetworkSource,
+        errors: Arc<RefreshErrors>,
+    ) -> Self {
         Networks {
             interfaces: HashMap::new(),
+            sysfs_net: sys_path.join("class/net"),
+            net_dev: match source {
+                NetworkSource::Sysfs => None,
+                NetworkSource::ProcNetDev => Some(proc_path.join("net/dev")),
+                NetworkSource::HostProcNetDev => Some(proc_path.join("1/net/dev")),
+            },
+            own_namespace: source != NetworkSource::HostProcNetDev,
+            proc: proc_path.to_owned(),
+            uptime: None,
+            errors,
+        }
+    }
+
+    fn ip_networks(&self) -> HashMap<String, Vec<IpNetwork>> {
+        if self.own_namespace {
+            get_ip_networks()
+        } else {
+            HashMap::new()
+        }
+    }
+
+    /// Gives each interface the time elapsed since the previous refresh of the counters.
+    fn update_elapsed(&mut self) {
+        let path = self.proc.join("uptime");
+        let uptime = match get_precise_uptime(&self.proc) {
+            Ok(uptime) => {
+                self.errors.resolve(RefreshArea::Networks, &path);
+                Some(uptime)
+            }
+            Err(e) => {
+                self.errors.record(RefreshArea::Networks, &path, None, &e);
+                None
+            }
+        };
+        let elapsed = match (uptime, self.uptime) {
+            (Some(new), Some(old)) if new > old => new - old,
+            _ => 0.,
+        };
+        self.uptime = uptime;
+        for data in self.interfaces.values_mut() {
+            data.elapsed = elapsed;
         }
     }
 }
 
-fn refresh_networks_list_from_sysfs(
-    interfaces: &mut HashMap<String, NetworkData>,
-    sysfs_net: &Path,
-) {
-    if let Ok(dir) = std::fs::read_dir(sysfs_net) {
-        let mut data = vec![0; 30];
+/// The state of the link of an interface, read from its sysfs attributes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Link {
+    mac_address: MacAddr,
+    mtu: u64,
+    operational_state: OperationalState,
+    carrier: Option<bool>,
+    duplex: Duplex,
+    speed: Option<u64>,
+}
 
-        for stats in interfaces.values_mut() {
-            stats.updated = false;
+impl Link {
+    /// Reads the attributes of `/sys/class/net/<interface>`. Some of them can't be read while the
+    /// interface is down or for virtual interfaces, and are left unknown.
+    fn read(interface: &Path) -> Link {
+        let read = |name: &str| {
+            get_all_data(interface.join(name), 64)
+                .ok()
+                .map(|value| value.trim().to_owned())
+        };
+        Link {
+            mac_address: read("address")
+                .and_then(|address| parse_mac_address(&address))
+                .unwrap_or_default(),
+            mtu: read("mtu").and_then(|mtu| mtu.parse().ok()).unwrap_or(0),
+            operational_state: match read("operstate").as_deref() {
+                Some("notpresent") => OperationalState::NotPresent,
+                Some("down") => OperationalState::Down,
+                Some("lowerlayerdown") => OperationalState::LowerLayerDown,
+                Some("testing") => OperationalState::Testing,
+                Some("dormant") => OperationalState::Dormant,
+                Some("up") => OperationalState::Up,
+                _ => OperationalState::Unknown,
+            },
+            carrier: match read("carrier").as_deref() {
+                Some("0") => Some(false),
+                Some("1") => Some(true),
+                _ => None,
+            },
+            duplex: match read("duplex").as_deref() {
+                Some("half") => Duplex::Half,
+                Some("full") => Duplex::Full,
+                _ => Duplex::Unknown,
+            },
+            // It is -1 when unknown, or can't be read at all
+            speed: read("speed")
+                .and_then(|speed| speed.parse::<i64>().ok())
+                .and_then(|speed| u64::try_from(speed).ok()),
         }
+    }
+}
 
-        for entry in dir.flatten() {
-            let parent = &entry.path().join("statistics");
-            let entry = match entry.file_name().into_string() {
-                Ok(entry) => entry,
-                Err(_) => continue,
-            };
-            let rx_bytes = read(parent, "rx_bytes", &mut data);
-            let tx_bytes = read(parent, "tx_bytes", &mut data);
-            let rx_packets = read(parent, "rx_packets", &mut data);
-            let tx_packets = read(parent, "tx_packets", &mut data);
-            let rx_errors = read(parent, "rx_errors", &mut data);
-            let tx_errors = read(parent, "tx_errors", &mut data);
-            // let rx_compressed = read(parent, "rx_compressed", &mut data);
-            // let tx_compressed = read(parent, "tx_compressed", &mut data);
-            match interfaces.entry(entry) {
-                hash_map::Entry::Occupied(mut e) => {
-                    let mut interface = e.get_mut();
-                    old_and_new!(interface, rx_bytes, old_rx_bytes);
-                    old_and_new!(interface, tx_bytes, old_tx_bytes);
-                    old_and_new!(interface, rx_packets, old_rx_packets);
-                    old_and_new!(interface, tx_packets, old_tx_packets);
-                    old_and_new!(interface, rx_errors, old_rx_errors);
-                    old_and_new!(interface, tx_errors, old_tx_errors);
-                    // old_and_new!(e, rx_compressed, old_rx_compressed);
-                    // old_and_new!(e, tx_compressed, old_tx_compressed);
-                    interface.updated = true;
-                }
-                hash_map::Entry::Vacant(e) => {
-                    e.insert(NetworkData {
-                        rx_bytes,
-                        old_rx_bytes: rx_bytes,
-                        tx_bytes,
-                        old_tx_bytes: tx_bytes,
-                        rx_packets,
-                        old_rx_packets: rx_packets,
-                        tx_packets,
-                        old_tx_packets: tx_packets,
-                        rx_errors,
-                        old_rx_errors: rx_errors,
-                        tx_errors,
-                        old_tx_errors: tx_errors,
-                        // rx_compressed,
-                        // old_rx_compressed: rx_compressed,
-                        // tx_compressed,
-                        // old_tx_compressed: tx_compressed,
-                        updated: true,
-                    });
-                }
+/// Parses an address like `52:54:00:12:34:56`.
+fn parse_mac_address(address: &str) -> Option<MacAddr> {
+    let mut bytes = [0; 6];
+    let mut parts = address.split(':');
+    for byte in bytes.iter_mut() {
+        *byte = u8::from_str_radix(parts.next()?, 16).ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(MacAddr(bytes))
+}
+
+/// Lists the addresses of each interface with `getifaddrs`, which only knows about the network
+/// namespace of the process.
+fn get_ip_networks() -> HashMap<String, Vec<IpNetwork>> {
+    let mut ip_networks: HashMap<String, Vec<IpNetwork>> = HashMap::new();
+    unsafe {
+        let mut addresses: *mut libc::ifaddrs = ptr::null_mut();
+        if libc::getifaddrs(&mut addresses) != 0 {
+            sysinfo_debug!("getifaddrs failed: {}", io::Error::last_os_error());
+            return ip_networks;
+        }
+        let mut address = addresses;
+        while let Some(current) = address.as_ref() {
+            address = current.ifa_next;
+            let addr = match to_ip_addr(current.ifa_addr) {
+                Some(addr) => addr,
+                None => continue,
             };
+            let prefix = to_ip_addr(current.ifa_netmask).map_or(0, |netmask| match netmask {
+                IpAddr::V4(netmask) => u32::from(netmask).count_ones() as u8,
+                IpAddr::V6(netmask) => u128::from(netmask).count_ones() as u8,
+            });
+            let name = CStr::from_ptr(current.ifa_name)
+                .to_string_lossy()
+                .into_owned();
+            ip_networks
+                .entry(name)
+                .or_default()
+                .push(IpNetwork { addr, prefix });
+        }
+        libc::freeifaddrs(addresses);
+    }
+    ip_networks
+}
+
+/// Converts an IPv4 or IPv6 socket address, `None` for the other families like link addresses.
+unsafe fn to_ip_addr(addr: *const libc::sockaddr) -> Option<IpAddr> {
+    let family = i32::from(addr.as_ref()?.sa_family);
+    if family == libc::AF_INET {
+        let addr = &*(addr as *const libc::sockaddr_in);
+        Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(
+            addr.sin_addr.s_addr,
+        ))))
+    } else if family == libc::AF_INET6 {
+        let addr = &*(addr as *const libc::sockaddr_in6);
+        Some(IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr)))
+    } else {
+        None
+    }
+}
+
+/// The counters of an interface, read from its sysfs statistics or from `net/dev`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Counters {
+    rx_bytes: u64,
+    tx_bytes: u64,
+    rx_packets: u64,
+    tx_packets: u64,
+    rx_errors: u64,
+    tx_errors: u64,
+    // rx_compressed: u64,
+    // tx_compressed: u64,
+}
+
+impl Counters {
+    /// Reads the files of `/sys/class/net/<interface>/statistics`.
+    fn read(statistics: &Path, data: &mut Vec<u8>) -> Counters {
+        Counters {
+            rx_bytes: read(statistics, "rx_bytes", data),
+            tx_bytes: read(statistics, "tx_bytes", data),
+            rx_packets: read(statistics, "rx_packets", data),
+            tx_packets: read(statistics, "tx_packets", data),
+            rx_errors: read(statistics, "rx_errors", data),
+            tx_errors: read(statistics, "tx_errors", data),
+            // rx_compressed: read(statistics, "rx_compressed", data),
+            // tx_compressed: read(statistics, "tx_compressed", data),
         }
+    }
+}
+
+/// Parses the interfaces of `net/dev`, which has two header lines followed by a line like this
+/// one for each interface:
+///
+/// ```text
+///   eth0: 1000 10 0 0 0 0 0 0 2000 20 0 0 0 0 0 0
+/// ```
+///
+/// The first eight counters are about the received data, the next eight about the transmitted
+/// data.
+fn parse_net_dev(data: &str) -> impl Iterator<Item = (&str, Counters)> {
+    data.lines().skip(2).filter_map(|line| {
+        let (name, values) = line.split_once(':')?;
+        let values = values
+            .split_whitespace()
+            .take(16)
+            .map(|value| value.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        if values.len() < 16 {
+            return None;
+        }
+        Some((
+            name.trim(),
+            Counters {
+                rx_bytes: values[0],
+                rx_packets: values[1],
+                rx_errors: values[2],
+                tx_bytes: values[8],
+                tx_packets: values[9],
+                tx_errors: values[10],
+            },
+        ))
+    })
+}
+
+fn read_net_dev(net_dev: &Path) -> io::Result<HashMap<String, Counters>> {
+    let data = get_all_data(net_dev, 16_385)?;
+    Ok(parse_net_dev(&data)
+        .map(|(name, counters)| (name.to_owned(), counters))
+        .collect())
+}
 
-        // Remove interfaces which are gone.
-        interfaces.retain(|_, d| d.updated);
+fn refresh_networks_list_from_sysfs(
+    interfaces: &mut HashMap<String, NetworkData>,
+    sysfs_net: &Path,
+) -> io::Result<()> {
+    let dir = std::fs::read_dir(sysfs_net)?;
+    let mut data = vec![0; 30];
+    let mut found = HashMap::new();
+
+    for entry in dir.flatten() {
+        let counters = Counters::read(&entry.path().join("statistics"), &mut data);
+        if let Ok(entry) = entry.file_name().into_string() {
+            found.insert(entry, counters);
+        }
     }
+    update_interfaces(interfaces, found, sysfs_net, get_ip_networks());
+    Ok(())
+}
+
+/// Lists the interfaces from `net/dev`, whose link attributes are still read from `sysfs_net`.
+fn refresh_networks_list_from_net_dev(
+    interfaces: &mut HashMap<String, NetworkData>,
+    net_dev: &Path,
+    sysfs_net: &Path,
+    ip_networks: HashMap<String, Vec<IpNetwork>>,
+) -> io::Result<()> {
+    let found = read_net_dev(net_dev)?;
+    update_interfaces(interfaces, found, sysfs_net, ip_networks);
+    Ok(())
+}
+
+/// Adds the `found` interfaces which are new, updates the known ones and removes the ones which
+/// are gone.
+fn update_interfaces(
+    interfaces: &mut HashMap<String, NetworkData>,
+    found: HashMap<String, Counters>,
+    sysfs_net: &Path,
+    mut ip_networks: HashMap<String, Vec<IpNetwork>>,
+) {
+    for stats in interfaces.values_mut() {
+        stats.updated = false;
+    }
+
+    for (entry, counters) in found {
+        let link = Link::read(&sysfs_net.join(&entry));
+        let interface_ip_networks = ip_networks.remove(&entry).unwrap_or_default();
+        match interfaces.entry(entry) {
+            hash_map::Entry::Occupied(mut e) => {
+                let interface = e.get_mut();
+                interface.update(&counters);
+                interface.link = link;
+                interface.ip_networks = interface_ip_networks;
+                interface.updated = true;
+            }
+            hash_map::Entry::Vacant(e) => {
+                e.insert(NetworkData {
+                    rx_bytes: counters.rx_bytes,
+                    old_rx_bytes: counters.rx_bytes,
+                    tx_bytes: counters.tx_bytes,
+                    old_tx_bytes: counters.tx_bytes,
+                    rx_packets: counters.rx_packets,
+                    old_rx_packets: counters.rx_packets,
+                    tx_packets: counters.tx_packets,
+                    old_tx_packets: counters.tx_packets,
+                    rx_errors: counters.rx_errors,
+                    old_rx_errors: counters.rx_errors,
+                    tx_errors: counters.tx_errors,
+                    old_tx_errors: counters.tx_errors,
+                    // rx_compressed: counters.rx_compressed,
+                    // old_rx_compressed: counters.rx_compressed,
+                    // tx_compressed: counters.tx_compressed,
+                    // old_tx_compressed: counters.tx_compressed,
+                    link,
+                    ip_networks: interface_ip_networks,
+                    elapsed: 0.,
+                    updated: true,
+                });
+            }
+        };
+    }
+
+    // Remove interfaces which are gone.
+    interfaces.retain(|_, d| d.updated);
 }
 
 impl NetworksExt for Networks {
@@ -122,14 +392,53 @@ impl NetworksExt for Networks {
 
     fn refresh(&mut self) {
         let mut v = vec![0; 30];
+        let mut ip_networks = self.ip_networks();
+        let errors = &self.errors;
+        let mut net_dev = self.net_dev.as_ref().map(|path| {
+            read_net_dev(path).unwrap_or_else(|e| {
+                errors.record(RefreshArea::Networks, path, None, &e);
+                HashMap::new()
+            })
+        });
 
         for (interface_name, data) in self.interfaces.iter_mut() {
-            data.update(interface_name, &mut v);
+            let path = self.sysfs_net.join(interface_name);
+            let counters = match net_dev {
+                // An interface which is gone reports no traffic until the list is refreshed.
+                Some(ref mut net_dev) => net_dev
+                    .remove(interface_name)
+                    .unwrap_or_else(|| data.counters()),
+                None => Counters::read(&path.join("statistics"), &mut v),
+            };
+            data.update(&counters);
+            data.link = Link::read(&path);
+            data.ip_networks = ip_networks.remove(interface_name).unwrap_or_default();
         }
+        self.update_elapsed();
     }
 
     fn refresh_networks_list(&mut self) {
-        refresh_networks_list_from_sysfs(&mut self.interfaces, Path::new("/sys/class/net/"));
+        self.errors.clear(RefreshArea::Networks);
+        let ip_networks = self.ip_networks();
+        let (result, path) = match self.net_dev {
+            Some(ref net_dev) => (
+                refresh_networks_list_from_net_dev(
+                    &mut self.interfaces,
+                    net_dev,
+                    &self.sysfs_net,
+                    ip_networks,
+                ),
+                net_dev,
+            ),
+            None => (
+                refresh_networks_list_from_sysfs(&mut self.interfaces, &self.sysfs_net),
+                &self.sysfs_net,
+            ),
+        };
+        if let Err(e) = result {
+            self.errors.record(RefreshArea::Networks, path, None, &e);
+        }
+        self.update_elapsed();
     }
 }
 
@@ -165,51 +474,35 @@ pub struct NetworkData {
     // /// compression (e.g: PPP).
     // tx_compressed: usize,
     // old_tx_compressed: usize,
+    link: Link,
+    ip_networks: Vec<IpNetwork>,
+    /// The seconds elapsed between the last two refreshes.
+    elapsed: f64,
     /// Whether or not the above data has been updated during refresh
     updated: bool,
 }
 
 impl NetworkData {
-    fn update(&mut self, path: &str, data: &mut Vec<u8>) {
-        let path = &Path::new("/sys/class/net/").join(path).join("statistics");
-        old_and_new!(self, rx_bytes, old_rx_bytes, read(path, "rx_bytes", data));
-        old_and_new!(self, tx_bytes, old_tx_bytes, read(path, "tx_bytes", data));
-        old_and_new!(
-            self,
-            rx_packets,
-            old_rx_packets,
-            read(path, "rx_packets", data)
-        );
-        old_and_new!(
-            self,
-            tx_packets,
-            old_tx_packets,
-            read(path, "tx_packets", data)
-        );
-        old_and_new!(
-            self,
-            rx_errors,
-            old_rx_errors,
-            read(path, "rx_errors", data)
-        );
-        old_and_new!(
-            self,
-            tx_errors,
-            old_tx_errors,
-            read(path, "tx_errors", data)
-        );
-        // old_and_new!(
-        //     self,
-        //     rx_compressed,
-        //     old_rx_compressed,
-        //     read(path, "rx_compressed", data)
-        // );
-        // old_and_new!(
-        //     self,
-        //     tx_compressed,
-        //     old_tx_compressed,
-        //     read(path, "tx_compressed", data)
-        // );
+    fn counters(&self) -> Counters {
+        Counters {
+            rx_bytes: self.rx_bytes,
+            tx_bytes: self.tx_bytes,
+            rx_packets: self.rx_packets,
+            tx_packets: self.tx_packets,
+            rx_errors: self.rx_errors,
+            tx_errors: self.tx_errors,
+        }
+    }
+
+    fn update(&mut self, counters: &Counters) {
+        old_and_new!(self, rx_bytes, old_rx_bytes, counters.rx_bytes);
+        old_and_new!(self, tx_bytes, old_tx_bytes, counters.tx_bytes);
+        old_and_new!(self, rx_packets, old_rx_packets, counters.rx_packets);
+        old_and_new!(self, tx_packets, old_tx_packets, counters.tx_packets);
+        old_and_new!(self, rx_errors, old_rx_errors, counters.rx_errors);
+        old_and_new!(self, tx_errors, old_tx_errors, counters.tx_errors);
+        // old_and_new!(self, rx_compressed, old_rx_compressed, counters.rx_compressed);
+        // old_and_new!(self, tx_compressed, old_tx_compressed, counters.tx_compressed);
     }
 }
 
@@ -261,12 +554,71 @@ impl NetworkExt for NetworkData {
     fn total_errors_on_transmitted(&self) -> u64 {
         self.tx_errors
     }
+
+    fn received_per_second(&self) -> f64 {
+        if self.elapsed > 0. {
+            self.received() as f64 / self.elapsed
+        } else {
+            0.
+        }
+    }
+
+    fn transmitted_per_second(&self) -> f64 {
+        if self.elapsed > 0. {
+            self.transmitted() as f64 / self.elapsed
+        } else {
+            0.
+        }
+    }
+
+    fn mac_address(&self) -> MacAddr {
+        self.link.mac_address
+    }
+
+    fn ip_networks(&self) -> &[IpNetwork] {
+        &self.ip_networks
+    }
+
+    fn mtu(&self) -> u64 {
+        self.link.mtu
+    }
+
+    fn operational_state(&self) -> OperationalState {
+        self.link.operational_state
+    }
+
+    fn carrier(&self) -> Option<bool> {
+        self.link.carrier
+    }
+
+    fn duplex(&self) -> Duplex {
+        self.link.duplex
+    }
+
+    fn speed(&self) -> Option<u64> {
+        self.link.speed
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::refresh_networks_list_from_sysfs;
-    use std::{collections::HashMap, fs};
+    use super::{refresh_networks_list_from_net_dev, refresh_networks_list_from_sysfs};
+    use std::{collections::HashMap, fs, path::Path};
+
+    fn write_net_dev(path: &Path, interfaces: &[&str]) {
+        let mut data = String::from(
+            "Inter-|   Receive                                                |  Transmit\n \
+             face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs \
+             drop fifo colls carrier compressed\n",
+        );
+        for interface in interfaces {
+            data.push_str(&format!(
+                "{:>6}: 1000 10 0 0 0 0 0 0 2000 20 0 0 0 0 0 0\n",
+                interface
+            ));
+        }
+        fs::write(path, data).expect("failed to write net/dev");
+    }
 
     #[test]
     fn refresh_networks_list_add_interface() {
@@ -276,12 +628,12 @@ mod test {
 
         let mut interfaces = HashMap::new();
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path()).unwrap();
         assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf1"]);
 
         fs::create_dir(sys_net_dir.path().join("itf2")).expect("failed to create subdirectory");
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path()).unwrap();
         let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
         itf_names.sort();
         assert_eq!(itf_names, ["itf1", "itf2"]);
@@ -298,14 +650,60 @@ mod test {
 
         let mut interfaces = HashMap::new();
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path()).unwrap();
         let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
         itf_names.sort();
         assert_eq!(itf_names, ["itf1", "itf2"]);
 
         fs::remove_dir(&itf1_dir).expect("failed to remove subdirectory");
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path()).unwrap();
+        assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf2"]);
+    }
+
+    #[test]
+    fn refresh_networks_list_from_net_dev_add_interface() {
+        let dir = tempfile::tempdir().expect("failed to create temporary directory");
+        let net_dev = dir.path().join("dev");
+
+        write_net_dev(&net_dev, &["itf1"]);
+
+        let mut interfaces = HashMap::new();
+
+        refresh_networks_list_from_net_dev(&mut interfaces, &net_dev, dir.path(), HashMap::new())
+            .unwrap();
+        assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf1"]);
+        assert_eq!(interfaces["itf1"].rx_bytes, 1000);
+        assert_eq!(interfaces["itf1"].tx_packets, 20);
+
+        write_net_dev(&net_dev, &["itf1", "itf2"]);
+
+        refresh_networks_list_from_net_dev(&mut interfaces, &net_dev, dir.path(), HashMap::new())
+            .unwrap();
+        let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
+        itf_names.sort();
+        assert_eq!(itf_names, ["itf1", "itf2"]);
+    }
+
+    #[test]
+    fn refresh_networks_list_from_net_dev_remove_interface() {
+        let dir = tempfile::tempdir().expect("failed to create temporary directory");
+        let net_dev = dir.path().join("dev");
+
+        write_net_dev(&net_dev, &["itf1", "itf2"]);
+
+        let mut interfaces = HashMap::new();
+
+        refresh_networks_list_from_net_dev(&mut interfaces, &net_dev, dir.path(), HashMap::new())
+            .unwrap();
+        let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
+        itf_names.sort();
+        assert_eq!(itf_names, ["itf1", "itf2"]);
+
+        write_net_dev(&net_dev, &["itf2"]);
+
+        refresh_networks_list_from_net_dev(&mut interfaces, &net_dev, dir.path(), HashMap::new())
+            .unwrap();
         assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf2"]);
     }
 }
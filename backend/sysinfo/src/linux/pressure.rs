@@ -0,0 +1,74 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{io, path::Path};
+
+use crate::{
+    sys::utils::{get_all_data, RefreshErrors},
+    Pressure, PressureStall, RefreshArea, ResourcePressure,
+};
+
+/// Reads the pressure of every resource from `/proc/pressure`.
+pub(crate) fn get_pressure(proc_path: &Path, errors: &RefreshErrors) -> Pressure {
+    let dir = proc_path.join("pressure");
+    let read = |name: &str| {
+        let path = dir.join(name);
+        match get_all_data(&path, 256) {
+            Ok(data) => parse_resource_pressure(&data),
+            Err(e) => {
+                // The kernel doesn't support PSI, or it was disabled with `psi=0`
+                if !is_unsupported(&e) {
+                    errors.record(RefreshArea::Pressure, &path, None, &e);
+                }
+                None
+            }
+        }
+    };
+    Pressure {
+        cpu: read("cpu"),
+        memory: read("memory"),
+        io: read("io"),
+    }
+}
+
+fn is_unsupported(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::NotFound || error.raw_os_error() == Some(libc::EOPNOTSUPP)
+}
+
+/// Parses a file like:
+///
+/// ```text
+/// some avg10=0.12 avg60=0.05 avg300=0.01 total=123456
+/// full avg10=0.00 avg60=0.00 avg300=0.00 total=0
+/// ```
+fn parse_resource_pressure(data: &str) -> Option<ResourcePressure> {
+    let mut some = None;
+    let mut full = None;
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        match parts.next() {
+            Some("some") => some = parse_stall(parts),
+            Some("full") => full = parse_stall(parts),
+            _ => {}
+        }
+    }
+    some.map(|some| ResourcePressure { some, full })
+}
+
+fn parse_stall<'a, I: Iterator<Item = &'a str>>(parts: I) -> Option<PressureStall> {
+    let mut stall = PressureStall::default();
+    for part in parts {
+        let mut kv = part.splitn(2, '=');
+        let (key, value) = match (kv.next(), kv.next()) {
+            (Some(key), Some(value)) => (key, value),
+            _ => return None,
+        };
+        match key {
+            "avg10" => stall.avg10 = value.parse().ok()?,
+            "avg60" => stall.avg60 = value.parse().ok()?,
+            "avg300" => stall.avg300 = value.parse().ok()?,
+            "total" => stall.total = value.parse().ok()?,
+            _ => {}
+        }
+    }
+    Some(stall)
+}
@@ -5,7 +5,7 @@ use std::{
     collections::HashMap,
     fmt,
     fs::{self, File},
-    io::Read,
+    io::{self, Read},
     mem::MaybeUninit,
     path::{Path, PathBuf},
     str::FromStr,
@@ -19,7 +19,8 @@ use crate::{
         utils::{get_all_data, get_all_data_from_file, realpath, FileCounter},
     },
     utils::into_iter,
-    DiskUsage, Gid, Pid, ProcessExt, ProcessRefreshKind, ProcessStatus, Signal, Uid,
+    DiskUsage, Gid, OpenFile, OpenFileKind, Pid, ProcessExt, ProcessLimits, ProcessRefreshKind,
+    ProcessStatus, RefreshArea, ResourceLimit, Signal, Uid,
 };
 
 #[doc(hidden)]
@@ -97,6 +98,9 @@ pub struct Process {
     user_id: Option<Uid>,
     group_id: Option<Gid>,
     pub(crate) status: ProcessStatus,
+    /// The attributes which are only read once (`cmd`, `environ`, `exe`, `cwd` and `root`) and
+    /// have already been read.
+    static_info: ProcessRefreshKind,
     /// Tasks run by this process.
     pub tasks: HashMap<Pid, Process>,
     pub(crate) stat_file: Option<FileCounter>,
@@ -104,6 +108,8 @@ pub struct Process {
     old_written_bytes: u64,
     read_bytes: u64,
     written_bytes: u64,
+    open_files: Option<Vec<OpenFile>>,
+    limits: Option<ProcessLimits>,
 }
 
 impl Process {
@@ -131,6 +137,7 @@ impl Process {
             user_id: None,
             group_id: None,
             status: ProcessStatus::Unknown(0),
+            static_info: ProcessRefreshKind::new(),
             tasks: if pid.0 == 0 {
                 HashMap::with_capacity(1000)
             } else {
@@ -141,6 +148,8 @@ impl Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            open_files: None,
+            limits: None,
         }
     }
 }
@@ -223,6 +232,14 @@ impl ProcessExt for Process {
     fn group_id(&self) -> Option<Gid> {
         self.group_id
     }
+
+    fn open_files(&self) -> Option<&[OpenFile]> {
+        self.open_files.as_deref()
+    }
+
+    fn limits(&self) -> Option<&ProcessLimits> {
+        self.limits.as_ref()
+    }
 }
 
 pub(crate) fn compute_cpu_usage(p: &mut Process, total_time: f32, max_value: f32) {
@@ -248,12 +265,16 @@ pub(crate) fn set_time(p: &mut Process, utime: u64, stime: u64) {
     p.updated = true;
 }
 
-pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
+pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path, info: &SystemInfo) {
     let mut path = PathBuf::from(path);
     path.push("io");
     let data = match get_all_data(&path, 16_384) {
         Ok(d) => d,
-        Err(_) => return,
+        Err(e) => {
+            info.errors
+                .record(RefreshArea::Processes, &path, Some(p.pid), &e);
+            return;
+        }
     };
     let mut done = 0;
     for line in data.split('\n') {
@@ -283,6 +304,112 @@ pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
     }
 }
 
+/// Reads the open files and the limits of a process. Tasks share them with their process, so
+/// they are only read for processes.
+fn refresh_files_and_limits(
+    p: &mut Process,
+    shared: Option<&SharedInfo>,
+    path: &Path,
+    info: &SystemInfo,
+    refresh_kind: ProcessRefreshKind,
+) {
+    if shared.is_some() {
+        return;
+    }
+    if refresh_kind.open_files() {
+        refresh_open_files(p, path, info);
+    }
+    if refresh_kind.limits() {
+        refresh_limits(p, path, info);
+    }
+}
+
+fn refresh_open_files(p: &mut Process, path: &Path, info: &SystemInfo) {
+    let path = path.join("fd");
+    let entries = match fs::read_dir(&path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            record_unless_denied(p, &path, info, &e);
+            p.open_files = None;
+            return;
+        }
+    };
+    let mut files: Vec<OpenFile> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let fd = entry.file_name().to_str()?.parse().ok()?;
+            // The file descriptor can be closed while the directory is read
+            let target = entry.path().read_link().ok()?;
+            Some(OpenFile {
+                fd,
+                kind: OpenFileKind::of(&target),
+                target,
+            })
+        })
+        .collect();
+    files.sort_unstable_by_key(|file| file.fd);
+    p.open_files = Some(files);
+}
+
+fn refresh_limits(p: &mut Process, path: &Path, info: &SystemInfo) {
+    let path = path.join("limits");
+    p.limits = match get_all_data(&path, 4096) {
+        Ok(data) => Some(parse_limits(&data)),
+        Err(e) => {
+            record_unless_denied(p, &path, info, &e);
+            None
+        }
+    };
+}
+
+/// Records an error reading the open files or the limits of a process, unless it only means that
+/// the process belongs to another user. Those are expected when not running as root, and would
+/// push the other errors out.
+fn record_unless_denied(p: &Process, path: &Path, info: &SystemInfo, error: &io::Error) {
+    if error.kind() != io::ErrorKind::PermissionDenied {
+        info.errors
+            .record(RefreshArea::Processes, path, Some(p.pid), error);
+    }
+}
+
+/// Parses `/proc/<pid>/limits`. The names of the limits contain spaces, so they are read from
+/// their fixed width column.
+fn parse_limits(data: &str) -> ProcessLimits {
+    let mut limits = ProcessLimits::default();
+    // Skip the header
+    for line in data.lines().skip(1) {
+        let (name, values) = match (line.get(..26), line.get(26..)) {
+            (Some(name), Some(values)) => (name.trim_end(), values),
+            _ => continue,
+        };
+        let limit = match name {
+            "Max cpu time" => &mut limits.cpu_time,
+            "Max file size" => &mut limits.file_size,
+            "Max data size" => &mut limits.data_size,
+            "Max stack size" => &mut limits.stack_size,
+            "Max core file size" => &mut limits.core_file_size,
+            "Max resident set" => &mut limits.resident_set,
+            "Max processes" => &mut limits.processes,
+            "Max open files" => &mut limits.open_files,
+            "Max locked memory" => &mut limits.locked_memory,
+            "Max address space" => &mut limits.address_space,
+            "Max file locks" => &mut limits.file_locks,
+            "Max pending signals" => &mut limits.pending_signals,
+            "Max msgqueue size" => &mut limits.message_queue_size,
+            "Max nice priority" => &mut limits.nice_priority,
+            "Max realtime priority" => &mut limits.realtime_priority,
+            "Max realtime timeout" => &mut limits.realtime_timeout,
+            _ => continue,
+        };
+        // "unlimited" doesn't parse
+        let mut values = values.split_whitespace().map(|value| value.parse().ok());
+        if let (Some(soft), Some(hard)) = (values.next(), values.next()) {
+            *limit = ResourceLimit { soft, hard };
+        }
+    }
+    limits
+}
+
 struct Wrap<'a, T>(UnsafeCell<&'a mut T>);
 
 impl<'a, T> Wrap<'a, T> {
@@ -302,12 +429,21 @@ fn compute_start_time_without_boot_time(parts: &[&str], info: &SystemInfo) -> u6
     u64::from_str(parts[21]).unwrap_or(0) / info.clock_cycle
 }
 
-fn _get_stat_data(path: &Path, stat_file: &mut Option<FileCounter>) -> Result<String, ()> {
+fn _get_stat_data(
+    path: &Path,
+    pid: Pid,
+    stat_file: &mut Option<FileCounter>,
+    info: &SystemInfo,
+) -> Result<String, ()> {
     let mut tmp = PathBuf::from(path);
     tmp.push("stat");
-    let mut file = File::open(tmp).map_err(|_| ())?;
-    let data = get_all_data_from_file(&mut file, 1024).map_err(|_| ())?;
-    *stat_file = FileCounter::new(file);
+    let record = |e: io::Error| {
+        info.errors
+            .record(RefreshArea::Processes, &tmp, Some(pid), &e);
+    };
+    let mut file = File::open(&tmp).map_err(record)?;
+    let data = get_all_data_from_file(&mut file, 1024).map_err(record)?;
+    *stat_file = FileCounter::new(file, &info.remaining_files);
     Ok(data)
 }
 
@@ -328,6 +464,105 @@ fn refresh_user_group_ids(p: &mut Process, path: &mut PathBuf) {
     }
 }
 
+/// The attributes a task shares with its process.
+struct SharedInfo<'a> {
+    cmd: &'a [String],
+    environ: &'a [String],
+    exe: &'a Path,
+    cwd: &'a Path,
+    root: &'a Path,
+}
+
+impl<'a> SharedInfo<'a> {
+    /// Gets the attributes of a process for its tasks, or `None` for the root of the process
+    /// list.
+    fn of(proc_list: &'a Process) -> Option<Self> {
+        if proc_list.pid.0 == 0 {
+            return None;
+        }
+        Some(SharedInfo {
+            cmd: &proc_list.cmd,
+            environ: &proc_list.environ,
+            exe: &proc_list.exe,
+            cwd: &proc_list.cwd,
+            root: &proc_list.root,
+        })
+    }
+
+    /// Splits a process into its tasks and the attributes they share with it.
+    fn split(proc_list: &'a mut Process) -> (&'a mut HashMap<Pid, Process>, Option<Self>) {
+        let Process {
+            ref mut tasks,
+            pid,
+            ref cmd,
+            ref environ,
+            ref exe,
+            ref cwd,
+            ref root,
+            ..
+        } = *proc_list;
+        let shared = if pid.0 == 0 {
+            None
+        } else {
+            Some(SharedInfo {
+                cmd,
+                environ,
+                exe,
+                cwd,
+                root,
+            })
+        };
+        (tasks, shared)
+    }
+}
+
+/// Reads the attributes which don't change during the lifetime of a process, if they are
+/// requested and haven't been read yet. Tasks copy them from their process instead.
+fn refresh_static_info(
+    p: &mut Process,
+    shared: Option<&SharedInfo>,
+    path: &Path,
+    refresh_kind: ProcessRefreshKind,
+) {
+    if refresh_kind.cmd() && !p.static_info.cmd() {
+        p.cmd = match shared {
+            Some(shared) => shared.cmd.to_vec(),
+            None => copy_from_file(&path.join("cmdline")),
+        };
+        p.static_info = p.static_info.with_cmd();
+    }
+    if refresh_kind.exe() && !p.static_info.exe() {
+        p.exe = match shared {
+            Some(shared) => shared.exe.to_owned(),
+            // Do not use cmd[0] because it is not the same thing.
+            // See https://github.com/GuillaumeGomez/sysinfo/issues/697.
+            None => path.join("exe").read_link().unwrap_or_default(),
+        };
+        p.static_info = p.static_info.with_exe();
+    }
+    if refresh_kind.environ() && !p.static_info.environ() {
+        p.environ = match shared {
+            Some(shared) => shared.environ.to_vec(),
+            None => copy_from_file(&path.join("environ")),
+        };
+        p.static_info = p.static_info.with_environ();
+    }
+    if refresh_kind.cwd() && !p.static_info.cwd() {
+        p.cwd = match shared {
+            Some(shared) => shared.cwd.to_owned(),
+            None => realpath(&path.join("cwd")),
+        };
+        p.static_info = p.static_info.with_cwd();
+    }
+    if refresh_kind.root() && !p.static_info.root() {
+        p.root = match shared {
+            Some(shared) => shared.root.to_owned(),
+            None => realpath(&path.join("root")),
+        };
+        p.static_info = p.static_info.with_root();
+    }
+}
+
 fn retrieve_all_new_process_info(
     pid: Pid,
     proc_list: &Process,
@@ -355,53 +590,22 @@ fn retrieve_all_new_process_info(
         .start_time_without_boot_time
         .saturating_add(info.boot_time);
 
-    get_status(&mut p, parts[2]);
+    if refresh_kind.status() {
+        get_status(&mut p, parts[2]);
+    }
 
     if refresh_kind.user() {
         refresh_user_group_ids(&mut p, &mut tmp);
     }
 
-    if proc_list.pid.0 != 0 {
-        // If we're getting information for a child, no need to get those info since we
-        // already have them...
-        p.cmd = proc_list.cmd.clone();
-        p.name = proc_list.name.clone();
-        p.environ = proc_list.environ.clone();
-        p.exe = proc_list.exe.clone();
-        p.cwd = proc_list.cwd.clone();
-        p.root = proc_list.root.clone();
-    } else {
-        p.name = name.into();
-
-        tmp.pop();
-        tmp.push("cmdline");
-        p.cmd = copy_from_file(&tmp);
-
-        tmp.pop();
-        tmp.push("exe");
-        match tmp.read_link() {
-            Ok(exe_path) => {
-                p.exe = exe_path;
-            }
-            Err(_) => {
-                // Do not use cmd[0] because it is not the same thing.
-                // See https://github.com/GuillaumeGomez/sysinfo/issues/697.
-                p.exe = PathBuf::new()
-            }
-        }
-
-        tmp.pop();
-        tmp.push("environ");
-        p.environ = copy_from_file(&tmp);
-
-        tmp.pop();
-        tmp.push("cwd");
-        p.cwd = realpath(&tmp);
-
-        tmp.pop();
-        tmp.push("root");
-        p.root = realpath(&tmp);
-    }
+    // If we're getting information for a child, no need to get those info since we already
+    // have them...
+    let shared = SharedInfo::of(proc_list);
+    p.name = match shared {
+        Some(_) => proc_list.name.clone(),
+        None => name.into(),
+    };
+    refresh_static_info(&mut p, shared.as_ref(), path, refresh_kind);
 
     update_time_and_memory(
         path,
@@ -414,8 +618,9 @@ fn retrieve_all_new_process_info(
         refresh_kind,
     );
     if refresh_kind.disk_usage() {
-        update_process_disk_activity(&mut p, path);
+        update_process_disk_activity(&mut p, path, info);
     }
+    refresh_files_and_limits(&mut p, shared.as_ref(), path, info, refresh_kind);
     p
 }
 
@@ -436,7 +641,8 @@ pub(crate) fn _get_process_data(
     let parent_virtual_memory = proc_list.virtual_memory;
 
     let data;
-    let parts = if let Some(ref mut entry) = proc_list.tasks.get_mut(&pid) {
+    let (tasks, shared) = SharedInfo::split(proc_list);
+    let parts = if let Some(ref mut entry) = tasks.get_mut(&pid) {
         data = if let Some(mut f) = entry.stat_file.take() {
             match get_all_data_from_file(&mut f, 1024) {
                 Ok(data) => {
@@ -447,11 +653,11 @@ pub(crate) fn _get_process_data(
                 Err(_) => {
                     // It's possible that the file descriptor is no longer valid in case the
                     // original process was terminated and another one took its place.
-                    _get_stat_data(path, &mut entry.stat_file)?
+                    _get_stat_data(path, pid, &mut entry.stat_file, info)?
                 }
             }
         } else {
-            _get_stat_data(path, &mut entry.stat_file)?
+            _get_stat_data(path, pid, &mut entry.stat_file, info)?
         };
         let parts = parse_stat_file(&data)?;
         let start_time_without_boot_time = compute_start_time_without_boot_time(&parts, info);
@@ -460,7 +666,10 @@ pub(crate) fn _get_process_data(
         // If the start time differs, then it means it's not the same process anymore and that we
         // need to get all its information, hence why we check it here.
         if start_time_without_boot_time == entry.start_time_without_boot_time {
-            get_status(entry, parts[2]);
+            if refresh_kind.status() {
+                get_status(entry, parts[2]);
+            }
+            refresh_static_info(entry, shared.as_ref(), path, refresh_kind);
             update_time_and_memory(
                 path,
                 entry,
@@ -472,8 +681,9 @@ pub(crate) fn _get_process_data(
                 refresh_kind,
             );
             if refresh_kind.disk_usage() {
-                update_process_disk_activity(entry, path);
+                update_process_disk_activity(entry, path, info);
             }
+            refresh_files_and_limits(entry, shared.as_ref(), path, info, refresh_kind);
             if refresh_kind.user() && entry.user_id.is_none() {
                 refresh_user_group_ids(entry, &mut PathBuf::from(path));
             }
@@ -482,7 +692,7 @@ pub(crate) fn _get_process_data(
         parts
     } else {
         let mut stat_file = None;
-        let data = _get_stat_data(path, &mut stat_file)?;
+        let data = _get_stat_data(path, pid, &mut stat_file, info)?;
         let parts = parse_stat_file(&data)?;
 
         let mut p =
@@ -514,11 +724,13 @@ fn update_time_and_memory(
     info: &SystemInfo,
     refresh_kind: ProcessRefreshKind,
 ) {
-    {
-        // rss
+    if refresh_kind.memory() {
+        // rss is a number of pages. Multiplying by the page size gives us bytes, and dividing by
+        // 1_000 gives us kb, just like vsz below.
         entry.memory = u64::from_str(parts[23])
             .unwrap_or(0)
-            .saturating_mul(info.page_size_kb);
+            .saturating_mul(info.page_size_b)
+            / 1_000;
         if entry.memory >= parent_memory {
             entry.memory -= parent_memory;
         }
@@ -528,13 +740,13 @@ fn update_time_and_memory(
         if entry.virtual_memory >= parent_virtual_memory {
             entry.virtual_memory -= parent_virtual_memory;
         }
-        set_time(
-            entry,
-            u64::from_str(parts[13]).unwrap_or(0),
-            u64::from_str(parts[14]).unwrap_or(0),
-        );
-        entry.run_time = uptime.saturating_sub(entry.start_time_without_boot_time);
     }
+    set_time(
+        entry,
+        u64::from_str(parts[13]).unwrap_or(0),
+        u64::from_str(parts[14]).unwrap_or(0),
+    );
+    entry.run_time = uptime.saturating_sub(entry.start_time_without_boot_time);
     refresh_procs(
         entry,
         &path.join("task"),
@@ -553,7 +765,14 @@ pub(crate) fn refresh_procs(
     info: &SystemInfo,
     refresh_kind: ProcessRefreshKind,
 ) -> bool {
-    if let Ok(d) = fs::read_dir(path) {
+    let d = fs::read_dir(path);
+    if let Err(ref e) = d {
+        // A task directory disappearing only means that its process exited.
+        if pid.0 == 0 {
+            info.errors.record(RefreshArea::Processes, path, None, e);
+        }
+    }
+    if let Ok(d) = d {
         let folders = d
             .filter_map(|entry| {
                 if let Ok(entry) = entry {
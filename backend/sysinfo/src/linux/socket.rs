@@ -0,0 +1,368 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::Path,
+};
+
+use crate::{
+    sys::utils::{get_all_data, get_precise_uptime, RefreshErrors},
+    ListeningSocket, Pid, RefreshArea, SocketProtocol, SocketStats, TcpActivity, TcpState,
+    UdpActivity,
+};
+
+/// The flag of `/proc/net/unix` set for the sockets accepting connections (`__SO_ACCEPTCON`).
+const UNIX_ACCEPTING: u32 = 0x10000;
+
+/// The counters of `/proc/net/snmp` and `/proc/net/snmp6` used to compute the [`TcpActivity`]
+/// and [`UdpActivity`], along with the uptime they were read at. They are named like
+/// `TcpRetransSegs`, and the IPv6 UDP counters are added to the IPv4 ones.
+#[derive(Default, Debug, Clone)]
+pub(crate) struct SnmpCounters {
+    uptime: f64,
+    values: HashMap<String, u64>,
+}
+
+/// A line of `/proc/net/tcp`, `/proc/net/udp` or their IPv6 versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct InetSocket {
+    local: SocketAddr,
+    remote: SocketAddr,
+    state: u8,
+    inode: u64,
+}
+
+/// Reads the sockets from `net_path`, usually `/proc/net`, and computes the rates since the `old`
+/// counters, which are replaced by the new ones. The uptime is read from `proc_path`.
+pub(crate) fn get_sockets(
+    proc_path: &Path,
+    net_path: &Path,
+    errors: &RefreshErrors,
+    old: &mut Option<SnmpCounters>,
+) -> SocketStats {
+    let read = |name: &str| {
+        let path = net_path.join(name);
+        get_all_data(&path, 16_385)
+            .map_err(|e| {
+                // The IPv6 files don't exist when IPv6 is disabled
+                if !(name.ends_with('6') && e.kind() == io::ErrorKind::NotFound) {
+                    errors.record(RefreshArea::Sockets, &path, None, &e);
+                }
+            })
+            .ok()
+    };
+    let mut stats = SocketStats::default();
+
+    for name in &["tcp", "tcp6"] {
+        let data = match read(name) {
+            Some(data) => data,
+            None => continue,
+        };
+        for socket in data.lines().skip(1).filter_map(parse_inet_socket) {
+            let state = match tcp_state(socket.state) {
+                Some(state) => state,
+                None => continue,
+            };
+            *stats.tcp_states.entry(state).or_insert(0) += 1;
+            if state == TcpState::Listen {
+                stats
+                    .listening
+                    .push(inet_listening_socket(SocketProtocol::Tcp, &socket));
+            }
+        }
+    }
+    for name in &["udp", "udp6"] {
+        let data = match read(name) {
+            Some(data) => data,
+            None => continue,
+        };
+        for socket in data.lines().skip(1).filter_map(parse_inet_socket) {
+            stats.udp_sockets += 1;
+            // Bound sockets without a remote address are reported as closed
+            if tcp_state(socket.state) == Some(TcpState::Close) && socket.remote.port() == 0 {
+                stats
+                    .listening
+                    .push(inet_listening_socket(SocketProtocol::Udp, &socket));
+            }
+        }
+    }
+    if let Some(data) = read("unix") {
+        for (flags, inode, path) in data.lines().skip(1).filter_map(parse_unix_socket) {
+            stats.unix_sockets += 1;
+            if flags & UNIX_ACCEPTING != 0 {
+                stats.listening.push(ListeningSocket {
+                    protocol: SocketProtocol::Unix,
+                    address: None,
+                    path,
+                    inode,
+                    pids: Vec::new(),
+                });
+            }
+        }
+    }
+
+    let uptime = get_precise_uptime(proc_path).map_err(|e| {
+        errors.record(RefreshArea::Sockets, &proc_path.join("uptime"), None, &e);
+    });
+    if let (Ok(uptime), Some(snmp)) = (uptime, read("snmp")) {
+        let mut counters = SnmpCounters {
+            uptime,
+            values: HashMap::new(),
+        };
+        parse_snmp(&snmp, &mut counters.values);
+        if let Some(snmp6) = read("snmp6") {
+            parse_snmp6(&snmp6, &mut counters.values);
+        }
+        if let Some(old) = old.as_ref() {
+            set_rates(&mut stats, &counters, old);
+        }
+        *old = Some(counters);
+    }
+    stats
+}
+
+fn set_rates(stats: &mut SocketStats, new: &SnmpCounters, old: &SnmpCounters) {
+    let elapsed = new.uptime - old.uptime;
+    if elapsed <= 0. {
+        return;
+    }
+    let rate = |name: &str| {
+        let value = |counters: &SnmpCounters| counters.values.get(name).copied().unwrap_or(0);
+        value(new).saturating_sub(value(old)) as f64 / elapsed
+    };
+    stats.tcp = TcpActivity {
+        active_opens: rate("TcpActiveOpens"),
+        passive_opens: rate("TcpPassiveOpens"),
+        failed_connection_attempts: rate("TcpAttemptFails"),
+        established_resets: rate("TcpEstabResets"),
+        segments_sent: rate("TcpOutSegs"),
+        segments_retransmitted: rate("TcpRetransSegs"),
+        receive_errors: rate("TcpInErrs"),
+        resets_sent: rate("TcpOutRsts"),
+    };
+    stats.udp = UdpActivity {
+        datagrams_received: rate("UdpInDatagrams"),
+        datagrams_sent: rate("UdpOutDatagrams"),
+        no_port: rate("UdpNoPorts"),
+        receive_errors: rate("UdpInErrors"),
+        receive_buffer_errors: rate("UdpRcvbufErrors"),
+        send_buffer_errors: rate("UdpSndbufErrors"),
+    };
+}
+
+fn inet_listening_socket(protocol: SocketProtocol, socket: &InetSocket) -> ListeningSocket {
+    ListeningSocket {
+        protocol,
+        address: Some(socket.local),
+        path: None,
+        inode: socket.inode,
+        pids: Vec::new(),
+    }
+}
+
+/// Finds the processes which have the listening sockets open, by matching the inodes of the
+/// sockets with the targets of the links in `/proc/<pid>/fd`, like `socket:[12345]`.
+pub(crate) fn set_listening_pids(proc_path: &Path, listening: &mut [ListeningSocket]) {
+    if listening.is_empty() {
+        return;
+    }
+    let mut pids: HashMap<u64, Vec<Pid>> = listening
+        .iter()
+        .map(|socket| (socket.inode, Vec::new()))
+        .collect();
+    let entries = match fs::read_dir(proc_path) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let pid = match entry.file_name().to_str().and_then(|pid| pid.parse().ok()) {
+            Some(pid) => Pid(pid),
+            None => continue,
+        };
+        // The file descriptors of the processes of other users can't be read without privileges
+        let fds = match fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(_) => continue,
+        };
+        for fd in fds.flatten() {
+            let inode = fs::read_link(fd.path()).ok().and_then(|target| {
+                target
+                    .to_str()?
+                    .strip_prefix("socket:[")?
+                    .strip_suffix(']')?
+                    .parse::<u64>()
+                    .ok()
+            });
+            if let Some(socket_pids) = inode.and_then(|inode| pids.get_mut(&inode)) {
+                // A process can have the same socket open several times
+                if !socket_pids.contains(&pid) {
+                    socket_pids.push(pid);
+                }
+            }
+        }
+    }
+    for socket in listening.iter_mut() {
+        if let Some(socket_pids) = pids.get(&socket.inode) {
+            socket.pids = socket_pids.clone();
+            socket.pids.sort_unstable();
+        }
+    }
+}
+
+/// Converts the states of `include/net/tcp_states.h`.
+fn tcp_state(state: u8) -> Option<TcpState> {
+    Some(match state {
+        0x01 => TcpState::Established,
+        0x02 => TcpState::SynSent,
+        0x03 => TcpState::SynReceived,
+        0x04 => TcpState::FinWait1,
+        0x05 => TcpState::FinWait2,
+        0x06 => TcpState::TimeWait,
+        0x07 => TcpState::Close,
+        0x08 => TcpState::CloseWait,
+        0x09 => TcpState::LastAck,
+        0x0A => TcpState::Listen,
+        0x0B => TcpState::Closing,
+        0x0C => TcpState::NewSynReceived,
+        _ => return None,
+    })
+}
+
+/// Parses a line like:
+///
+/// ```text
+///    0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 12345 1 0000000000000000 100 0 0 10 0
+/// ```
+///
+/// The fields after the inode aren't used.
+fn parse_inet_socket(line: &str) -> Option<InetSocket> {
+    let fields: Vec<&str> = line.split_whitespace().take(10).collect();
+    if fields.len() < 10 {
+        return None;
+    }
+    Some(InetSocket {
+        local: parse_inet_address(fields[1])?,
+        remote: parse_inet_address(fields[2])?,
+        state: u8::from_str_radix(fields[3], 16).ok()?,
+        inode: fields[9].parse().ok()?,
+    })
+}
+
+/// Parses an address like `0100007F:1F90`, where the IP address is written as 32 bits words in
+/// the byte order of the host, and the port in hexadecimal.
+fn parse_inet_address(address: &str) -> Option<SocketAddr> {
+    let (ip, port) = address.split_once(':')?;
+    let mut bytes = Vec::with_capacity(16);
+    for i in (0..ip.len()).step_by(8) {
+        let word = u32::from_str_radix(ip.get(i..i + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&bytes);
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, u16::from_str_radix(port, 16).ok()?))
+}
+
+/// Parses a line like:
+///
+/// ```text
+/// 0000000000000000: 00000002 00000000 00010000 0001 01 12345 /run/systemd/private
+/// ```
+///
+/// It returns the flags, the inode and the path, which is missing for unnamed sockets.
+fn parse_unix_socket(line: &str) -> Option<(u32, u64, Option<String>)> {
+    let mut fields = line.split_whitespace();
+    let flags = u32::from_str_radix(fields.nth(3)?, 16).ok()?;
+    let inode = fields.nth(2)?.parse().ok()?;
+    let path = fields.collect::<Vec<_>>().join(" ");
+    Some((
+        flags,
+        inode,
+        if path.is_empty() { None } else { Some(path) },
+    ))
+}
+
+/// Parses the pairs of lines of `/proc/net/snmp` like:
+///
+/// ```text
+/// Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens PassiveOpens AttemptFails EstabResets
+/// Tcp: 1 200 120000 -1 1000 500 20 10
+/// ```
+///
+/// The counters are added to `values`, prefixed by the protocol.
+fn parse_snmp(data: &str, values: &mut HashMap<String, u64>) {
+    let mut lines = data.lines();
+    while let (Some(names), Some(numbers)) = (lines.next(), lines.next()) {
+        let mut names = names.split_whitespace();
+        let mut numbers = numbers.split_whitespace();
+        let protocol = match (names.next(), numbers.next()) {
+            (Some(protocol), Some(other)) if protocol == other => protocol.trim_end_matches(':'),
+            _ => continue,
+        };
+        for (name, value) in names.zip(numbers) {
+            // Some values, like `MaxConn`, can be negative and aren't counters
+            if let Ok(value) = value.parse::<u64>() {
+                *values.entry(format!("{}{}", protocol, name)).or_insert(0) += value;
+            }
+        }
+    }
+}
+
+/// Parses the `name value` lines of `/proc/net/snmp6` like `Udp6InDatagrams 1000`, adding the
+/// UDP counters to those of IPv4 in `values`. The TCP counters of `/proc/net/snmp` already count
+/// IPv6.
+fn parse_snmp6(data: &str, values: &mut HashMap<String, u64>) {
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let (name, value) = match (parts.next(), parts.next().map(str::parse::<u64>)) {
+            (Some(name), Some(Ok(value))) => (name, value),
+            _ => continue,
+        };
+        if let Some(name) = name.strip_prefix("Udp6") {
+            *values.entry(format!("Udp{}", name)).or_insert(0) += value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_inet_address, parse_unix_socket};
+    use std::net::SocketAddr;
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn check_parse_inet_address() {
+        assert_eq!(
+            parse_inet_address("0100007F:1F90"),
+            Some("127.0.0.1:8080".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(
+            parse_inet_address("00000000000000000000000001000000:0016"),
+            Some("[::1]:22".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(parse_inet_address("0100007F"), None);
+        assert_eq!(parse_inet_address("0100007:1F90"), None);
+    }
+
+    #[test]
+    fn check_parse_unix_socket() {
+        assert_eq!(
+            parse_unix_socket(
+                "0000000000000000: 00000002 00000000 00010000 0001 01 12345 /run/with space.sock"
+            ),
+            Some((0x10000, 12345, Some("/run/with space.sock".to_owned())))
+        );
+        assert_eq!(
+            parse_unix_socket("0000000000000000: 00000003 00000000 00000000 0001 03 678"),
+            Some((0, 678, None))
+        );
+    }
+}
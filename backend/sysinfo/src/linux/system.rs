@@ -4,19 +4,25 @@ use crate::{
     sys::{
         component::{self, Component},
         cpu::*,
-        disk,
+        disk::{self, DiskOptions},
+        disk_io::{get_disks_io, DiskCounters},
+        memory::{get_memory_activity, VmCounters},
+        pressure::get_pressure,
         process::*,
-        utils::get_all_data,
+        socket::{get_sockets, set_listening_pids, SnmpCounters},
+        utils::{get_all_data, RefreshErrors, SystemPaths},
     },
-    CpuRefreshKind, Disk, LoadAvg, Networks, Pid, ProcessRefreshKind, RefreshKind, SystemExt, User,
+    CpuRefreshKind, Disk, DiskIo, KernelActivity, LoadAvg, MemoryActivity, MemoryDetails, Networks,
+    Pid, Pressure, ProcessRefreshKind, RefreshArea, RefreshError, RefreshKind, SocketStats,
+    SystemBuilder, SystemExt, User,
 };
 
 use libc::{self, c_char, c_int, sysconf, _SC_CLK_TCK, _SC_HOST_NAME_MAX, _SC_PAGESIZE};
 use std::{
     collections::HashMap,
     fs::File,
-    io::{BufRead, BufReader, Read},
-    path::{Path, PathBuf},
+    io::{BufRead, BufReader},
+    path::Path,
     str::FromStr,
     sync::{Arc, Mutex},
 };
@@ -106,18 +112,30 @@ fn boot_time(proc_path: &Path) -> u64 {
 }
 
 pub(crate) struct SystemInfo {
-    pub(crate) page_size_kb: u64,
+    pub(crate) page_size_b: u64,
     pub(crate) clock_cycle: u64,
     pub(crate) boot_time: u64,
+    /// The number of files which can still be kept open.
+    pub(crate) remaining_files: Arc<Mutex<isize>>,
+    /// The errors which happened while refreshing. Shared with [`Networks`].
+    pub(crate) errors: Arc<RefreshErrors>,
 }
 
 impl SystemInfo {
-    fn new(proc_path: &Path) -> Self {
+    fn new(proc_path: &Path, open_files_limit: Option<isize>) -> Self {
+        // Always initialize the global limit since it raises the soft limit of the process.
+        let global_remaining_files = unsafe { Arc::clone(&*std::ptr::addr_of!(REMAINING_FILES)) };
+        let remaining_files = match open_files_limit {
+            Some(limit) => Arc::new(Mutex::new(limit.clamp(0, get_max_nb_fds()))),
+            None => global_remaining_files,
+        };
         unsafe {
             Self {
-                page_size_kb: (sysconf(_SC_PAGESIZE) / 1024) as _,
+                page_size_b: sysconf(_SC_PAGESIZE) as _,
                 clock_cycle: sysconf(_SC_CLK_TCK) as _,
                 boot_time: boot_time(proc_path),
+                remaining_files,
+                errors: Default::default(),
             }
         }
     }
@@ -170,11 +188,22 @@ pub struct System {
     mem_slab_reclaimable: u64,
     swap_total: u64,
     swap_free: u64,
+    memory_details: MemoryDetails,
+    vm_counters: Option<VmCounters>,
+    memory_activity: MemoryActivity,
     global_cpu: Cpu,
     cpus: Vec<Cpu>,
+    kernel_counters: Option<KernelCounters>,
+    kernel_activity: KernelActivity,
+    pressure: Pressure,
     components: Vec<Component>,
     disks: Vec<Disk>,
+    disk_options: DiskOptions,
+    disk_counters: Option<DiskCounters>,
+    disks_io: Vec<DiskIo>,
     networks: Networks,
+    snmp_counters: Option<SnmpCounters>,
+    sockets: SocketStats,
     users: Vec<User>,
     /// Field set to `false` in `update_cpus` and to `true` in `refresh_processes_specifics`.
     ///
@@ -183,11 +212,71 @@ pub struct System {
     need_cpus_update: bool,
     info: SystemInfo,
     got_cpu_frequency: bool,
-    /// Allows for reading from paths other than `/proc`.
-    proc_path: PathBuf,
+    /// Allows for reading from paths other than `/proc`, `/sys`, `/etc` and `/dev`.
+    paths: SystemPaths,
 }
 
 impl System {
+    pub(crate) fn new_with_builder(builder: SystemBuilder) -> System {
+        let paths = SystemPaths::new(&builder);
+        let process_list = Process::new(Pid(0));
+        let info = SystemInfo::new(&paths.proc, builder.open_files_limit);
+        let mut s = System {
+            process_list,
+            mem_total: 0,
+            mem_free: 0,
+            mem_available: 0,
+            mem_buffers: 0,
+            mem_page_cache: 0,
+            mem_slab_reclaimable: 0,
+            swap_total: 0,
+            swap_free: 0,
+            memory_details: MemoryDetails::default(),
+            vm_counters: None,
+            memory_activity: MemoryActivity::default(),
+            global_cpu: Cpu::new_with_values(
+                "",
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                String::new(),
+                String::new(),
+            ),
+            cpus: Vec::with_capacity(4),
+            kernel_counters: None,
+            kernel_activity: KernelActivity::default(),
+            pressure: Pressure::default(),
+            components: Vec::new(),
+            disks: Vec::with_capacity(2),
+            disk_options: DiskOptions::new(&builder),
+            disk_counters: None,
+            disks_io: Vec::new(),
+            networks: Networks::new(
+                &paths.sys,
+                &paths.proc,
+                builder.network_source,
+                info.errors.clone(),
+            ),
+            snmp_counters: None,
+            sockets: SocketStats::default(),
+            users: Vec::new(),
+            need_cpus_update: true,
+            info,
+            got_cpu_frequency: false,
+            paths,
+        };
+        s.refresh_specifics(builder.refresh_kind);
+        s
+    }
+
     /// It is sometime possible that a CPU usage computation is bigger than
     /// `"number of CPUs" * 100`.
     ///
@@ -231,8 +320,31 @@ impl System {
         });
     }
 
+    /// Reads a file under `/proc` which is read every time the information is requested.
+    fn read_system_file(&self, name: &str, size: usize) -> Option<String> {
+        let path = self.paths.proc.join(name);
+        match get_all_data(&path, size) {
+            Ok(data) => {
+                self.info.errors.resolve(RefreshArea::System, &path);
+                Some(data)
+            }
+            Err(e) => {
+                self.info
+                    .errors
+                    .record(RefreshArea::System, &path, None, &e);
+                None
+            }
+        }
+    }
+
     fn refresh_cpus(&mut self, only_update_global_cpu: bool, refresh_kind: CpuRefreshKind) {
-        if let Ok(f) = File::open(self.proc_path.join("stat")) {
+        self.info.errors.clear(RefreshArea::Cpu);
+        let path = self.paths.proc.join("stat");
+        let f = File::open(&path);
+        if let Err(ref e) = f {
+            self.info.errors.record(RefreshArea::Cpu, &path, None, e);
+        }
+        if let Ok(f) = f {
             self.need_cpus_update = false;
 
             let buf = BufReader::new(f);
@@ -240,12 +352,15 @@ impl System {
             let first = self.cpus.is_empty();
             let mut it = buf.split(b'\n');
             let (vendor_id, brand) = if first {
-                get_vendor_id_and_brand(&self.proc_path)
+                get_vendor_id_and_brand(&self.paths.proc)
             } else {
                 (String::new(), String::new())
             };
 
-            if first || refresh_kind.cpu_usage() {
+            // The first line following the CPU lines, if they were read
+            let mut next_line = None;
+            // The kernel activity rates are computed from the global CPU time
+            if first || refresh_kind.cpu_usage() || refresh_kind.kernel_activity() {
                 if let Some(Ok(line)) = it.next() {
                     if &line[..4] != b"cpu " {
                         return;
@@ -272,6 +387,7 @@ impl System {
                 if first || !only_update_global_cpu {
                     while let Some(Ok(line)) = it.next() {
                         if &line[..3] != b"cpu" {
+                            next_line = Some(line);
                             break;
                         }
 
@@ -314,6 +430,22 @@ impl System {
                 }
             }
 
+            if refresh_kind.kernel_activity() {
+                let lines = next_line
+                    .into_iter()
+                    .chain(it.filter_map(Result::ok))
+                    .filter(|line| !line.starts_with(b"cpu"));
+                let (counters, activity) = parse_kernel_activity(
+                    lines,
+                    get_raw_times(&self.global_cpu).0,
+                    self.cpus.len(),
+                    self.info.clock_cycle,
+                    self.kernel_counters.as_ref(),
+                );
+                self.kernel_counters = Some(counters);
+                self.kernel_activity = activity;
+            }
+
             if refresh_kind.frequency() {
                 #[cfg(feature = "multithread")]
                 use rayon::iter::{
@@ -337,11 +469,11 @@ impl System {
                 }
 
                 // `get_cpu_frequency` is very slow, so better run it in parallel.
-                let proc_path = self.proc_path.clone();
+                let paths = self.paths.clone();
                 self.global_cpu.frequency = iter_mut(&mut self.cpus)
                     .enumerate()
                     .map(|(pos, proc_)| {
-                        proc_.frequency = get_cpu_frequency(&proc_path, pos);
+                        proc_.frequency = get_cpu_frequency(&paths, pos);
                         proc_.frequency
                     })
                     .max()
@@ -363,59 +495,27 @@ impl SystemExt for System {
     const SUPPORTED_SIGNALS: &'static [Signal] = supported_signals();
 
     fn new_with_specifics(refreshes: RefreshKind) -> System {
-        let proc_path = std::env::var_os("SYSINFO_PROC_PATH")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| PathBuf::from("/proc"));
-        let process_list = Process::new(Pid(0));
-        let mut s = System {
-            process_list,
-            mem_total: 0,
-            mem_free: 0,
-            mem_available: 0,
-            mem_buffers: 0,
-            mem_page_cache: 0,
-            mem_slab_reclaimable: 0,
-            swap_total: 0,
-            swap_free: 0,
-            global_cpu: Cpu::new_with_values(
-                "",
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                String::new(),
-                String::new(),
-            ),
-            cpus: Vec::with_capacity(4),
-            components: Vec::new(),
-            disks: Vec::with_capacity(2),
-            networks: Networks::new(),
-            users: Vec::new(),
-            need_cpus_update: true,
-            info: SystemInfo::new(&proc_path),
-            got_cpu_frequency: false,
-            proc_path,
-        };
-        s.refresh_specifics(refreshes);
-        s
+        SystemBuilder::from_env()
+            .with_refresh_kind(refreshes)
+            .build()
     }
 
     fn refresh_components_list(&mut self) {
-        self.components = component::get_components();
+        self.components = component::get_components(&self.paths.sys);
     }
 
     fn refresh_memory(&mut self) {
-        if let Ok(data) = get_all_data(self.proc_path.join("meminfo"), 16_385) {
+        self.info.errors.clear(RefreshArea::Memory);
+        let path = self.paths.proc.join("meminfo");
+        let data = get_all_data(&path, 16_385);
+        if let Err(ref e) = data {
+            self.info.errors.record(RefreshArea::Memory, &path, None, e);
+        }
+        if let Ok(data) = data {
             for line in data.split('\n') {
                 let mut iter = line.split(':');
-                let field = match iter.next() {
+                let name = iter.next();
+                let field = match name {
                     Some("MemTotal") => &mut self.mem_total,
                     Some("MemFree") => &mut self.mem_free,
                     Some("MemAvailable") => &mut self.mem_available,
@@ -424,13 +524,25 @@ impl SystemExt for System {
                     Some("SReclaimable") => &mut self.mem_slab_reclaimable,
                     Some("SwapTotal") => &mut self.swap_total,
                     Some("SwapFree") => &mut self.swap_free,
+                    Some("Dirty") => &mut self.memory_details.dirty,
+                    Some("Writeback") => &mut self.memory_details.writeback,
+                    Some("Shmem") => &mut self.memory_details.shared,
+                    Some("Committed_AS") => &mut self.memory_details.committed,
+                    Some("HugePages_Total") => &mut self.memory_details.huge_pages_total,
+                    Some("HugePages_Free") => &mut self.memory_details.huge_pages_free,
+                    Some("Hugepagesize") => &mut self.memory_details.huge_page_size_bytes,
                     _ => continue,
                 };
-                if let Some(val_str) = iter.next().and_then(|s| s.trim_start().split(' ').next()) {
-                    if let Ok(value) = u64::from_str(val_str) {
+                let mut parts = iter.next().unwrap_or_default().split_whitespace();
+                if let Some(Ok(value)) = parts.next().map(u64::from_str) {
+                    *field = match parts.next() {
+                        // The size of a huge page has to be exact, so it is kept in bytes.
+                        Some("kB") if name == Some("Hugepagesize") => value.saturating_mul(1024),
                         // /proc/meminfo reports KiB, though it says "kB". Convert it.
-                        *field = value.saturating_mul(128) / 125;
-                    }
+                        Some("kB") => value.saturating_mul(128) / 125,
+                        // The number of huge pages has no unit.
+                        _ => value,
+                    };
                 }
             }
         }
@@ -441,10 +553,11 @@ impl SystemExt for System {
     }
 
     fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
+        self.info.errors.clear(RefreshArea::Processes);
         let uptime = self.uptime();
         refresh_procs(
             &mut self.process_list,
-            &self.proc_path,
+            &self.paths.proc,
             Pid(0),
             uptime,
             &self.info,
@@ -457,7 +570,7 @@ impl SystemExt for System {
     fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
         let uptime = self.uptime();
         let found = match _get_process_data(
-            &self.proc_path.join(pid.to_string()),
+            &self.paths.proc.join(pid.to_string()),
             &mut self.process_list,
             Pid(0),
             uptime,
@@ -495,11 +608,57 @@ impl SystemExt for System {
     }
 
     fn refresh_disks_list(&mut self) {
-        self.disks = disk::get_all_disks(&self.proc_path);
+        self.info.errors.clear(RefreshArea::Disks);
+        let previous = std::mem::take(&mut self.disks);
+        self.disks =
+            disk::get_all_disks(&self.paths, &self.disk_options, &self.info.errors, previous);
     }
 
     fn refresh_users_list(&mut self) {
-        self.users = crate::users::get_users_list();
+        self.info.errors.clear(RefreshArea::Users);
+        let path = self.paths.etc.join("passwd");
+        self.users = match crate::users::get_users_list_from(&path) {
+            Ok(users) => users,
+            Err(e) => {
+                self.info.errors.record(RefreshArea::Users, &path, None, &e);
+                Vec::new()
+            }
+        };
+    }
+
+    fn refresh_pressure(&mut self) {
+        self.info.errors.clear(RefreshArea::Pressure);
+        self.pressure = get_pressure(&self.paths.proc, &self.info.errors);
+    }
+
+    fn refresh_disks_io(&mut self) {
+        self.info.errors.clear(RefreshArea::DisksIo);
+        self.disks_io = get_disks_io(
+            &self.paths,
+            &self.info.errors,
+            &self.disks,
+            &mut self.disk_counters,
+        );
+    }
+
+    fn refresh_memory_activity(&mut self) {
+        self.info.errors.clear(RefreshArea::MemoryActivity);
+        self.memory_activity =
+            get_memory_activity(&self.paths.proc, &self.info.errors, &mut self.vm_counters);
+    }
+
+    fn refresh_sockets(&mut self) {
+        self.info.errors.clear(RefreshArea::Sockets);
+        self.sockets = get_sockets(
+            &self.paths.proc,
+            &self.paths.net,
+            &self.info.errors,
+            &mut self.snmp_counters,
+        );
+    }
+
+    fn refresh_socket_processes(&mut self) {
+        set_listening_pids(&self.paths.proc, &mut self.sockets.listening);
     }
 
     // COMMON PART
@@ -531,7 +690,7 @@ impl SystemExt for System {
     }
 
     fn physical_core_count(&self) -> Option<usize> {
-        get_physical_core_count(&self.proc_path)
+        get_physical_core_count(&self.paths.proc)
     }
 
     fn total_memory(&self) -> u64 {
@@ -591,7 +750,7 @@ impl SystemExt for System {
     }
 
     fn uptime(&self) -> u64 {
-        let content = get_all_data(&self.proc_path.join("uptime"), 50).unwrap_or_default();
+        let content = self.read_system_file("uptime", 50).unwrap_or_default();
         content
             .split('.')
             .next()
@@ -604,13 +763,10 @@ impl SystemExt for System {
     }
 
     fn load_average(&self) -> LoadAvg {
-        let mut s = String::new();
-        if File::open(self.proc_path.join("loadavg"))
-            .and_then(|mut f| f.read_to_string(&mut s))
-            .is_err()
-        {
-            return LoadAvg::default();
-        }
+        let s = match self.read_system_file("loadavg", 50) {
+            Some(s) => s,
+            None => return LoadAvg::default(),
+        };
         let loads = s
             .trim()
             .split(' ')
@@ -624,16 +780,44 @@ impl SystemExt for System {
         }
     }
 
+    fn kernel_activity(&self) -> KernelActivity {
+        self.kernel_activity
+    }
+
+    fn pressure(&self) -> Pressure {
+        self.pressure
+    }
+
+    fn disks_io(&self) -> &[DiskIo] {
+        &self.disks_io
+    }
+
+    fn memory_details(&self) -> MemoryDetails {
+        self.memory_details
+    }
+
+    fn memory_activity(&self) -> MemoryActivity {
+        self.memory_activity
+    }
+
+    fn sockets(&self) -> &SocketStats {
+        &self.sockets
+    }
+
     fn users(&self) -> &[User] {
         &self.users
     }
 
+    fn refresh_errors(&self) -> Vec<RefreshError> {
+        self.info.errors.to_vec()
+    }
+
     #[cfg(not(target_os = "android"))]
     fn name(&self) -> Option<String> {
         get_system_info_linux(
             InfoType::Name,
-            Path::new("/etc/os-release"),
-            Path::new("/etc/lsb-release"),
+            &self.paths.etc.join("os-release"),
+            &self.paths.etc.join("lsb-release"),
         )
     }
 
@@ -699,8 +883,8 @@ impl SystemExt for System {
     fn os_version(&self) -> Option<String> {
         get_system_info_linux(
             InfoType::OsVersion,
-            Path::new("/etc/os-release"),
-            Path::new("/etc/lsb-release"),
+            &self.paths.etc.join("os-release"),
+            &self.paths.etc.join("lsb-release"),
         )
     }
 
@@ -866,4 +1050,30 @@ DISTRIB_DESCRIPTION="Ubuntu 20.10"
             Some("Ubuntu".to_owned())
         );
     }
+
+    #[test]
+    #[cfg(not(target_os = "android"))]
+    fn builder_uses_independent_roots() {
+        use crate::{RefreshKind, SystemBuilder, SystemExt};
+
+        let proc1 = tempfile::tempdir().expect("failed to create temporary directory");
+        let proc2 = tempfile::tempdir().expect("failed to create temporary directory");
+        std::fs::write(proc1.path().join("meminfo"), "MemTotal: 1000 kB\n")
+            .expect("failed to create meminfo");
+        std::fs::write(proc2.path().join("meminfo"), "MemTotal: 2000 kB\n")
+            .expect("failed to create meminfo");
+
+        let build = |proc_path| {
+            SystemBuilder::new()
+                .with_refresh_kind(RefreshKind::new().with_memory())
+                .with_proc_path(proc_path)
+                .with_open_files_limit(1)
+                .build()
+        };
+        let s1 = build(proc1.path());
+        let s2 = build(proc2.path());
+
+        assert_eq!(s1.total_memory(), 1024);
+        assert_eq!(s2.total_memory(), 2048);
+    }
 }
@@ -1,12 +1,134 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
 use std::{
-    fs::File,
+    collections::BTreeMap,
+    fs::{self, File},
     io::{self, Read, Seek, SeekFrom},
-    path::Path,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
-use crate::sys::system::REMAINING_FILES;
+use crate::{NetworkSource, Pid, RefreshArea, RefreshError, SystemBuilder};
+
+/// The roots of the file systems read by the Linux implementation. Each of them can be changed
+/// with the [`SystemBuilder`], so that a process running in a container can read the host's
+/// information from where it is mounted in the container.
+#[derive(Clone, Debug)]
+pub(crate) struct SystemPaths {
+    /// Replaces `/proc`.
+    pub(crate) proc: PathBuf,
+    /// Replaces `/sys`.
+    pub(crate) sys: PathBuf,
+    /// Replaces `/etc`.
+    pub(crate) etc: PathBuf,
+    /// Replaces `/dev`.
+    pub(crate) dev: PathBuf,
+    /// Where the root of the host is mounted, if it isn't `/`.
+    pub(crate) host_root: Option<PathBuf>,
+    /// Where the sockets are read from, `net` under [`SystemPaths::proc`] or `1/net` to read the
+    /// network namespace of the init process.
+    pub(crate) net: PathBuf,
+}
+
+impl SystemPaths {
+    pub(crate) fn new(builder: &SystemBuilder) -> Self {
+        fn or(path: &Option<PathBuf>, default: &str) -> PathBuf {
+            path.clone().unwrap_or_else(|| PathBuf::from(default))
+        }
+
+        let proc = or(&builder.proc_path, "/proc");
+        SystemPaths {
+            net: match builder.network_source {
+                NetworkSource::HostProcNetDev => proc.join("1/net"),
+                NetworkSource::Sysfs | NetworkSource::ProcNetDev => proc.join("net"),
+            },
+            proc,
+            sys: or(&builder.sys_path, "/sys"),
+            etc: or(&builder.etc_path, "/etc"),
+            dev: or(&builder.dev_path, "/dev"),
+            host_root: builder.host_root_path.clone(),
+        }
+    }
+
+    /// The path where a path of the host can be accessed, under [`SystemPaths::host_root`].
+    pub(crate) fn host_path(&self, path: &Path) -> PathBuf {
+        match self.host_root {
+            Some(ref host_root) => host_root.join(path.strip_prefix("/").unwrap_or(path)),
+            None => path.to_owned(),
+        }
+    }
+
+    /// Canonicalizes a path under `/dev` (like `/dev/mapper/root`), resolving it under
+    /// [`SystemPaths::dev`] instead. The result is under `/dev` again so that it can be compared
+    /// to the device names listed in `/proc`.
+    pub(crate) fn canonicalize_dev(&self, path: &Path) -> Option<PathBuf> {
+        let relative = path.strip_prefix("/dev").ok()?;
+        let dev = fs::canonicalize(&self.dev).unwrap_or_else(|_| self.dev.clone());
+        let real = fs::canonicalize(dev.join(relative)).ok()?;
+        let relative = real.strip_prefix(&dev).ok()?;
+        Some(Path::new("/dev").join(relative))
+    }
+}
+
+/// The errors which happened during the latest refresh of each [`RefreshArea`].
+#[derive(Debug, Default)]
+pub(crate) struct RefreshErrors {
+    errors: Mutex<BTreeMap<(RefreshArea, PathBuf), RefreshError>>,
+}
+
+impl RefreshErrors {
+    /// The maximum number of errors kept, so that a refresh failing for every process doesn't use
+    /// too much memory.
+    const MAX_ERRORS: usize = 1_000;
+
+    /// Records an error, unless it only means that the process exited during the refresh.
+    pub(crate) fn record(
+        &self,
+        area: RefreshArea,
+        path: &Path,
+        pid: Option<Pid>,
+        error: &io::Error,
+    ) {
+        if pid.is_some() && is_process_gone(error) {
+            return;
+        }
+        if let Ok(mut errors) = self.errors.lock() {
+            if errors.len() < Self::MAX_ERRORS {
+                let key = (area, path.to_owned());
+                let error = RefreshError::new(area, path.to_owned(), pid, error);
+                errors.insert(key, error);
+            }
+        }
+    }
+
+    /// Removes the error for a file which could be read again.
+    pub(crate) fn resolve(&self, area: RefreshArea, path: &Path) {
+        if let Ok(mut errors) = self.errors.lock() {
+            if !errors.is_empty() {
+                errors.remove(&(area, path.to_owned()));
+            }
+        }
+    }
+
+    /// Removes the errors of an area before it is refreshed again.
+    pub(crate) fn clear(&self, area: RefreshArea) {
+        if let Ok(mut errors) = self.errors.lock() {
+            errors.retain(|(error_area, _), _| *error_area != area);
+        }
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<RefreshError> {
+        match self.errors.lock() {
+            Ok(errors) => errors.values().cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Whether reading a file of a process failed because the process exited.
+fn is_process_gone(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::NotFound || error.raw_os_error() == Some(libc::ESRCH)
+}
 
 pub(crate) fn get_all_data_from_file(file: &mut File, size: usize) -> io::Result<String> {
     let mut buf = String::with_capacity(size);
@@ -20,6 +142,16 @@ pub(crate) fn get_all_data<P: AsRef<Path>>(file_path: P, size: usize) -> io::Res
     get_all_data_from_file(&mut file, size)
 }
 
+/// Reads the uptime with a hundredth of a second of precision, unlike `SystemExt::uptime`. It is
+/// used to know how much time passed between two refreshes.
+pub(crate) fn get_precise_uptime(proc_path: &Path) -> io::Result<f64> {
+    get_all_data(proc_path.join("uptime"), 50)?
+        .split_whitespace()
+        .next()
+        .and_then(|uptime| uptime.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid uptime"))
+}
+
 #[allow(clippy::useless_conversion)]
 pub(crate) fn realpath(original: &Path) -> std::path::PathBuf {
     use libc::{lstat, stat, S_IFLNK, S_IFMT};
@@ -56,19 +188,18 @@ pub(crate) fn realpath(original: &Path) -> std::path::PathBuf {
     }
 }
 
-/// Type used to correctly handle the `REMAINING_FILES` global.
-pub(crate) struct FileCounter(File);
+/// Type used to correctly handle the number of remaining files a `System` can keep open. This is
+/// usually the `REMAINING_FILES` global.
+pub(crate) struct FileCounter(File, Arc<Mutex<isize>>);
 
 impl FileCounter {
-    pub(crate) fn new(f: File) -> Option<Self> {
-        unsafe {
-            if let Ok(ref mut x) = REMAINING_FILES.lock() {
-                if **x > 0 {
-                    **x -= 1;
-                    return Some(Self(f));
-                }
-                // All file descriptors we were allowed are being used.
+    pub(crate) fn new(f: File, remaining_files: &Arc<Mutex<isize>>) -> Option<Self> {
+        if let Ok(ref mut x) = remaining_files.lock() {
+            if **x > 0 {
+                **x -= 1;
+                return Some(Self(f, remaining_files.clone()));
             }
+            // All file descriptors we were allowed are being used.
         }
         None
     }
@@ -89,10 +220,31 @@ impl std::ops::DerefMut for FileCounter {
 
 impl Drop for FileCounter {
     fn drop(&mut self) {
-        unsafe {
-            if let Ok(ref mut x) = crate::sys::system::REMAINING_FILES.lock() {
-                **x += 1;
-            }
+        if let Ok(ref mut x) = self.1.lock() {
+            **x += 1;
         }
     }
 }
+
+#[cfg(all(test, not(target_os = "android")))]
+mod test {
+    use super::SystemPaths;
+    use crate::SystemBuilder;
+    use std::{fs, os::unix::fs::symlink, path::Path};
+
+    #[test]
+    fn canonicalize_dev_in_other_root() {
+        let dev = tempfile::tempdir().expect("failed to create temporary directory");
+        fs::create_dir(dev.path().join("mapper")).expect("failed to create subdirectory");
+        fs::write(dev.path().join("dm-0"), "").expect("failed to create device");
+        symlink("../dm-0", dev.path().join("mapper/root")).expect("failed to create symlink");
+        let paths = SystemPaths::new(&SystemBuilder::new().with_dev_path(dev.path()));
+
+        assert_eq!(
+            paths.canonicalize_dev(Path::new("/dev/mapper/root")),
+            Some(Path::new("/dev/dm-0").to_owned())
+        );
+        assert_eq!(paths.canonicalize_dev(Path::new("/dev/missing")), None);
+        assert_eq!(paths.canonicalize_dev(Path::new("/other/dm-0")), None);
+    }
+}
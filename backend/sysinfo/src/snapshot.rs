@@ -0,0 +1,434 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{
+    Component, ComponentExt, Cpu, CpuExt, CpuUsageBreakdown, DeviceId, Disk, DiskExt, DiskIo,
+    DiskType, DiskUsage, Duplex, Gid, IpNetwork, KernelActivity, LoadAvg, MacAddr, MemoryActivity,
+    MemoryDetails, NetworkData, NetworkExt, NetworksExt, OpenFile, OperationalState, Pid, Pressure,
+    Process, ProcessExt, ProcessLimits, ProcessStatus, SocketStats, System, SystemExt, Uid, User,
+};
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// An owned copy of everything a [`System`] contains at one point in time.
+///
+/// Unlike [`System`], it can be cloned, compared and, with the `serde` feature, serialized and
+/// deserialized. This makes it possible to record, diff or forward the information without
+/// mapping it by hand.
+///
+/// Paths and names which aren't valid UTF-8 are converted lossily.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt, SystemSnapshot};
+///
+/// let s = System::new_all();
+/// let snapshot = SystemSnapshot::from(&s);
+/// println!("{} processes", snapshot.processes.len());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemSnapshot {
+    /// See [`SystemExt::global_cpu_info`].
+    pub global_cpu: CpuSnapshot,
+    /// See [`SystemExt::cpus`].
+    pub cpus: Vec<CpuSnapshot>,
+    /// See [`SystemExt::physical_core_count`].
+    pub physical_core_count: Option<usize>,
+    /// See [`SystemExt::total_memory`].
+    pub total_memory: u64,
+    /// See [`SystemExt::free_memory`].
+    pub free_memory: u64,
+    /// See [`SystemExt::available_memory`].
+    pub available_memory: u64,
+    /// See [`SystemExt::used_memory`].
+    pub used_memory: u64,
+    /// See [`SystemExt::total_swap`].
+    pub total_swap: u64,
+    /// See [`SystemExt::free_swap`].
+    pub free_swap: u64,
+    /// See [`SystemExt::used_swap`].
+    pub used_swap: u64,
+    /// See [`SystemExt::memory_details`].
+    pub memory_details: MemoryDetails,
+    /// See [`SystemExt::memory_activity`].
+    pub memory_activity: MemoryActivity,
+    /// See [`SystemExt::processes`].
+    pub processes: BTreeMap<Pid, ProcessSnapshot>,
+    /// See [`SystemExt::components`].
+    pub components: Vec<ComponentSnapshot>,
+    /// See [`SystemExt::users`].
+    pub users: Vec<User>,
+    /// See [`SystemExt::disks`].
+    pub disks: Vec<DiskSnapshot>,
+    /// See [`SystemExt::disks_io`].
+    pub disks_io: Vec<DiskIo>,
+    /// See [`SystemExt::networks`], by interface name.
+    pub networks: BTreeMap<String, NetworkSnapshot>,
+    /// See [`SystemExt::sockets`].
+    pub sockets: SocketStats,
+    /// See [`SystemExt::uptime`].
+    pub uptime: u64,
+    /// See [`SystemExt::boot_time`].
+    pub boot_time: u64,
+    /// See [`SystemExt::load_average`].
+    pub load_average: LoadAvg,
+    /// See [`SystemExt::kernel_activity`].
+    pub kernel_activity: KernelActivity,
+    /// See [`SystemExt::pressure`].
+    pub pressure: Pressure,
+    /// See [`SystemExt::name`].
+    pub name: Option<String>,
+    /// See [`SystemExt::kernel_version`].
+    pub kernel_version: Option<String>,
+    /// See [`SystemExt::os_version`].
+    pub os_version: Option<String>,
+    /// See [`SystemExt::long_os_version`].
+    pub long_os_version: Option<String>,
+    /// See [`SystemExt::host_name`].
+    pub host_name: Option<String>,
+}
+
+impl From<&System> for SystemSnapshot {
+    fn from(system: &System) -> Self {
+        SystemSnapshot {
+            global_cpu: system.global_cpu_info().into(),
+            cpus: system.cpus().iter().map(CpuSnapshot::from).collect(),
+            physical_core_count: system.physical_core_count(),
+            total_memory: system.total_memory(),
+            free_memory: system.free_memory(),
+            available_memory: system.available_memory(),
+            used_memory: system.used_memory(),
+            total_swap: system.total_swap(),
+            free_swap: system.free_swap(),
+            used_swap: system.used_swap(),
+            memory_details: system.memory_details(),
+            memory_activity: system.memory_activity(),
+            processes: system
+                .processes()
+                .iter()
+                .map(|(pid, process)| (*pid, process.into()))
+                .collect(),
+            components: system
+                .components()
+                .iter()
+                .map(ComponentSnapshot::from)
+                .collect(),
+            users: system.users().to_vec(),
+            disks: system.disks().iter().map(DiskSnapshot::from).collect(),
+            disks_io: system.disks_io().to_vec(),
+            networks: system
+                .networks()
+                .iter()
+                .map(|(name, data)| (name.clone(), data.into()))
+                .collect(),
+            sockets: system.sockets().clone(),
+            uptime: system.uptime(),
+            boot_time: system.boot_time(),
+            load_average: system.load_average(),
+            kernel_activity: system.kernel_activity(),
+            pressure: system.pressure(),
+            name: system.name(),
+            kernel_version: system.kernel_version(),
+            os_version: system.os_version(),
+            long_os_version: system.long_os_version(),
+            host_name: system.host_name(),
+        }
+    }
+}
+
+/// An owned copy of a [`Cpu`]. See [`CpuExt`] for the meaning of the fields.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CpuSnapshot {
+    /// See [`CpuExt::name`].
+    pub name: String,
+    /// See [`CpuExt::cpu_usage`].
+    pub cpu_usage: f32,
+    /// See [`CpuExt::usage_breakdown`].
+    pub usage_breakdown: CpuUsageBreakdown,
+    /// See [`CpuExt::frequency`].
+    pub frequency: u64,
+    /// See [`CpuExt::vendor_id`].
+    pub vendor_id: String,
+    /// See [`CpuExt::brand`].
+    pub brand: String,
+}
+
+impl From<&Cpu> for CpuSnapshot {
+    fn from(cpu: &Cpu) -> Self {
+        CpuSnapshot {
+            name: cpu.name().to_owned(),
+            cpu_usage: cpu.cpu_usage(),
+            usage_breakdown: cpu.usage_breakdown(),
+            frequency: cpu.frequency(),
+            vendor_id: cpu.vendor_id().to_owned(),
+            brand: cpu.brand().to_owned(),
+        }
+    }
+}
+
+/// An owned copy of a [`Process`]. See [`ProcessExt`] for the meaning of the fields.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessSnapshot {
+    /// See [`ProcessExt::pid`].
+    pub pid: Pid,
+    /// See [`ProcessExt::parent`].
+    pub parent: Option<Pid>,
+    /// See [`ProcessExt::name`].
+    pub name: String,
+    /// See [`ProcessExt::cmd`].
+    pub cmd: Vec<String>,
+    /// See [`ProcessExt::exe`].
+    pub exe: PathBuf,
+    /// See [`ProcessExt::environ`].
+    pub environ: Vec<String>,
+    /// See [`ProcessExt::cwd`].
+    pub cwd: PathBuf,
+    /// See [`ProcessExt::root`].
+    pub root: PathBuf,
+    /// See [`ProcessExt::memory`].
+    pub memory: u64,
+    /// See [`ProcessExt::virtual_memory`].
+    pub virtual_memory: u64,
+    /// See [`ProcessExt::status`].
+    pub status: ProcessStatus,
+    /// See [`ProcessExt::start_time`].
+    pub start_time: u64,
+    /// See [`ProcessExt::run_time`].
+    pub run_time: u64,
+    /// See [`ProcessExt::cpu_usage`].
+    pub cpu_usage: f32,
+    /// See [`ProcessExt::disk_usage`].
+    pub disk_usage: DiskUsage,
+    /// See [`ProcessExt::user_id`].
+    pub user_id: Option<Uid>,
+    /// See [`ProcessExt::group_id`].
+    pub group_id: Option<Gid>,
+    /// See [`ProcessExt::open_files`].
+    pub open_files: Option<Vec<OpenFile>>,
+    /// See [`ProcessExt::limits`].
+    pub limits: Option<ProcessLimits>,
+}
+
+impl From<&Process> for ProcessSnapshot {
+    fn from(process: &Process) -> Self {
+        ProcessSnapshot {
+            pid: process.pid(),
+            parent: process.parent(),
+            name: process.name().to_owned(),
+            cmd: process.cmd().to_vec(),
+            exe: lossy_path(process.exe()),
+            environ: process.environ().to_vec(),
+            cwd: lossy_path(process.cwd()),
+            root: lossy_path(process.root()),
+            memory: process.memory(),
+            virtual_memory: process.virtual_memory(),
+            status: process.status(),
+            start_time: process.start_time(),
+            run_time: process.run_time(),
+            cpu_usage: process.cpu_usage(),
+            disk_usage: process.disk_usage(),
+            user_id: process.user_id().cloned(),
+            group_id: process.group_id(),
+            open_files: process.open_files().map(|files| {
+                files
+                    .iter()
+                    .map(|file| OpenFile {
+                        target: lossy_path(&file.target),
+                        ..file.clone()
+                    })
+                    .collect()
+            }),
+            limits: process.limits().cloned(),
+        }
+    }
+}
+
+/// An owned copy of a [`Disk`]. See [`DiskExt`] for the meaning of the fields.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiskSnapshot {
+    /// See [`DiskExt::name`].
+    pub name: String,
+    /// See [`DiskExt::type_`].
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: DiskType,
+    /// See [`DiskExt::file_system`].
+    pub file_system: String,
+    /// See [`DiskExt::mount_point`].
+    pub mount_point: PathBuf,
+    /// See [`DiskExt::total_space`].
+    pub total_space: u64,
+    /// See [`DiskExt::available_space`].
+    pub available_space: u64,
+    /// See [`DiskExt::is_removable`].
+    pub is_removable: bool,
+    /// See [`DiskExt::total_inodes`].
+    pub total_inodes: u64,
+    /// See [`DiskExt::available_inodes`].
+    pub available_inodes: u64,
+    /// See [`DiskExt::mount_options`].
+    pub mount_options: Vec<String>,
+    /// See [`DiskExt::is_read_only`].
+    pub is_read_only: bool,
+    /// See [`DiskExt::device_id`].
+    pub device_id: Option<DeviceId>,
+    /// See [`DiskExt::is_unreachable`].
+    pub is_unreachable: bool,
+}
+
+impl From<&Disk> for DiskSnapshot {
+    fn from(disk: &Disk) -> Self {
+        DiskSnapshot {
+            name: disk.name().to_string_lossy().into_owned(),
+            type_: disk.type_(),
+            file_system: String::from_utf8_lossy(disk.file_system()).into_owned(),
+            mount_point: lossy_path(disk.mount_point()),
+            total_space: disk.total_space(),
+            available_space: disk.available_space(),
+            is_removable: disk.is_removable(),
+            total_inodes: disk.total_inodes(),
+            available_inodes: disk.available_inodes(),
+            mount_options: disk.mount_options().to_vec(),
+            is_read_only: disk.is_read_only(),
+            device_id: disk.device_id(),
+            is_unreachable: disk.is_unreachable(),
+        }
+    }
+}
+
+/// An owned copy of a [`NetworkData`]. See [`NetworkExt`] for the meaning of the fields.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetworkSnapshot {
+    /// See [`NetworkExt::received`].
+    pub received: u64,
+    /// See [`NetworkExt::total_received`].
+    pub total_received: u64,
+    /// See [`NetworkExt::transmitted`].
+    pub transmitted: u64,
+    /// See [`NetworkExt::total_transmitted`].
+    pub total_transmitted: u64,
+    /// See [`NetworkExt::packets_received`].
+    pub packets_received: u64,
+    /// See [`NetworkExt::total_packets_received`].
+    pub total_packets_received: u64,
+    /// See [`NetworkExt::packets_transmitted`].
+    pub packets_transmitted: u64,
+    /// See [`NetworkExt::total_packets_transmitted`].
+    pub total_packets_transmitted: u64,
+    /// See [`NetworkExt::errors_on_received`].
+    pub errors_on_received: u64,
+    /// See [`NetworkExt::total_errors_on_received`].
+    pub total_errors_on_received: u64,
+    /// See [`NetworkExt::errors_on_transmitted`].
+    pub errors_on_transmitted: u64,
+    /// See [`NetworkExt::total_errors_on_transmitted`].
+    pub total_errors_on_transmitted: u64,
+    /// See [`NetworkExt::received_per_second`].
+    pub received_per_second: f64,
+    /// See [`NetworkExt::transmitted_per_second`].
+    pub transmitted_per_second: f64,
+    /// See [`NetworkExt::mac_address`].
+    pub mac_address: MacAddr,
+    /// See [`NetworkExt::ip_networks`].
+    pub ip_networks: Vec<IpNetwork>,
+    /// See [`NetworkExt::mtu`].
+    pub mtu: u64,
+    /// See [`NetworkExt::operational_state`].
+    pub operational_state: OperationalState,
+    /// See [`NetworkExt::carrier`].
+    pub carrier: Option<bool>,
+    /// See [`NetworkExt::duplex`].
+    pub duplex: Duplex,
+    /// See [`NetworkExt::speed`].
+    pub speed: Option<u64>,
+}
+
+impl From<&NetworkData> for NetworkSnapshot {
+    fn from(data: &NetworkData) -> Self {
+        NetworkSnapshot {
+            received: data.received(),
+            total_received: data.total_received(),
+            transmitted: data.transmitted(),
+            total_transmitted: data.total_transmitted(),
+            packets_received: data.packets_received(),
+            total_packets_received: data.total_packets_received(),
+            packets_transmitted: data.packets_transmitted(),
+            total_packets_transmitted: data.total_packets_transmitted(),
+            errors_on_received: data.errors_on_received(),
+            total_errors_on_received: data.total_errors_on_received(),
+            errors_on_transmitted: data.errors_on_transmitted(),
+            total_errors_on_transmitted: data.total_errors_on_transmitted(),
+            received_per_second: data.received_per_second(),
+            transmitted_per_second: data.transmitted_per_second(),
+            mac_address: data.mac_address(),
+            ip_networks: data.ip_networks().to_vec(),
+            mtu: data.mtu(),
+            operational_state: data.operational_state(),
+            carrier: data.carrier(),
+            duplex: data.duplex(),
+            speed: data.speed(),
+        }
+    }
+}
+
+/// An owned copy of a [`Component`]. See [`ComponentExt`] for the meaning of the fields.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentSnapshot {
+    /// See [`ComponentExt::label`].
+    pub label: String,
+    /// See [`ComponentExt::temperature`].
+    pub temperature: f32,
+    /// See [`ComponentExt::max`].
+    pub max: f32,
+    /// See [`ComponentExt::critical`].
+    pub critical: Option<f32>,
+}
+
+impl From<&Component> for ComponentSnapshot {
+    fn from(component: &Component) -> Self {
+        ComponentSnapshot {
+            label: component.label().to_owned(),
+            temperature: component.temperature(),
+            max: component.max(),
+            critical: component.critical(),
+        }
+    }
+}
+
+fn lossy_path(path: &Path) -> PathBuf {
+    match path.to_str() {
+        Some(_) => path.to_owned(),
+        None => PathBuf::from(path.to_string_lossy().into_owned()),
+    }
+}
+
+// The platform types serialize as their snapshots, so both have the same representation.
+#[cfg(feature = "serde")]
+mod serialize {
+    use super::*;
+
+    macro_rules! serialize_as {
+        ($($ty:ty => $snapshot:ty,)+) => {
+            $(
+                impl serde::Serialize for $ty {
+                    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        <$snapshot>::from(self).serialize(serializer)
+                    }
+                }
+            )+
+        };
+    }
+
+    serialize_as! {
+        Cpu => CpuSnapshot,
+        Process => ProcessSnapshot,
+        Disk => DiskSnapshot,
+        NetworkData => NetworkSnapshot,
+        Component => ComponentSnapshot,
+        System => SystemSnapshot,
+    }
+}
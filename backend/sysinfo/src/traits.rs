@@ -3,8 +3,10 @@
 use crate::{
     common::{Gid, Uid},
     sys::{Component, Cpu, Disk, Networks, Process},
-    CpuRefreshKind, DiskType, DiskUsage, LoadAvg, NetworksIter, Pid, ProcessRefreshKind,
-    ProcessStatus, RefreshKind, Signal, User,
+    CpuRefreshKind, CpuUsageBreakdown, DeviceId, DiskIo, DiskType, DiskUsage, Duplex, IpNetwork,
+    KernelActivity, LoadAvg, MacAddr, MemoryActivity, MemoryDetails, NetworksIter, OpenFile,
+    OperationalState, Pid, Pressure, ProcessLimits, ProcessRefreshKind, ProcessStatus,
+    RefreshError, RefreshKind, Signal, SocketStats, User,
 };
 
 use std::{collections::HashMap, ffi::OsStr, fmt::Debug, path::Path};
@@ -104,6 +106,107 @@ pub trait DiskExt: Debug {
     /// ```
     fn is_removable(&self) -> bool;
 
+    /// Returns the total number of inodes of the file system.
+    ///
+    /// Only Linux reports it, other platforms return 0. File systems which allocate inodes
+    /// dynamically, like Btrfs, also report 0.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.disks() {
+    ///     println!("{}", disk.total_inodes());
+    /// }
+    /// ```
+    fn total_inodes(&self) -> u64 {
+        0
+    }
+
+    /// Returns the number of inodes which can still be allocated. A file system can run out of
+    /// inodes while it still has free space.
+    ///
+    /// Only Linux reports it, other platforms return 0.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.disks() {
+    ///     println!("{}", disk.available_inodes());
+    /// }
+    /// ```
+    fn available_inodes(&self) -> u64 {
+        0
+    }
+
+    /// Returns the options the file system is mounted with, like `rw`, `noexec` or
+    /// `relatime`.
+    ///
+    /// Only Linux reports them, other platforms return an empty list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.disks() {
+    ///     println!("{}", disk.mount_options().join(","));
+    /// }
+    /// ```
+    fn mount_options(&self) -> &[String] {
+        &[]
+    }
+
+    /// Returns `true` if the file system is mounted read-only.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.disks() {
+    ///     println!("{}", disk.is_read_only());
+    /// }
+    /// ```
+    fn is_read_only(&self) -> bool {
+        self.mount_options().iter().any(|option| option == "ro")
+    }
+
+    /// Returns the device the file system is on, as listed in the mount table. File systems
+    /// without a block device, like NFS or overlayfs, have an anonymous device whose major is 0.
+    ///
+    /// Only Linux reports it, other platforms return `None`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.disks() {
+    ///     println!("{:?}", disk.device_id());
+    /// }
+    /// ```
+    fn device_id(&self) -> Option<DeviceId> {
+        None
+    }
+
+    /// Returns `true` if the file system didn't answer in time during the latest refresh, see
+    /// [`SystemBuilder::with_disk_timeout`]. The space is then the last known one.
+    ///
+    /// Only Linux reports it, other platforms return `false`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.disks() {
+    ///     println!("{}", disk.is_unreachable());
+    /// }
+    /// ```
+    ///
+    /// [`SystemBuilder::with_disk_timeout`]: crate::SystemBuilder::with_disk_timeout
+    fn is_unreachable(&self) -> bool {
+        false
+    }
+
     /// Updates the disk' information.
     ///
     /// ```no_run
@@ -407,6 +510,48 @@ pub trait ProcessExt: Debug {
     /// }
     /// ```
     fn group_id(&self) -> Option<Gid>;
+
+    /// Returns the file descriptors opened by the process, sorted by number, or `None` if they
+    /// couldn't be read, like for the processes of other users when not running as root.
+    ///
+    /// Only Linux reports them, other platforms return `None`. They are only read when
+    /// [`ProcessRefreshKind::open_files`][crate::ProcessRefreshKind::open_files] is set, which
+    /// [`System::new_all`][crate::System::new_all] doesn't do.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, ProcessRefreshKind, RefreshKind, System, SystemExt};
+    ///
+    /// let s = System::new_with_specifics(
+    ///     RefreshKind::new().with_processes(ProcessRefreshKind::new().with_open_files()),
+    /// );
+    /// if let Some(process) = s.process(Pid::from(1337)) {
+    ///     if let Some(files) = process.open_files() {
+    ///         println!("{} open files", files.len());
+    ///     }
+    /// }
+    /// ```
+    fn open_files(&self) -> Option<&[OpenFile]> {
+        None
+    }
+
+    /// Returns the resource limits of the process, or `None` if they couldn't be read.
+    ///
+    /// Only Linux reports them, other platforms return `None`. They are only read when
+    /// [`ProcessRefreshKind::limits`][crate::ProcessRefreshKind::limits] is set.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.process(Pid::from(1337)) {
+    ///     if let Some(limits) = process.limits() {
+    ///         println!("at most {:?} open files", limits.open_files.soft);
+    ///     }
+    /// }
+    /// ```
+    fn limits(&self) -> Option<&ProcessLimits> {
+        None
+    }
 }
 
 /// Contains all the methods of the [`Cpu`][crate::Cpu] struct.
@@ -473,6 +618,23 @@ pub trait CpuExt: Debug {
     /// }
     /// ```
     fn frequency(&self) -> u64;
+
+    /// Returns how this CPU's time was spent since the previous refresh.
+    ///
+    /// Like [`CpuExt::cpu_usage`], it needs the CPUs to be refreshed at least twice to be
+    /// meaningful. Only Linux reports it, other platforms return all zeros.
+    ///
+    /// ```no_run
+    /// use sysinfo::{CpuExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for cpu in s.cpus() {
+    ///     println!("{}% steal", cpu.usage_breakdown().steal);
+    /// }
+    /// ```
+    fn usage_breakdown(&self) -> CpuUsageBreakdown {
+        CpuUsageBreakdown::default()
+    }
 }
 
 /// Contains all the methods of the [`System`][crate::System] type.
@@ -596,9 +758,24 @@ pub trait SystemExt: Sized + Debug + Default + Send + Sync {
         } else if refreshes.disks() {
             self.refresh_disks();
         }
+        if refreshes.disks_io() {
+            self.refresh_disks_io();
+        }
         if refreshes.users_list() {
             self.refresh_users_list();
         }
+        if refreshes.pressure() {
+            self.refresh_pressure();
+        }
+        if refreshes.memory_activity() {
+            self.refresh_memory_activity();
+        }
+        if refreshes.sockets() {
+            self.refresh_sockets();
+        }
+        if refreshes.socket_processes() {
+            self.refresh_socket_processes();
+        }
     }
 
     /// Refreshes all system, processes, disks and network interfaces information.
@@ -799,6 +976,71 @@ pub trait SystemExt: Sized + Debug + Default + Send + Sync {
     /// ```
     fn refresh_users_list(&mut self);
 
+    /// Refreshes the pressure stall information.
+    ///
+    /// It does nothing on platforms other than Linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_pressure();
+    /// ```
+    fn refresh_pressure(&mut self) {}
+
+    /// Refreshes the activity of the virtual memory subsystem.
+    ///
+    /// It does nothing on platforms other than Linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_memory_activity();
+    /// ```
+    fn refresh_memory_activity(&mut self) {}
+
+    /// Refreshes the I/O activity of the block devices.
+    ///
+    /// It does nothing on platforms other than Linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_disks_io();
+    /// ```
+    fn refresh_disks_io(&mut self) {}
+
+    /// Refreshes the sockets and the activity of the TCP and UDP protocols.
+    ///
+    /// It does nothing on platforms other than Linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_sockets();
+    /// ```
+    fn refresh_sockets(&mut self) {}
+
+    /// Finds the processes which have the listening sockets of the last call to
+    /// [`SystemExt::refresh_sockets`] open, in [`ListeningSocket::pids`].
+    ///
+    /// On Linux, every file descriptor of every process is read, so it is much slower than
+    /// refreshing the sockets. It does nothing on other platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_sockets();
+    /// s.refresh_socket_processes();
+    /// ```
+    ///
+    /// [`ListeningSocket::pids`]: crate::ListeningSocket::pids
+    fn refresh_socket_processes(&mut self) {}
+
     /// Refreshes networks data.
     ///
     /// ```no_run
@@ -1182,6 +1424,105 @@ pub trait SystemExt: Sized + Debug + Default + Send + Sync {
     /// ```
     fn load_average(&self) -> LoadAvg;
 
+    /// Returns the activity of the kernel scheduler, as of the last CPU refresh with
+    /// [`CpuRefreshKind::kernel_activity`] set.
+    ///
+    /// Only Linux reports it, other platforms return all zeros.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} forks/s", s.kernel_activity().forks);
+    /// ```
+    fn kernel_activity(&self) -> KernelActivity {
+        KernelActivity::default()
+    }
+
+    /// Returns the pressure stall information, as of the last call to
+    /// [`SystemExt::refresh_pressure`].
+    ///
+    /// Only Linux reports it, other platforms return `None` for every resource.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_pressure();
+    /// if let Some(io) = s.pressure().io {
+    ///     println!("{}% of the last minute stalled on I/O", io.some.avg60);
+    /// }
+    /// ```
+    fn pressure(&self) -> Pressure {
+        Pressure::default()
+    }
+
+    /// Returns the parts of `/proc/meminfo` which aren't covered by the other memory methods, as
+    /// of the last memory refresh.
+    ///
+    /// Only Linux reports them, other platforms return all zeros.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} KB of shared memory", s.memory_details().shared);
+    /// ```
+    fn memory_details(&self) -> MemoryDetails {
+        MemoryDetails::default()
+    }
+
+    /// Returns the activity of the virtual memory subsystem, as of the last call to
+    /// [`SystemExt::refresh_memory_activity`].
+    ///
+    /// Only Linux reports it, other platforms return all zeros.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_memory_activity();
+    /// println!("{} pages swapped out/s", s.memory_activity().pages_swapped_out);
+    /// ```
+    fn memory_activity(&self) -> MemoryActivity {
+        MemoryActivity::default()
+    }
+
+    /// Returns the I/O activity of the block devices which were used since boot, as of the last
+    /// call to [`SystemExt::refresh_disks_io`].
+    ///
+    /// Only Linux reports it, other platforms return an empty list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_disks_list();
+    /// s.refresh_disks_io();
+    /// for io in s.disks_io() {
+    ///     println!("{:?} written at {} B/s", io.mount_points, io.written_bytes);
+    /// }
+    /// ```
+    fn disks_io(&self) -> &[DiskIo] {
+        &[]
+    }
+
+    /// Returns the sockets and the activity of the TCP and UDP protocols, as of the last call to
+    /// [`SystemExt::refresh_sockets`].
+    ///
+    /// Only Linux reports them, other platforms return no sockets and all zeros.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_sockets();
+    /// for socket in &s.sockets().listening {
+    ///     println!("{:?} is listened on by {:?}", socket.address, socket.pids);
+    /// }
+    /// ```
+    fn sockets(&self) -> &SocketStats;
+
     /// Returns the system name.
     ///
     /// **Important**: this information is computed every time this function is called.
@@ -1242,6 +1583,24 @@ pub trait SystemExt: Sized + Debug + Default + Send + Sync {
     /// ```
     fn host_name(&self) -> Option<String>;
 
+    /// Returns the errors which happened while reading the system's information.
+    ///
+    /// Each refresh replaces the errors for the information it reads (its
+    /// [`RefreshArea`][crate::RefreshArea]), so these are the errors of the latest refresh of each
+    /// kind of information. They are only collected on Linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for error in s.refresh_errors() {
+    ///     eprintln!("{}", error);
+    /// }
+    /// ```
+    fn refresh_errors(&self) -> Vec<RefreshError> {
+        Vec::new()
+    }
+
     /// Returns the [`User`] matching the given `user_id`.
     ///
     /// **Important**: The user list must be filled before using this method, otherwise it will
@@ -1429,6 +1788,157 @@ pub trait NetworkExt: Debug {
     /// }
     /// ```
     fn total_errors_on_transmitted(&self) -> u64;
+
+    /// Returns the number of bytes received per second between the last two refreshes.
+    ///
+    /// Only Linux reports it, other platforms return 0.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     println!("in: {} B/s", network.received_per_second());
+    /// }
+    /// ```
+    fn received_per_second(&self) -> f64 {
+        0.
+    }
+
+    /// Returns the number of bytes transmitted per second between the last two refreshes.
+    ///
+    /// Only Linux reports it, other platforms return 0.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     println!("out: {} B/s", network.transmitted_per_second());
+    /// }
+    /// ```
+    fn transmitted_per_second(&self) -> f64 {
+        0.
+    }
+
+    /// Returns the MAC address of the interface.
+    ///
+    /// Only Linux reports it, other platforms return [`MacAddr::UNSPECIFIED`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     println!("{}: {}", interface_name, network.mac_address());
+    /// }
+    /// ```
+    fn mac_address(&self) -> MacAddr {
+        MacAddr::UNSPECIFIED
+    }
+
+    /// Returns the IPv4 and IPv6 addresses of the interface.
+    ///
+    /// Only Linux reports them, other platforms return an empty list. They are always those of the
+    /// network namespace the process runs in, even if the interfaces are read from another one.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     for ip_network in network.ip_networks() {
+    ///         println!("{}: {}", interface_name, ip_network);
+    ///     }
+    /// }
+    /// ```
+    fn ip_networks(&self) -> &[IpNetwork] {
+        &[]
+    }
+
+    /// Returns the largest packet the interface can transmit, in bytes.
+    ///
+    /// Only Linux reports it, other platforms return 0.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     println!("{}: {} B", interface_name, network.mtu());
+    /// }
+    /// ```
+    fn mtu(&self) -> u64 {
+        0
+    }
+
+    /// Returns whether the interface can pass packets.
+    ///
+    /// Only Linux reports it, other platforms return [`OperationalState::Unknown`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     println!("{}: {}", interface_name, network.operational_state());
+    /// }
+    /// ```
+    fn operational_state(&self) -> OperationalState {
+        OperationalState::Unknown
+    }
+
+    /// Returns whether the interface detects a link, like a plugged cable. It isn't known while
+    /// the interface is administratively down.
+    ///
+    /// Only Linux reports it, other platforms return `None`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     println!("{}: {:?}", interface_name, network.carrier());
+    /// }
+    /// ```
+    fn carrier(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns the duplex mode negotiated by the interface.
+    ///
+    /// Only Linux reports it, other platforms return [`Duplex::Unknown`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     println!("{}: {}", interface_name, network.duplex());
+    /// }
+    /// ```
+    fn duplex(&self) -> Duplex {
+        Duplex::Unknown
+    }
+
+    /// Returns the speed negotiated by the interface in Mb/s. It isn't known for most virtual
+    /// interfaces, or while the link is down.
+    ///
+    /// Only Linux reports it, other platforms return `None`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (interface_name, network) in s.networks() {
+    ///     if let Some(speed) = network.speed() {
+    ///         println!("{}: {} Mb/s", interface_name, speed);
+    ///     }
+    /// }
+    /// ```
+    fn speed(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// Interacting with network interfaces.
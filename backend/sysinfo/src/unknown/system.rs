@@ -2,7 +2,7 @@
 
 use crate::{
     sys::{component::Component, Cpu, Disk, Networks, Process},
-    CpuRefreshKind, LoadAvg, Pid, ProcessRefreshKind, RefreshKind, SystemExt, User,
+    CpuRefreshKind, LoadAvg, Pid, ProcessRefreshKind, RefreshKind, SocketStats, SystemExt, User,
 };
 
 use std::collections::HashMap;
@@ -17,6 +17,7 @@ pub struct System {
     processes_list: HashMap<Pid, Process>,
     networks: Networks,
     global_cpu: Cpu,
+    sockets: SocketStats,
 }
 
 impl SystemExt for System {
@@ -28,6 +29,7 @@ impl SystemExt for System {
             processes_list: Default::default(),
             networks: Networks::new(),
             global_cpu: Cpu::new(),
+            sockets: SocketStats::default(),
         }
     }
 
@@ -150,6 +152,10 @@ impl SystemExt for System {
         &[]
     }
 
+    fn sockets(&self) -> &SocketStats {
+        &self.sockets
+    }
+
     fn name(&self) -> Option<String> {
         None
     }
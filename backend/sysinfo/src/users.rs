@@ -6,15 +6,21 @@ use crate::{
 };
 
 use libc::{getgrgid, getgrouplist};
-use std::{fs::File, io::Read};
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
 
-pub fn get_users_list() -> Vec<User> {
+/// Reads the users from the given `passwd` file. Note that their groups are still looked up
+/// through the running system.
+pub fn get_users_list_from(passwd_path: &Path) -> io::Result<Vec<User>> {
     let mut s = String::new();
     let mut ngroups = 100;
     let mut groups = vec![0; ngroups as usize];
 
-    let _ = File::open("/etc/passwd").and_then(|mut f| f.read_to_string(&mut s));
-    s.lines()
+    File::open(passwd_path).and_then(|mut f| f.read_to_string(&mut s))?;
+    Ok(s.lines()
         .filter_map(|line| {
             let mut parts = line.split(':');
             if let Some(username) = parts.next() {
@@ -87,7 +93,7 @@ pub fn get_users_list() -> Vec<User> {
             }
             None
         })
-        .collect()
+        .collect())
 }
 
 #[inline]
@@ -1,8 +1,8 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
 use crate::{
-    CpuRefreshKind, LoadAvg, Networks, Pid, ProcessExt, ProcessRefreshKind, RefreshKind, SystemExt,
-    User,
+    CpuRefreshKind, LoadAvg, Networks, Pid, ProcessExt, ProcessRefreshKind, RefreshKind,
+    SocketStats, SystemExt, User,
 };
 use winapi::um::winreg::HKEY_LOCAL_MACHINE;
 
@@ -72,6 +72,7 @@ pub struct System {
     networks: Networks,
     boot_time: u64,
     users: Vec<User>,
+    sockets: SocketStats,
 }
 
 // Useful for parallel iterations.
@@ -110,6 +111,7 @@ impl SystemExt for System {
             networks: Networks::new(),
             boot_time: unsafe { boot_time() },
             users: Vec::new(),
+            sockets: SocketStats::default(),
         };
         s.refresh_specifics(refreshes);
         s
@@ -420,6 +422,10 @@ impl SystemExt for System {
         &self.users
     }
 
+    fn sockets(&self) -> &SocketStats {
+        &self.sockets
+    }
+
     fn networks(&self) -> &Networks {
         &self.networks
     }
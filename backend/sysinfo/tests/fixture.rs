@@ -0,0 +1,994 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Runs the Linux implementation against the files in `tests/fixtures`, see the README there.
+
+#![cfg(all(target_os = "linux", not(target_os = "android")))]
+
+use std::{
+    fs, io,
+    os::unix::fs::symlink,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use sysinfo::{
+    CpuExt, CpuRefreshKind, DeviceId, DiskExt, DiskIo, DiskType, DiskUsage, Duplex, MemoryActivity,
+    MemoryDetails, NetworkExt, NetworkSource, NetworksExt, OpenFileKind, OperationalState, Pid,
+    Pressure, ProcessExt, ProcessRefreshKind, ProcessStatus, RefreshArea, RefreshKind,
+    SocketProtocol, System, SystemBuilder, SystemExt, SystemSnapshot, TcpActivity, TcpState,
+    UdpActivity, UserExt,
+};
+
+/// The directories a fixture step can contain.
+const ROOTS: &[&str] = &["proc", "sys", "etc", "dev"];
+
+/// A copy of a fixture which can be advanced step by step.
+struct Fixture {
+    source: PathBuf,
+    root: tempfile::TempDir,
+}
+
+impl Fixture {
+    fn new(name: &str) -> Fixture {
+        let fixture = Fixture {
+            source: Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(name),
+            root: tempfile::tempdir().expect("failed to create temporary directory"),
+        };
+        fixture.advance(0);
+        fixture
+    }
+
+    /// Replaces the directories contained in the given step.
+    fn advance(&self, step: usize) {
+        let step = self.source.join(step.to_string());
+        assert!(step.is_dir(), "missing fixture step {}", step.display());
+        for root in ROOTS {
+            let src = step.join(root);
+            if !src.exists() {
+                continue;
+            }
+            let dst = self.root.path().join(root);
+            if dst.exists() {
+                fs::remove_dir_all(&dst).expect("failed to remove previous step");
+            }
+            copy_dir(&src, &dst).expect("failed to copy fixture step");
+        }
+    }
+
+    fn system(&self, refresh_kind: RefreshKind) -> System {
+        self.builder(refresh_kind).build()
+    }
+
+    fn builder(&self, refresh_kind: RefreshKind) -> SystemBuilder {
+        let root = self.root.path();
+        SystemBuilder::new()
+            .with_refresh_kind(refresh_kind)
+            .with_proc_path(root.join("proc"))
+            .with_sys_path(root.join("sys"))
+            .with_etc_path(root.join("etc"))
+            .with_dev_path(root.join("dev"))
+            // Open files would keep reading the previous step
+            .with_open_files_limit(0)
+    }
+}
+
+fn copy_dir(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let dst = dst.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            symlink(fs::read_link(entry.path())?, dst)?;
+        } else if file_type.is_dir() {
+            copy_dir(&entry.path(), &dst)?;
+        } else {
+            fs::copy(entry.path(), dst)?;
+        }
+    }
+    Ok(())
+}
+
+fn page_size_kb(pages: u64) -> u64 {
+    pages * unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64 / 1_000
+}
+
+fn clock_ticks_secs(ticks: u64) -> u64 {
+    ticks / unsafe { libc::sysconf(libc::_SC_CLK_TCK) } as u64
+}
+
+fn assert_usage(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 0.01,
+        "expected a usage of {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn fixture_memory() {
+    let fixture = Fixture::new("basic");
+    let s = fixture.system(RefreshKind::new().with_memory());
+
+    // `/proc/meminfo` is in KiB
+    assert_eq!(s.total_memory(), 2_048_000);
+    assert_eq!(s.free_memory(), 512_000);
+    assert_eq!(s.available_memory(), 1_024_000);
+    // Total minus free, buffers, cached and reclaimable slab
+    assert_eq!(s.used_memory(), 1_177_600);
+    assert_eq!(s.total_swap(), 1_024_000);
+    assert_eq!(s.free_swap(), 768_000);
+    assert_eq!(s.used_swap(), 256_000);
+}
+
+#[test]
+fn fixture_cpu() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_cpu(CpuRefreshKind::everything()));
+
+    assert_eq!(s.cpus().len(), 2);
+    assert_eq!(s.physical_core_count(), Some(2));
+    let names: Vec<_> = s.cpus().iter().map(|cpu| cpu.name()).collect();
+    assert_eq!(names, ["cpu0", "cpu1"]);
+    for cpu in s.cpus().iter().chain(Some(s.global_cpu_info())) {
+        assert_eq!(cpu.vendor_id(), "GenuineFixture");
+        assert_eq!(cpu.brand(), "Fixture CPU @ 2.00GHz");
+    }
+    let frequencies: Vec<_> = s.cpus().iter().map(|cpu| cpu.frequency()).collect();
+    assert_eq!(frequencies, [2000, 1500]);
+    assert_eq!(s.global_cpu_info().frequency(), 2000);
+
+    fixture.advance(1);
+    s.refresh_cpu_specifics(CpuRefreshKind::everything());
+
+    assert_usage(s.global_cpu_info().cpu_usage(), 40.);
+    assert_usage(s.cpus()[0].cpu_usage(), 60.);
+    assert_usage(s.cpus()[1].cpu_usage(), 20.);
+    let usage = s.cpus()[1].usage_breakdown();
+    assert_usage(usage.user, 10.);
+    assert_usage(usage.system, 0.);
+    assert_usage(usage.idle, 70.);
+    assert_usage(usage.iowait, 10.);
+    assert_usage(usage.steal, 10.);
+    assert_usage(s.global_cpu_info().usage_breakdown().iowait, 5.);
+    let frequencies: Vec<_> = s.cpus().iter().map(|cpu| cpu.frequency()).collect();
+    assert_eq!(frequencies, [2400, 1200]);
+}
+
+#[test]
+fn fixture_kernel_activity() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_cpu(CpuRefreshKind::everything()));
+
+    // There is no rate before the second refresh
+    let activity = s.kernel_activity();
+    assert_eq!(activity.context_switches, 0.);
+    assert_eq!(activity.running_processes, 1);
+    assert_eq!(activity.blocked_processes, 0);
+
+    fixture.advance(1);
+    // Refreshing the global CPU usage in-between doesn't change the interval of the rates
+    s.refresh_processes_specifics(ProcessRefreshKind::new());
+    s.refresh_cpu_specifics(CpuRefreshKind::new().with_kernel_activity());
+
+    // The CPUs spent 1000 clock ticks in total, so 500 elapsed
+    let elapsed = clock_ticks_secs(500) as f64;
+    let activity = s.kernel_activity();
+    assert_eq!(activity.context_switches, 10_000. / elapsed);
+    assert_eq!(activity.interrupts, 2_500. / elapsed);
+    assert_eq!(activity.forks, 10. / elapsed);
+    assert_eq!(activity.running_processes, 2);
+    assert_eq!(activity.blocked_processes, 3);
+}
+
+#[test]
+fn fixture_processes() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::everything());
+
+    let mut pids: Vec<_> = s.processes().keys().copied().collect();
+    pids.sort();
+    assert_eq!(pids, [Pid::from(1), Pid::from(42), Pid::from(50)]);
+
+    let init = s.process(Pid::from(1)).expect("missing init");
+    assert_eq!(init.name(), "init");
+    assert_eq!(init.cmd(), ["/sbin/init", "splash"]);
+    assert_eq!(init.exe(), Path::new("/sbin/init"));
+    assert_eq!(init.parent(), None);
+    assert_eq!(init.memory(), page_size_kb(2000));
+    assert_eq!(init.virtual_memory(), 169_000);
+    // No previous values to compute the usage from yet
+    assert_eq!(init.cpu_usage(), 0.);
+
+    let worker = s.process(Pid::from(42)).expect("missing worker");
+    assert_eq!(worker.name(), "fixture worker");
+    assert_eq!(worker.cmd(), ["/usr/bin/worker", "--threads", "2"]);
+    assert_eq!(worker.parent(), Some(Pid::from(1)));
+    assert_eq!(
+        worker.start_time(),
+        1_600_000_000 + clock_ticks_secs(50_000)
+    );
+    assert_eq!(worker.run_time(), 1000 - clock_ticks_secs(50_000));
+    assert!(worker.environ().is_empty());
+
+    fixture.advance(1);
+    s.refresh_all();
+
+    let mut pids: Vec<_> = s.processes().keys().copied().collect();
+    pids.sort();
+    assert_eq!(pids, [Pid::from(1), Pid::from(42), Pid::from(77)]);
+
+    // The CPU time of the processes is divided by the CPU time of a single CPU
+    assert_usage(s.process(Pid::from(1)).unwrap().cpu_usage(), 20.);
+    let worker = s.process(Pid::from(42)).unwrap();
+    assert_usage(worker.cpu_usage(), 80.);
+    assert_eq!(worker.memory(), page_size_kb(12_000));
+    assert_eq!(worker.run_time(), 1010 - clock_ticks_secs(50_000));
+    assert_eq!(
+        worker.disk_usage(),
+        DiskUsage {
+            total_written_bytes: 8192,
+            written_bytes: 0,
+            total_read_bytes: 12_288,
+            read_bytes: 8192,
+        }
+    );
+
+    let new = s.process(Pid::from(77)).expect("missing new process");
+    assert_eq!(new.parent(), Some(Pid::from(42)));
+    assert_eq!(new.cpu_usage(), 0.);
+}
+
+#[test]
+fn fixture_process_refresh_kind() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_processes(ProcessRefreshKind::new()));
+
+    // The name, parent and times are always read
+    let worker = s.process(Pid::from(42)).expect("missing worker");
+    assert_eq!(worker.name(), "fixture worker");
+    assert_eq!(worker.parent(), Some(Pid::from(1)));
+    assert!(worker.cmd().is_empty());
+    assert_eq!(worker.exe(), Path::new(""));
+    assert_eq!(worker.memory(), 0);
+    assert_eq!(worker.status(), ProcessStatus::Unknown(0));
+
+    let refresh_kind = ProcessRefreshKind::new()
+        .with_cmd()
+        .with_memory()
+        .with_status();
+    s.refresh_processes_specifics(refresh_kind);
+
+    let worker = s.process(Pid::from(42)).unwrap();
+    assert_eq!(worker.cmd(), ["/usr/bin/worker", "--threads", "2"]);
+    assert_eq!(worker.exe(), Path::new(""));
+    assert_eq!(worker.memory(), page_size_kb(10_000));
+    assert_eq!(worker.status(), ProcessStatus::Sleep);
+
+    // The command line is only read once for each process
+    fs::write(
+        fixture.root.path().join("proc/42/cmdline"),
+        "/usr/bin/changed\0",
+    )
+    .unwrap();
+    s.refresh_processes_specifics(refresh_kind.with_exe());
+
+    let worker = s.process(Pid::from(42)).unwrap();
+    assert_eq!(worker.cmd(), ["/usr/bin/worker", "--threads", "2"]);
+    assert_eq!(worker.exe(), Path::new("/usr/bin/worker"));
+}
+
+#[test]
+fn fixture_disks() {
+    let fixture = Fixture::new("basic");
+    let s = fixture.system(RefreshKind::new().with_disks_list());
+
+    // Pseudo file systems, mounts under `/sys`, `/proc` and `/run` and snaps are filtered out.
+    // `/etc` has the same device as `/` in the mount table, so it is a duplicate. The space of
+    // `/` and `/tmp` is queried on this system, which has them too, so it isn't checked here.
+    let disks: Vec<_> = s
+        .disks()
+        .iter()
+        .map(|disk| {
+            (
+                disk.name().to_str().unwrap(),
+                disk.mount_point(),
+                disk.file_system(),
+                disk.type_(),
+                disk.is_removable(),
+                disk.device_id(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        disks,
+        [
+            (
+                "/dev/sda1",
+                Path::new("/"),
+                &b"ext4"[..],
+                DiskType::SSD,
+                false,
+                Some(DeviceId { major: 8, minor: 1 })
+            ),
+            (
+                "/dev/sdb1",
+                Path::new("/tmp"),
+                &b"vfat"[..],
+                DiskType::HDD,
+                true,
+                Some(DeviceId {
+                    major: 8,
+                    minor: 17
+                })
+            ),
+        ]
+    );
+
+    let disks = s.disks();
+    assert_eq!(disks[0].mount_options(), ["rw", "relatime"]);
+    assert!(!disks[0].is_read_only());
+    assert_eq!(disks[1].mount_options(), ["ro", "nosuid", "relatime"]);
+    assert!(disks[1].is_read_only());
+}
+
+/// Creates the mount points of `proc/1/mountinfo`, which are queried under a host root.
+fn host_root() -> tempfile::TempDir {
+    let host_root = tempfile::tempdir().expect("failed to create temporary directory");
+    for dir in &["data", "var/lib/srv", "mnt/with space", "mnt/nas"] {
+        fs::create_dir_all(host_root.path().join(dir)).expect("failed to create subdirectory");
+    }
+    host_root
+}
+
+#[test]
+fn fixture_disks_host_root() {
+    let fixture = Fixture::new("basic");
+    let host_root = host_root();
+    let s = fixture
+        .builder(RefreshKind::new().with_disks_list())
+        .with_host_root_path(host_root.path())
+        .build();
+
+    // The mounts come from the mount table of process 1, and the bind mount of `/srv` on
+    // `/var/lib/srv` has the same device id as `/`.
+    let disks: Vec<_> = s
+        .disks()
+        .iter()
+        .map(|disk| {
+            (
+                disk.name().to_str().unwrap(),
+                disk.mount_point(),
+                disk.file_system(),
+                disk.is_read_only(),
+                disk.device_id(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        disks,
+        [
+            (
+                "/dev/sda1",
+                Path::new("/"),
+                &b"ext4"[..],
+                false,
+                Some(DeviceId { major: 8, minor: 1 })
+            ),
+            (
+                "/dev/sdb1",
+                Path::new("/data"),
+                &b"vfat"[..],
+                true,
+                Some(DeviceId {
+                    major: 8,
+                    minor: 17
+                })
+            ),
+            (
+                "/dev/sdc1",
+                Path::new("/mnt/with space"),
+                &b"ext4"[..],
+                false,
+                Some(DeviceId {
+                    major: 8,
+                    minor: 33
+                })
+            ),
+            (
+                "nas:/export",
+                Path::new("/mnt/nas"),
+                &b"nfs4"[..],
+                false,
+                Some(DeviceId {
+                    major: 0,
+                    minor: 50
+                })
+            ),
+        ]
+    );
+    assert_eq!(s.disks()[2].mount_options(), ["rw", "noatime"]);
+}
+
+#[test]
+fn fixture_disks_timeout() {
+    let fixture = Fixture::new("basic");
+    let host_root = host_root();
+    let mut s = fixture
+        .builder(RefreshKind::new().with_disks_list())
+        .with_host_root_path(host_root.path())
+        .with_disk_timeout(Duration::from_secs(10))
+        .without_network_disks()
+        .build();
+
+    // The NFS mount is left out, and the others answer in time
+    let names = |s: &System| -> Vec<String> {
+        s.disks()
+            .iter()
+            .map(|disk| disk.name().to_string_lossy().into_owned())
+            .collect()
+    };
+    assert_eq!(names(&s), ["/dev/sda1", "/dev/sdb1", "/dev/sdc1"]);
+    assert!(s.disks().iter().all(|disk| !disk.is_unreachable()));
+
+    s.refresh_disks();
+    assert!(s.disks().iter().all(|disk| !disk.is_unreachable()));
+    s.refresh_disks_list();
+    assert_eq!(names(&s), ["/dev/sda1", "/dev/sdb1", "/dev/sdc1"]);
+}
+
+#[test]
+fn fixture_networks() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_networks_list());
+
+    let mut names: Vec<_> = s.networks().iter().map(|(name, _)| name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["eth0", "lo"]);
+
+    fixture.advance(1);
+    s.refresh_networks();
+
+    let (_, eth0) = s
+        .networks()
+        .iter()
+        .find(|(name, _)| *name == "eth0")
+        .expect("missing eth0");
+    assert_eq!(eth0.received(), 500_000);
+    assert_eq!(eth0.total_received(), 1_500_000);
+    assert_eq!(eth0.transmitted(), 60_000);
+    assert_eq!(eth0.total_transmitted(), 260_000);
+    assert_eq!(eth0.packets_received(), 400);
+    assert_eq!(eth0.packets_transmitted(), 100);
+    assert_eq!(eth0.errors_on_received(), 0);
+    assert_eq!(eth0.errors_on_transmitted(), 2);
+    // 10 seconds of uptime elapsed
+    assert_eq!(eth0.received_per_second(), 50_000.);
+    assert_eq!(eth0.transmitted_per_second(), 6_000.);
+
+    assert_eq!(eth0.mac_address().to_string(), "52:54:00:12:34:56");
+    assert_eq!(eth0.mtu(), 1500);
+    assert_eq!(eth0.operational_state(), OperationalState::Up);
+    assert_eq!(eth0.carrier(), Some(true));
+    assert_eq!(eth0.duplex(), Duplex::Full);
+    assert_eq!(eth0.speed(), Some(100));
+
+    // The loopback has no hardware address, and no duplex mode or speed to read
+    let (_, lo) = s
+        .networks()
+        .iter()
+        .find(|(name, _)| *name == "lo")
+        .expect("missing lo");
+    assert!(lo.mac_address().is_unspecified());
+    assert_eq!(lo.mtu(), 65536);
+    assert_eq!(lo.operational_state(), OperationalState::Unknown);
+    assert_eq!(lo.duplex(), Duplex::Unknown);
+    assert_eq!(lo.speed(), None);
+}
+
+#[test]
+fn fixture_networks_net_dev() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture
+        .builder(RefreshKind::new().with_networks_list())
+        .with_network_source(NetworkSource::ProcNetDev)
+        .build();
+
+    let mut names: Vec<_> = s.networks().iter().map(|(name, _)| name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["eth0", "lo"]);
+
+    fixture.advance(1);
+    s.refresh_networks();
+
+    // The counters are the same as in sysfs, and so is the link
+    let (_, eth0) = s
+        .networks()
+        .iter()
+        .find(|(name, _)| *name == "eth0")
+        .expect("missing eth0");
+    assert_eq!(eth0.received(), 500_000);
+    assert_eq!(eth0.transmitted(), 60_000);
+    assert_eq!(eth0.packets_received(), 400);
+    assert_eq!(eth0.errors_on_transmitted(), 2);
+    assert_eq!(eth0.received_per_second(), 50_000.);
+    assert_eq!(eth0.speed(), Some(100));
+}
+
+#[test]
+fn fixture_networks_host_net_dev() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture
+        .builder(RefreshKind::new().with_networks_list())
+        .with_network_source(NetworkSource::HostProcNetDev)
+        .build();
+
+    let mut names: Vec<_> = s.networks().iter().map(|(name, _)| name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["bond0", "eno1", "eno2", "lo"]);
+
+    // A refresh keeps the interfaces which are gone, without traffic
+    fixture.advance(1);
+    s.refresh_networks();
+    let mut names: Vec<_> = s.networks().iter().map(|(name, _)| name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["bond0", "eno1", "eno2", "lo"]);
+
+    let (_, bond0) = s
+        .networks()
+        .iter()
+        .find(|(name, _)| *name == "bond0")
+        .expect("missing bond0");
+    assert_eq!(bond0.received(), 20_000_000);
+    assert_eq!(bond0.total_received(), 30_000_000);
+    assert_eq!(bond0.transmitted(), 1_000_000);
+    assert_eq!(bond0.packets_received(), 16_000);
+    assert_eq!(bond0.received_per_second(), 2_000_000.);
+    assert_eq!(bond0.transmitted_per_second(), 100_000.);
+    // The addresses of the process' namespace aren't those of the host, and the fixture's sysfs
+    // has no bond0
+    assert!(bond0.ip_networks().is_empty());
+    assert_eq!(bond0.operational_state(), OperationalState::Unknown);
+    assert_eq!(bond0.mtu(), 0);
+
+    let (_, eno2) = s
+        .networks()
+        .iter()
+        .find(|(name, _)| *name == "eno2")
+        .expect("missing eno2");
+    assert_eq!(eno2.received(), 0);
+    assert_eq!(eno2.total_received(), 4_000_000);
+
+    // Listing them again removes eno2 and adds docker0
+    s.refresh_networks_list();
+    let mut names: Vec<_> = s.networks().iter().map(|(name, _)| name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["bond0", "docker0", "eno1", "lo"]);
+    assert_eq!(s.refresh_errors(), []);
+}
+
+#[test]
+#[cfg(target_endian = "little")]
+fn fixture_sockets() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_sockets().with_socket_processes());
+
+    let sockets = s.sockets();
+    let states: Vec<_> = sockets
+        .tcp_states
+        .iter()
+        .map(|(&state, &count)| (state, count))
+        .collect();
+    assert_eq!(
+        states,
+        [
+            (TcpState::Established, 3),
+            (TcpState::TimeWait, 3),
+            (TcpState::Listen, 3)
+        ]
+    );
+    assert_eq!(sockets.udp_sockets, 2);
+    assert_eq!(sockets.unix_sockets, 4);
+    // The rates need two refreshes
+    assert_eq!(sockets.tcp, TcpActivity::default());
+
+    let listening: Vec<_> = sockets
+        .listening
+        .iter()
+        .map(|socket| {
+            (
+                socket.protocol,
+                socket.address.map(|address| address.to_string()),
+                socket.path.as_deref(),
+                socket.pids.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        listening,
+        [
+            (
+                SocketProtocol::Tcp,
+                Some("0.0.0.0:8080".to_owned()),
+                None,
+                vec![Pid::from(42)]
+            ),
+            (
+                SocketProtocol::Tcp,
+                Some("127.0.0.1:5432".to_owned()),
+                None,
+                vec![]
+            ),
+            (
+                SocketProtocol::Tcp,
+                Some("[::]:22".to_owned()),
+                None,
+                vec![]
+            ),
+            // The connected UDP socket isn't listening
+            (
+                SocketProtocol::Udp,
+                Some("0.0.0.0:68".to_owned()),
+                None,
+                vec![Pid::from(1)]
+            ),
+            (
+                SocketProtocol::Unix,
+                None,
+                Some("/run/systemd/private"),
+                vec![Pid::from(1)]
+            ),
+            (
+                SocketProtocol::Unix,
+                None,
+                Some("@/tmp/.X11-unix/X0"),
+                vec![]
+            ),
+        ]
+    );
+
+    fixture.advance(1);
+    s.refresh_sockets();
+
+    let sockets = s.sockets();
+    // The processes are only looked for on request
+    assert!(sockets
+        .listening
+        .iter()
+        .all(|socket| socket.pids.is_empty()));
+    assert_eq!(sockets.tcp_states.get(&TcpState::Established), Some(&2));
+    assert_eq!(sockets.tcp_states.get(&TcpState::TimeWait), Some(&5));
+    // 10 seconds of uptime elapsed
+    assert_eq!(
+        sockets.tcp,
+        TcpActivity {
+            active_opens: 10.,
+            passive_opens: 10.,
+            failed_connection_attempts: 1.,
+            established_resets: 0.,
+            segments_sent: 1_000.,
+            segments_retransmitted: 5.,
+            receive_errors: 0.,
+            resets_sent: 1.,
+        }
+    );
+    // The IPv6 datagrams are counted too
+    assert_eq!(
+        sockets.udp,
+        UdpActivity {
+            datagrams_received: 100.,
+            datagrams_sent: 50.,
+            no_port: 1.,
+            receive_errors: 0.2,
+            receive_buffer_errors: 0.2,
+            send_buffer_errors: 0.,
+        }
+    );
+    assert_eq!(s.refresh_errors(), []);
+}
+
+#[test]
+fn fixture_open_files() {
+    let fixture = Fixture::new("basic");
+    let refresh_kind = ProcessRefreshKind::new().with_open_files().with_limits();
+    let mut s = fixture.system(RefreshKind::new().with_processes(refresh_kind));
+
+    let worker = s.process(Pid::from(42)).expect("missing worker");
+    let files: Vec<_> = worker
+        .open_files()
+        .expect("missing open files")
+        .iter()
+        .map(|file| (file.fd, file.kind, file.target.to_str().unwrap()))
+        .collect();
+    assert_eq!(
+        files,
+        [
+            (0, OpenFileKind::File, "/dev/null"),
+            (1, OpenFileKind::File, "/var/log/worker.log"),
+            (2, OpenFileKind::File, "/var/log/worker.log"),
+            (3, OpenFileKind::Socket, "socket:[1001]"),
+            (4, OpenFileKind::Socket, "socket:[1001]"),
+            (5, OpenFileKind::Pipe, "pipe:[9]"),
+            (6, OpenFileKind::Socket, "socket:[1100]"),
+            (7, OpenFileKind::AnonInode, "anon_inode:[eventfd]"),
+            (8, OpenFileKind::AnonInode, "anon_inode:[eventpoll]"),
+            (10, OpenFileKind::Other, "mnt:[4026531841]"),
+        ]
+    );
+    let limits = worker.limits().expect("missing limits");
+    assert_eq!(limits.open_files.soft, Some(1024));
+    assert_eq!(limits.open_files.hard, Some(524_288));
+    assert_eq!(limits.stack_size.soft, Some(8_388_608));
+    assert_eq!(limits.stack_size.hard, None);
+    assert_eq!(limits.cpu_time.soft, None);
+    assert_eq!(limits.realtime_priority.hard, Some(0));
+    // Process 50 has no file descriptors nor limits in the fixture
+    let exiting = s.process(Pid::from(50)).expect("missing process 50");
+    assert_eq!(exiting.open_files(), None);
+    assert_eq!(exiting.limits(), None);
+
+    fixture.advance(1);
+    s.refresh_processes_specifics(refresh_kind);
+
+    let worker = s.process(Pid::from(42)).unwrap();
+    let files = worker.open_files().unwrap();
+    assert_eq!(files.len(), 11);
+    assert_eq!(files[9].target, Path::new("/var/lib/worker/data.db"));
+    assert_eq!(worker.limits().unwrap().open_files.soft, Some(4096));
+    assert_eq!(s.refresh_errors(), []);
+}
+
+#[test]
+fn fixture_system() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_users_list());
+
+    assert_eq!(s.boot_time(), 1_600_000_000);
+    assert_eq!(s.uptime(), 1000);
+    assert_eq!(s.load_average().one, 0.5);
+    assert_eq!(s.name().as_deref(), Some("Fixture Linux"));
+    assert_eq!(s.os_version().as_deref(), Some("1.0"));
+    // Users without a login shell are ignored
+    let users: Vec<_> = s.users().iter().map(|user| user.name()).collect();
+    assert_eq!(users, ["root", "alice"]);
+
+    fixture.advance(1);
+    s.refresh_system();
+
+    assert_eq!(s.uptime(), 1010);
+    assert_eq!(s.load_average().one, 1.5);
+}
+
+#[test]
+fn fixture_pressure() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_pressure());
+
+    let pressure = s.pressure();
+    let cpu = pressure.cpu.expect("missing CPU pressure");
+    assert_eq!(cpu.some.avg10, 1.5);
+    assert_eq!(cpu.some.total, 1_200_000);
+    // Older kernels don't report when all tasks are stalled on the CPU
+    assert_eq!(cpu.full, None);
+    assert_eq!(pressure.memory.map(|memory| memory.some.avg300), Some(0.));
+
+    fixture.advance(1);
+    s.refresh_pressure();
+
+    let io = s.pressure().io.expect("missing I/O pressure");
+    assert_eq!(io.some.avg10, 20.);
+    assert_eq!(io.full.map(|full| full.avg60), Some(3.));
+    assert_eq!(io.full.map(|full| full.total), Some(3_000_000));
+
+    // Kernels without PSI have no `/proc/pressure`, which isn't an error
+    fs::remove_dir_all(fixture.root.path().join("proc/pressure")).unwrap();
+    s.refresh_pressure();
+    assert_eq!(s.pressure(), Pressure::default());
+    assert_eq!(s.refresh_errors(), []);
+}
+
+#[test]
+fn fixture_disks_io() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_disks_list().with_disks_io());
+
+    // Unused devices are skipped, and the mount points are those of the partitions
+    let devices: Vec<_> = s
+        .disks_io()
+        .iter()
+        .map(|io| (io.name.as_str(), io.mount_points.clone()))
+        .collect();
+    assert_eq!(
+        devices,
+        [
+            ("sda", vec![]),
+            ("sda1", vec![PathBuf::from("/")]),
+            ("sdb", vec![]),
+            ("sdb1", vec![PathBuf::from("/tmp")]),
+        ]
+    );
+    // There is no previous refresh to compute the rates from
+    assert_eq!(s.disks_io()[1].utilization, 0.);
+
+    fixture.advance(1);
+    s.refresh_disks_io();
+
+    // 10 seconds of uptime elapsed
+    assert_eq!(
+        s.disks_io()[1],
+        DiskIo {
+            name: "sda1".to_owned(),
+            mount_points: vec![PathBuf::from("/")],
+            reads: 200.,
+            writes: 100.,
+            read_bytes: 4_096_000.,
+            written_bytes: 1_024_000.,
+            await_time: 3.,
+            utilization: 50.,
+        }
+    );
+    // An idle device has no rates, and no time per operation since there were none
+    assert_eq!(s.disks_io()[3].reads, 0.);
+    assert_eq!(s.disks_io()[3].await_time, 0.);
+}
+
+#[test]
+fn fixture_memory_activity() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_memory().with_memory_activity());
+
+    assert_eq!(
+        s.memory_details(),
+        MemoryDetails {
+            dirty: 4096,
+            writeback: 0,
+            shared: 30720,
+            committed: 1_536_000,
+            // The number of huge pages has no unit, unlike their size
+            huge_pages_total: 16,
+            huge_pages_free: 8,
+            huge_page_size_bytes: 2_097_152,
+        }
+    );
+    // There is no previous refresh to compute the rates from
+    assert_eq!(s.memory_activity(), MemoryActivity::default());
+
+    fixture.advance(1);
+    s.refresh_memory();
+    s.refresh_memory_activity();
+
+    assert_eq!(s.memory_details().dirty, 8192);
+    assert_eq!(s.memory_details().writeback, 1024);
+    // 10 seconds of uptime elapsed, the zone and anon/file reclaim counters aren't counted twice
+    assert_eq!(
+        s.memory_activity(),
+        MemoryActivity {
+            page_faults: 5000.,
+            major_page_faults: 10.,
+            pages_swapped_in: 5.,
+            pages_swapped_out: 20.,
+            pages_scanned: 200.,
+            pages_stolen: 100.,
+            oom_kills: 1,
+        }
+    );
+}
+
+#[test]
+fn fixture_refresh_errors() {
+    let fixture = Fixture::new("basic");
+    let mut s = fixture.system(RefreshKind::new().with_memory().with_users_list());
+    assert_eq!(s.refresh_errors(), []);
+
+    let meminfo = fixture.root.path().join("proc/meminfo");
+    fs::remove_file(&meminfo).unwrap();
+    fs::remove_file(fixture.root.path().join("etc/passwd")).unwrap();
+    s.refresh_memory();
+    s.refresh_users_list();
+
+    let errors = s.refresh_errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].area, RefreshArea::Memory);
+    assert_eq!(errors[0].path, meminfo);
+    assert_eq!(errors[0].pid, None);
+    assert_eq!(errors[0].kind, io::ErrorKind::NotFound);
+    assert_eq!(errors[0].raw_os_error, Some(libc::ENOENT));
+    assert_eq!(
+        errors[0].to_string(),
+        format!(
+            "cannot read {}: {}",
+            meminfo.display(),
+            io::Error::from_raw_os_error(libc::ENOENT)
+        )
+    );
+    assert_eq!(errors[1].area, RefreshArea::Users);
+
+    // The errors of an area are cleared when it is refreshed again
+    fixture.advance(1);
+    s.refresh_memory();
+    assert_eq!(s.refresh_errors().len(), 1);
+
+    // The memory activity has its own area, so refreshing it keeps the errors of the memory
+    let vmstat = fixture.root.path().join("proc/vmstat");
+    fs::remove_file(&meminfo).unwrap();
+    fs::remove_file(&vmstat).unwrap();
+    s.refresh_memory();
+    s.refresh_memory_activity();
+    let area_errors = |s: &System| -> Vec<(RefreshArea, PathBuf)> {
+        s.refresh_errors()
+            .into_iter()
+            .filter(|error| error.area != RefreshArea::Users)
+            .map(|error| (error.area, error.path))
+            .collect()
+    };
+    assert_eq!(
+        area_errors(&s),
+        [
+            (RefreshArea::Memory, meminfo.clone()),
+            (RefreshArea::MemoryActivity, vmstat)
+        ]
+    );
+
+    fixture.advance(1);
+    s.refresh_memory_activity();
+    assert_eq!(area_errors(&s), [(RefreshArea::Memory, meminfo)]);
+
+    // The activity of the disks has its own area
+    let diskstats = fixture.root.path().join("proc/diskstats");
+    fs::remove_file(&diskstats).unwrap();
+    s.refresh_disks_io();
+    let disks_io_errors = |s: &System| -> Vec<PathBuf> {
+        s.refresh_errors()
+            .into_iter()
+            .filter(|error| error.area == RefreshArea::DisksIo)
+            .map(|error| error.path)
+            .collect()
+    };
+    assert_eq!(disks_io_errors(&s), [diskstats]);
+
+    fixture.advance(1);
+    s.refresh_disks_io();
+    assert!(disks_io_errors(&s).is_empty());
+}
+
+#[test]
+fn fixture_snapshot() {
+    let fixture = Fixture::new("basic");
+    let s = fixture.system(RefreshKind::everything());
+
+    let snapshot = SystemSnapshot::from(&s);
+    assert_eq!(snapshot.cpus.len(), 2);
+    assert_eq!(snapshot.total_memory, 2_048_000);
+    assert_eq!(snapshot.name.as_deref(), Some("Fixture Linux"));
+    let pids: Vec<_> = snapshot.processes.keys().copied().collect();
+    assert_eq!(pids, [Pid::from(1), Pid::from(42), Pid::from(50)]);
+    let worker = &snapshot.processes[&Pid::from(42)];
+    assert_eq!(worker.name, "fixture worker");
+    assert_eq!(worker.cmd, ["/usr/bin/worker", "--threads", "2"]);
+    assert_eq!(worker.parent, Some(Pid::from(1)));
+    let names: Vec<_> = snapshot.networks.keys().map(String::as_str).collect();
+    assert_eq!(names, ["eth0", "lo"]);
+    assert_eq!(snapshot.disks.len(), 2);
+    assert_eq!(snapshot.disks[0].file_system, "ext4");
+    let users: Vec<_> = snapshot.users.iter().map(|user| user.name()).collect();
+    assert_eq!(users, ["root", "alice"]);
+
+    #[cfg(feature = "serde")]
+    {
+        use sysinfo::ProcessSnapshot;
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let deserialized: SystemSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, snapshot);
+
+        // The platform types serialize the same way as their snapshots
+        assert_eq!(serde_json::to_string(&s).unwrap(), json);
+        let worker = s.process(Pid::from(42)).unwrap();
+        assert_eq!(
+            serde_json::to_value(worker).unwrap(),
+            serde_json::to_value(ProcessSnapshot::from(worker)).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+/// A simple server monitoring tool. Everything other than the command is configured with
+/// `WHTOP_` environment variables.
+#[derive(Clone, Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// The command to run. Defaults to serving the live system.
+    pub fn command(self) -> Command {
+        self.command.unwrap_or(Command::Serve(ServeArgs::default()))
+    }
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum Command {
+    /// Serves the API and frontend.
+    Serve(ServeArgs),
+    /// Records snapshots of the system to a file until interrupted.
+    Record(RecordArgs),
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ServeArgs {
+    /// Serves the snapshots from a recording instead of the live system. The recording is
+    /// replayed in a loop.
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+    /// How much faster than real time to replay the recording.
+    #[arg(long, default_value_t = 1.0, requires = "replay", value_parser = parse_speed)]
+    pub speed: f64,
+}
+
+impl Default for ServeArgs {
+    fn default() -> Self {
+        ServeArgs {
+            replay: None,
+            speed: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct RecordArgs {
+    /// The file to write the recording to. It is overwritten if it already exists.
+    #[arg(long, value_name = "FILE")]
+    pub out: PathBuf,
+}
+
+fn parse_speed(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed > 0.0 => Ok(speed),
+        _ => Err(format!("`{value}` is not a positive number")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_to_serve() {
+        // Execute
+        let command = Cli::try_parse_from(["whtop"]).unwrap().command();
+
+        // Assert
+        assert!(matches!(
+            command,
+            Command::Serve(ServeArgs { replay: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_replay_speed() {
+        // Execute
+        let valid = Cli::try_parse_from(["whtop", "serve", "--replay", "a.jsonl", "--speed", "4"]);
+        let negative = Cli::try_parse_from(["whtop", "serve", "--replay", "a.jsonl", "--speed=-1"]);
+        let without_replay = Cli::try_parse_from(["whtop", "serve", "--speed", "4"]);
+
+        // Assert
+        assert!(matches!(
+            valid.unwrap().command(),
+            Command::Serve(ServeArgs { speed, .. }) if speed == 4.0
+        ));
+        assert!(negative.is_err());
+        assert!(without_replay.is_err());
+    }
+}
@@ -1,20 +1,90 @@
+use anyhow::bail;
 use serde::Deserialize;
 use std::{
+    fmt::{Display, Formatter},
     net::{Ipv6Addr, SocketAddr},
     path::PathBuf,
+    time::Duration,
 };
 
+/// The lowest rate limit, one request per day.
+const MIN_RATE_LIMIT_PER_SEC: f64 = 1.0 / (24.0 * 60.0 * 60.0);
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(default)]
 pub struct AppConfig {
     /// The address to listen on.
     pub address: SocketAddr,
-    /// The maximum refresh rate for system information.
+    /// The maximum refresh rate for system information. Must be above 0.
     pub refresh_rate_secs: f32,
     /// Whether to serve static assets.
     pub serve_static: bool,
     /// The path to the static files to serve.
     pub static_dir: PathBuf,
+    /// The number of API requests each client can make per second on average. Clients are
+    /// identified by their IP address. Set to 0 to disable rate limiting, otherwise it must be at
+    /// least one request per day.
+    pub rate_limit_per_sec: f64,
+    /// The number of API requests each client can make at once before being rate limited.
+    pub rate_limit_burst: u32,
+    /// The maximum number of system information requests handled at once across all clients. Must
+    /// be at least 1.
+    pub max_concurrent_requests: usize,
+    /// The maximum size of an API request body in bytes.
+    pub max_request_body_bytes: usize,
+    /// The system information to collect. Routes for information which isn't collected respond
+    /// with `404 Not Found`.
+    pub collectors: Vec<Collector>,
+    /// The information to collect about each process, if processes are collected.
+    pub process_attributes: Vec<ProcessAttribute>,
+    /// How long to wait for the space of each disk, in seconds. Disks which don't answer in time,
+    /// like hung network mounts, are reported as unreachable instead of holding up the refresh.
+    /// Set to 0 to wait for as long as it takes.
+    pub disk_timeout_secs: f32,
+    /// Whether to leave the mounts of network file systems, like NFS and CIFS, out of the disks.
+    pub exclude_network_disks: bool,
+    /// Whether to look for the processes which have the listening sockets open, if sockets are
+    /// collected. It reads every file descriptor of every process on each refresh.
+    pub socket_processes: bool,
+}
+
+impl AppConfig {
+    /// Whether the given information is collected.
+    pub fn collects(&self, collector: Collector) -> bool {
+        self.collectors.contains(&collector)
+    }
+
+    /// Checks the values which are out of range for their setting.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match Duration::try_from_secs_f32(self.refresh_rate_secs) {
+            Ok(refresh_rate) if !refresh_rate.is_zero() => {}
+            _ => bail!(
+                "WHTOP_REFRESH_RATE_SECS must be a number of seconds above 0, not {}",
+                self.refresh_rate_secs
+            ),
+        }
+        if Duration::try_from_secs_f32(self.disk_timeout_secs).is_err() {
+            bail!(
+                "WHTOP_DISK_TIMEOUT_SECS must be a number of seconds of at least 0, not {}",
+                self.disk_timeout_secs
+            );
+        }
+        // Lower rates refill too little to be noticed, and only make clients wait for ages
+        let valid_rate = self.rate_limit_per_sec == 0.0
+            || (self.rate_limit_per_sec.is_finite()
+                && self.rate_limit_per_sec >= MIN_RATE_LIMIT_PER_SEC);
+        if !valid_rate {
+            bail!(
+                "WHTOP_RATE_LIMIT_PER_SEC must be 0 or at least one request per day ({}), not {}",
+                MIN_RATE_LIMIT_PER_SEC,
+                self.rate_limit_per_sec
+            );
+        }
+        if self.max_concurrent_requests == 0 {
+            bail!("WHTOP_MAX_CONCURRENT_REQUESTS must be at least 1");
+        }
+        Ok(())
+    }
 }
 
 impl Default for AppConfig {
@@ -24,6 +94,158 @@ impl Default for AppConfig {
             address: (Ipv6Addr::UNSPECIFIED, 8080).into(),
             serve_static: true,
             static_dir: "dist".into(),
+            // A dashboard makes about 5 requests per second, so this leaves room for a few of them
+            // behind the same address
+            rate_limit_per_sec: 25.0,
+            rate_limit_burst: 100,
+            max_concurrent_requests: 16,
+            max_request_body_bytes: 16 * 1024,
+            collectors: vec![
+                Collector::Cpu,
+                Collector::Memory,
+                Collector::Processes,
+                Collector::Disks,
+                Collector::Networks,
+                Collector::Pressure,
+                Collector::Io,
+                Collector::Sockets,
+            ],
+            process_attributes: vec![ProcessAttribute::Cpu],
+            disk_timeout_secs: 1.0,
+            exclude_network_disks: false,
+            socket_processes: false,
+        }
+    }
+}
+
+/// System information which can be collected.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Collector {
+    /// CPU usage and frequency.
+    Cpu,
+    /// Memory usage.
+    Memory,
+    /// The running processes.
+    Processes,
+    /// Disks and their usage.
+    Disks,
+    /// Network interfaces and their usage.
+    Networks,
+    /// Hardware components, like temperature sensors.
+    Components,
+    /// Users on the system.
+    Users,
+    /// Pressure stall information.
+    Pressure,
+    /// The I/O activity of the block devices.
+    Io,
+    /// The sockets and the activity of TCP and UDP.
+    Sockets,
+}
+
+impl Display for Collector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Collector::Cpu => "cpu",
+            Collector::Memory => "memory",
+            Collector::Processes => "processes",
+            Collector::Disks => "disks",
+            Collector::Networks => "networks",
+            Collector::Components => "components",
+            Collector::Users => "users",
+            Collector::Pressure => "pressure",
+            Collector::Io => "io",
+            Collector::Sockets => "sockets",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Information which can be collected about each process.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessAttribute {
+    /// CPU usage.
+    Cpu,
+    /// Bytes read from and written to disk.
+    DiskUsage,
+    /// The user and group the process runs as.
+    User,
+    /// The open file descriptors and their limit. Every file descriptor of every process is read,
+    /// so this is slow on systems with many open files.
+    OpenFiles,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collectors_from_env() {
+        // Setup
+        let env = [
+            ("WHTOP_COLLECTORS".to_string(), "cpu,memory".to_string()),
+            (
+                "WHTOP_PROCESS_ATTRIBUTES".to_string(),
+                "cpu,disk_usage".to_string(),
+            ),
+        ];
+
+        // Execute
+        let config: AppConfig = envy::prefixed("WHTOP_").from_iter(env).unwrap();
+
+        // Assert
+        assert_eq!(vec![Collector::Cpu, Collector::Memory], config.collectors);
+        assert_eq!(
+            vec![ProcessAttribute::Cpu, ProcessAttribute::DiskUsage],
+            config.process_attributes
+        );
+        assert!(!config.collects(Collector::Processes));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(AppConfig::default().validate().is_ok());
+
+        for refresh_rate_secs in [0.0, f32::INFINITY, f32::NAN, -1.0, 1e30] {
+            let config = AppConfig {
+                refresh_rate_secs,
+                ..AppConfig::default()
+            };
+            assert!(config.validate().is_err(), "{refresh_rate_secs} is valid");
+        }
+
+        for disk_timeout_secs in [f32::INFINITY, f32::NAN, -1.0, 1e30] {
+            let config = AppConfig {
+                disk_timeout_secs,
+                ..AppConfig::default()
+            };
+            assert!(config.validate().is_err(), "{disk_timeout_secs} is valid");
+        }
+
+        for rate_limit_per_sec in [f64::INFINITY, f64::NAN, -1.0, 1e-20] {
+            let config = AppConfig {
+                rate_limit_per_sec,
+                ..AppConfig::default()
+            };
+            assert!(config.validate().is_err(), "{rate_limit_per_sec} is valid");
         }
+        let no_rate_limit = AppConfig {
+            rate_limit_per_sec: 0.0,
+            ..AppConfig::default()
+        };
+        assert!(no_rate_limit.validate().is_ok());
+        let slowest_rate_limit = AppConfig {
+            rate_limit_per_sec: MIN_RATE_LIMIT_PER_SEC,
+            ..AppConfig::default()
+        };
+        assert!(slowest_rate_limit.validate().is_ok());
+
+        let no_requests = AppConfig {
+            max_concurrent_requests: 0,
+            ..AppConfig::default()
+        };
+        assert!(no_requests.validate().is_err());
     }
 }
@@ -1,3 +0,0 @@
-mod request_error;
-
-pub use request_error::*;
@@ -1,7 +1,11 @@
 mod cache_control;
+mod concurrency_limit;
 mod last_modified;
+mod rate_limit;
 mod system_refresh;
 
 pub use cache_control::*;
+pub use concurrency_limit::*;
 pub use last_modified::*;
+pub use rate_limit::*;
 pub use system_refresh::*;
@@ -0,0 +1,119 @@
+use axum::{
+    http::Request,
+    response::{IntoResponse, Response},
+};
+use pin_project_lite::pin_project;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::{Layer, Service};
+use whtop_common::models::api::ErrorKind;
+
+use crate::routes::RouteError;
+
+/// How long clients are told to wait before retrying a rejected request.
+const RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Limits the number of requests handled at once across all clients. Unlike
+/// [`tower::limit::ConcurrencyLimitLayer`], requests over the limit are rejected immediately with
+/// `503 Service Unavailable` and a `retry-after` header rather than queued.
+#[derive(Clone, Debug)]
+pub struct ConcurrencyLimitLayer {
+    semaphore: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimitLayer {
+    pub fn new(max_concurrent_requests: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent_requests)),
+        }
+    }
+}
+
+impl<S> Layer<S> for ConcurrencyLimitLayer {
+    type Service = ConcurrencyLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConcurrencyLimit {
+            inner,
+            semaphore: self.semaphore.clone(),
+        }
+    }
+}
+
+/// Limits the number of requests handled at once.
+#[derive(Clone, Debug)]
+pub struct ConcurrencyLimit<S> {
+    inner: S,
+    semaphore: Arc<Semaphore>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for ConcurrencyLimit<S>
+where
+    S: Service<Request<ReqBody>, Response = Response>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ConcurrencyLimitFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        match self.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => ConcurrencyLimitFuture::Inner {
+                inner: self.inner.call(req),
+                _permit: permit,
+            },
+            Err(_) => {
+                let response = RouteError::new(
+                    ErrorKind::Unavailable,
+                    "the server is handling too many requests, try again later",
+                )
+                .with_retry_after(RETRY_AFTER)
+                .into_response();
+                ConcurrencyLimitFuture::Limited {
+                    response: Some(response),
+                }
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`ConcurrencyLimit`].
+    #[project = ConcurrencyLimitFutureProj]
+    pub enum ConcurrencyLimitFuture<F> {
+        Inner {
+            #[pin]
+            inner: F,
+            // Released when the future is dropped
+            _permit: OwnedSemaphorePermit,
+        },
+        Limited {
+            response: Option<Response>,
+        },
+    }
+}
+
+impl<F, E> Future for ConcurrencyLimitFuture<F>
+where
+    F: Future<Output = Result<Response, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            ConcurrencyLimitFutureProj::Inner { inner, .. } => inner.poll(cx),
+            ConcurrencyLimitFutureProj::Limited { response } => {
+                Poll::Ready(Ok(response.take().expect("polled after completion")))
+            }
+        }
+    }
+}
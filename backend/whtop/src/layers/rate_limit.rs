@@ -0,0 +1,270 @@
+use axum::{
+    extract::ConnectInfo,
+    http::Request,
+    response::{IntoResponse, Response},
+};
+use pin_project_lite::pin_project;
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use whtop_common::models::api::ErrorKind;
+
+use crate::routes::RouteError;
+
+/// The number of tracked clients after which clients with full buckets are forgotten.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Options for limiting the rate of requests from each client.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RateLimitOptions {
+    /// The number of requests each client can make per second on average.
+    pub requests_per_sec: f64,
+    /// The number of requests each client can make at once before being limited.
+    pub burst: u32,
+}
+
+/// Limits the rate of requests from each client using a token bucket per client. Clients are
+/// identified by their IP address, so the server must be run with
+/// [`into_make_service_with_connect_info`](axum::Router::into_make_service_with_connect_info).
+/// Requests without connection info share a single bucket.
+///
+/// Limited requests are rejected with `429 Too Many Requests` and a `retry-after` header.
+#[derive(Clone, Debug)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(options: RateLimitOptions) -> Self {
+        Self {
+            limiter: Arc::new(RateLimiter::new(options)),
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Limits the rate of requests from each client.
+#[derive(Clone, Debug)]
+pub struct RateLimit<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for RateLimit<S>
+where
+    S: Service<Request<ReqBody>, Response = Response>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = RateLimitFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let client = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip());
+        match self.limiter.try_acquire(client, Instant::now()) {
+            Ok(()) => RateLimitFuture::Inner {
+                inner: self.inner.call(req),
+            },
+            Err(retry_after) => {
+                let response = RouteError::new(
+                    ErrorKind::TooManyRequests,
+                    "too many requests were sent, try again later",
+                )
+                .with_retry_after(retry_after)
+                .into_response();
+                RateLimitFuture::Limited {
+                    response: Some(response),
+                }
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`RateLimit`].
+    #[project = RateLimitFutureProj]
+    pub enum RateLimitFuture<F> {
+        Inner {
+            #[pin]
+            inner: F,
+        },
+        Limited {
+            response: Option<Response>,
+        },
+    }
+}
+
+impl<F, E> Future for RateLimitFuture<F>
+where
+    F: Future<Output = Result<Response, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            RateLimitFutureProj::Inner { inner } => inner.poll(cx),
+            RateLimitFutureProj::Limited { response } => {
+                Poll::Ready(Ok(response.take().expect("polled after completion")))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RateLimiter {
+    options: RateLimitOptions,
+    buckets: Mutex<HashMap<Option<IpAddr>, TokenBucket>>,
+}
+
+impl RateLimiter {
+    fn new(options: RateLimitOptions) -> Self {
+        RateLimiter {
+            options,
+            buckets: Default::default(),
+        }
+    }
+
+    /// Takes a token from the client's bucket. If the bucket is empty, returns how long until the
+    /// next token is available instead.
+    fn try_acquire(&self, client: Option<IpAddr>, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.options.burst.max(1));
+        let rate = self.options.requests_per_sec;
+        let mut buckets = self.buckets.lock().expect("rate limiter poisoned");
+
+        // Forget clients which haven't made any requests recently
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| bucket.tokens_at(now, rate, capacity) < capacity);
+        }
+
+        let bucket = buckets.entry(client).or_insert(TokenBucket {
+            tokens: capacity,
+            updated: now,
+        });
+        bucket.tokens = bucket.tokens_at(now, rate, capacity);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            // A tiny rate can make the wait too long for a duration
+            Err(Duration::try_from_secs_f64((1.0 - bucket.tokens) / rate).unwrap_or(Duration::MAX))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// The number of tokens in the bucket after refilling it up to the given time.
+    fn tokens_at(&self, now: Instant, rate: f64, capacity: f64) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        (self.tokens + elapsed * rate).min(capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    const CLIENT: Option<IpAddr> = Some(IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+    #[test]
+    fn test_burst_then_limited() {
+        // Setup
+        let limiter = RateLimiter::new(RateLimitOptions {
+            requests_per_sec: 2.0,
+            burst: 3,
+        });
+        let now = Instant::now();
+
+        // Execute
+        let results: Vec<_> = (0..4).map(|_| limiter.try_acquire(CLIENT, now)).collect();
+
+        // Assert
+        assert_eq!(
+            vec![Ok(()), Ok(()), Ok(()), Err(Duration::from_millis(500))],
+            results
+        );
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        // Setup
+        let limiter = RateLimiter::new(RateLimitOptions {
+            requests_per_sec: 2.0,
+            burst: 1,
+        });
+        let now = Instant::now();
+        limiter.try_acquire(CLIENT, now).unwrap();
+
+        // Execute
+        let early = limiter.try_acquire(CLIENT, now + Duration::from_millis(250));
+        let late = limiter.try_acquire(CLIENT, now + Duration::from_millis(500));
+
+        // Assert
+        assert_eq!(Err(Duration::from_millis(250)), early);
+        assert_eq!(Ok(()), late);
+    }
+
+    #[test]
+    fn test_clients_are_independent() {
+        // Setup
+        let limiter = RateLimiter::new(RateLimitOptions {
+            requests_per_sec: 1.0,
+            burst: 1,
+        });
+        let now = Instant::now();
+        limiter.try_acquire(CLIENT, now).unwrap();
+
+        // Execute
+        let other = limiter.try_acquire(Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))), now);
+
+        // Assert
+        assert_eq!(Ok(()), other);
+    }
+
+    #[test]
+    fn test_tiny_rate() {
+        // Setup
+        let limiter = RateLimiter::new(RateLimitOptions {
+            requests_per_sec: 1e-20,
+            burst: 1,
+        });
+        let now = Instant::now();
+        limiter.try_acquire(CLIENT, now).unwrap();
+
+        // Execute
+        let limited = limiter.try_acquire(CLIENT, now);
+
+        // Assert
+        assert_eq!(Err(Duration::MAX), limited);
+    }
+}
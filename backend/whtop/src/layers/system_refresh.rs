@@ -2,33 +2,53 @@ use axum::http::Request;
 use chrono::{DateTime, Duration, Local};
 use futures::{future::BoxFuture, FutureExt};
 use std::{
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
-use sysinfo::{System, SystemExt};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tower::{Layer, Service};
 use tracing::debug;
 
+use crate::snapshot::{Snapshot, SnapshotSource};
+
 #[derive(Clone, Debug)]
 pub struct RefreshSystemLayer {
-    system: Arc<RwLock<System>>,
+    source: Arc<Mutex<dyn SnapshotSource>>,
+    snapshot: Arc<RwLock<Snapshot>>,
     refresh_rate: Duration,
     last_refresh: Arc<RwLock<Option<DateTime<Local>>>>,
+    generation: Arc<AtomicU64>,
 }
 
 impl RefreshSystemLayer {
-    pub fn new(system: Arc<RwLock<System>>, refresh_rate: Duration) -> Self {
+    /// Creates a layer which takes a snapshot from the source at most once every `refresh_rate`.
+    pub fn new(source: impl SnapshotSource + 'static, refresh_rate: Duration) -> Self {
         RefreshSystemLayer {
-            system,
+            source: Arc::new(Mutex::new(source)),
+            snapshot: Default::default(),
             refresh_rate,
             last_refresh: Default::default(),
+            generation: Default::default(),
         }
     }
 
+    /// The latest snapshot taken from the source.
+    pub fn snapshot(&self) -> Arc<RwLock<Snapshot>> {
+        self.snapshot.clone()
+    }
+
     pub fn last_refresh(&self) -> Arc<RwLock<Option<DateTime<Local>>>> {
         self.last_refresh.clone()
     }
+
+    /// The number of times the snapshot has changed. This is only updated while the write lock on
+    /// the snapshot is held, so it is stable while a read lock is held.
+    pub fn generation(&self) -> Arc<AtomicU64> {
+        self.generation.clone()
+    }
 }
 
 impl<S> Layer<S> for RefreshSystemLayer {
@@ -37,9 +57,11 @@ impl<S> Layer<S> for RefreshSystemLayer {
     fn layer(&self, inner: S) -> Self::Service {
         RefreshSystem {
             inner,
-            system: self.system.clone(),
+            source: self.source.clone(),
+            snapshot: self.snapshot.clone(),
             refresh_rate: self.refresh_rate,
             last_refresh: self.last_refresh.clone(),
+            generation: self.generation.clone(),
         }
     }
 }
@@ -47,9 +69,11 @@ impl<S> Layer<S> for RefreshSystemLayer {
 #[derive(Clone, Debug)]
 pub struct RefreshSystem<S> {
     inner: S,
-    system: Arc<RwLock<System>>,
+    source: Arc<Mutex<dyn SnapshotSource>>,
+    snapshot: Arc<RwLock<Snapshot>>,
     refresh_rate: Duration,
     last_refresh: Arc<RwLock<Option<DateTime<Local>>>>,
+    generation: Arc<AtomicU64>,
 }
 
 impl<ReqBody, S> Service<Request<ReqBody>> for RefreshSystem<S>
@@ -66,9 +90,11 @@ where
     }
 
     fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
-        let system = self.system.clone();
+        let source = self.source.clone();
+        let snapshot = self.snapshot.clone();
         let refresh_rate = self.refresh_rate;
         let last_refresh = self.last_refresh.clone();
+        let generation = self.generation.clone();
         let inner = self.inner.call(req);
         async move {
             // Update system if needed
@@ -83,7 +109,13 @@ where
                 if let Some(now) = should_refresh(*guard, refresh_rate) {
                     debug!(?last_refresh, "refreshing system");
                     *guard = Some(now);
-                    system.write().await.refresh_all();
+                    // Replayed sources don't change between every refresh
+                    let next = source.lock().await.next_snapshot();
+                    if let Some(next) = next {
+                        let mut snapshot = snapshot.write().await;
+                        *snapshot = next;
+                        generation.fetch_add(1, Ordering::Release);
+                    }
                 }
             }
 
@@ -1,8 +1,10 @@
+mod cli;
 mod config;
-mod errors;
 mod layers;
 mod modules;
+mod recording;
 mod routes;
+mod snapshot;
 mod startup;
 
 #[tokio::main]
@@ -1,16 +1,31 @@
 use crate::{
-    config::AppConfig,
-    layers::{CacheControlLayer, CacheOptions, LastModifiedLayer, RefreshSystemLayer},
-    routes::api::system::SystemState,
+    config::{AppConfig, Collector, ProcessAttribute},
+    layers::{
+        CacheControlLayer, CacheOptions, ConcurrencyLimitLayer, LastModifiedLayer,
+        RefreshSystemLayer,
+    },
+    routes::api::{
+        system::{self, SystemState},
+        v1,
+    },
+    snapshot::{LiveSource, SnapshotSource},
+};
+use axum::{
+    body::HttpBody,
+    http::{
+        header::{HeaderName, LINK},
+        HeaderValue,
+    },
+    Router,
 };
-use axum::{body::HttpBody, Router};
 use axum_extra::routing::SpaRouter;
 use chrono::{Duration, Local};
-use std::sync::Arc;
-use sysinfo::{CpuRefreshKind, ProcessRefreshKind, RefreshKind, System, SystemExt};
-use tokio::sync::RwLock;
+use sysinfo::{CpuRefreshKind, ProcessRefreshKind, RefreshKind, SystemBuilder};
 use tower::ServiceBuilder;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::{
+    cors::{Any, CorsLayer},
+    set_header::SetResponseHeaderLayer,
+};
 
 pub fn frontend<B>(config: &AppConfig) -> Router<(), B>
 where
@@ -23,26 +38,36 @@ where
     }
 }
 
-pub fn system<B>(config: &AppConfig) -> Router<(), B>
+/// Creates a source of snapshots of the live system, with the information for the enabled
+/// collectors.
+pub fn live_source(config: &AppConfig) -> LiveSource {
+    let refresh_kind = refresh_kind(config);
+    let mut builder = SystemBuilder::from_env().with_refresh_kind(with_lists(config, refresh_kind));
+    // The timeout is checked when the config is loaded
+    if config.disk_timeout_secs > 0.0 {
+        builder =
+            builder.with_disk_timeout(std::time::Duration::from_secs_f32(config.disk_timeout_secs));
+    }
+    if config.exclude_network_disks {
+        builder = builder.without_network_disks();
+    }
+    LiveSource::new(builder.build(), refresh_kind)
+}
+
+pub fn system<B>(config: &AppConfig, source: impl SnapshotSource + 'static) -> Router<(), B>
 where
     B: HttpBody + Send + 'static,
 {
-    // Create system info tracker
-    let system = System::new_with_specifics(
-        RefreshKind::new()
-            .with_cpu(CpuRefreshKind::new().with_cpu_usage().with_frequency())
-            .with_memory()
-            .with_processes(ProcessRefreshKind::new().with_cpu()),
-    );
-    let system = Arc::new(RwLock::new(system));
-
     // Layers
+    let concurrency_limit_layer = ConcurrencyLimitLayer::new(config.max_concurrent_requests);
     let refresh_layer = RefreshSystemLayer::new(
-        system.clone(),
+        source,
         Duration::seconds(config.refresh_rate_secs.floor() as i64)
             + Duration::nanoseconds((config.refresh_rate_secs.fract() * 1e9) as i64),
     );
+    let snapshot = refresh_layer.snapshot();
     let last_refresh = refresh_layer.last_refresh();
+    let generation = refresh_layer.generation();
     let cors_layer = CorsLayer::new().allow_origin(Any);
     let cache_control_layer = CacheControlLayer::new(CacheOptions {
         max_age: Some(config.refresh_rate_secs.floor() as u64),
@@ -57,26 +82,167 @@ where
         }
     });
 
-    // Build router
-    let state = SystemState { system };
-    Router::new()
+    // Build routers
+    let state = SystemState {
+        snapshot,
+        generation,
+        process_history: Default::default(),
+        v1_process_history: Default::default(),
+    };
+    let collected = |collector, router| {
+        if config.collects(collector) {
+            router
+        } else {
+            system::disabled(collector)
+        }
+    };
+    let deprecated_router = Router::new()
         .route(
             "/cpu",
-            crate::routes::api::system::cpu().with_state(state.clone()),
+            collected(Collector::Cpu, system::cpu()).with_state(state.clone()),
         )
         .route(
             "/memory",
-            crate::routes::api::system::memory().with_state(state.clone()),
+            collected(Collector::Memory, system::memory()).with_state(state.clone()),
         )
         .route(
             "/processes",
-            crate::routes::api::system::processes().with_state(state),
+            collected(Collector::Processes, system::processes()).with_state(state.clone()),
         )
         .layer(
             ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::overriding(
+                    HeaderName::from_static("deprecation"),
+                    HeaderValue::from_static("true"),
+                ))
+                .layer(SetResponseHeaderLayer::overriding(
+                    LINK,
+                    HeaderValue::from_static("</api/v1/system>; rel=\"successor-version\""),
+                )),
+        );
+    let v1_router = Router::new()
+        .route(
+            "/cpu",
+            collected(Collector::Cpu, v1::system::cpu()).with_state(state.clone()),
+        )
+        .route(
+            "/disks",
+            collected(Collector::Disks, v1::system::disks()).with_state(state.clone()),
+        )
+        .route(
+            "/io",
+            collected(Collector::Io, v1::system::io()).with_state(state.clone()),
+        )
+        .route(
+            "/load",
+            collected(Collector::Cpu, v1::system::load()).with_state(state.clone()),
+        )
+        .route(
+            "/memory",
+            collected(Collector::Memory, v1::system::memory()).with_state(state.clone()),
+        )
+        .route(
+            "/memory/detail",
+            collected(Collector::Memory, v1::system::memory_detail()).with_state(state.clone()),
+        )
+        .route(
+            "/networks",
+            collected(Collector::Networks, v1::system::networks()).with_state(state.clone()),
+        )
+        .route(
+            "/pressure",
+            collected(Collector::Pressure, v1::system::pressure()).with_state(state.clone()),
+        )
+        .route(
+            "/sockets",
+            collected(Collector::Sockets, v1::system::sockets()).with_state(state.clone()),
+        )
+        .route(
+            "/processes",
+            collected(Collector::Processes, v1::system::processes()).with_state(state.clone()),
+        )
+        .route(
+            "/processes/:pid",
+            collected(Collector::Processes, v1::system::process()).with_state(state.clone()),
+        )
+        .route("/diagnostics", v1::system::diagnostics().with_state(state));
+
+    Router::new()
+        .nest("/system", deprecated_router)
+        .nest("/v1/system", v1_router)
+        .layer(
+            ServiceBuilder::new()
+                .layer(concurrency_limit_layer)
                 .layer(refresh_layer)
                 .layer(cors_layer)
                 .layer(cache_control_layer)
                 .layer(last_modified_layer),
         )
 }
+
+/// Gets the information to refresh based on the enabled collectors.
+fn refresh_kind(config: &AppConfig) -> RefreshKind {
+    let mut refresh_kind = RefreshKind::new();
+    for &collector in &config.collectors {
+        refresh_kind = match collector {
+            Collector::Cpu => {
+                let cpu_refresh_kind = CpuRefreshKind::new()
+                    .with_cpu_usage()
+                    .with_frequency()
+                    .with_kernel_activity();
+                refresh_kind.with_cpu(cpu_refresh_kind)
+            }
+            Collector::Memory => refresh_kind.with_memory().with_memory_activity(),
+            Collector::Processes => refresh_kind.with_processes(process_refresh_kind(config)),
+            Collector::Disks => refresh_kind.with_disks(),
+            Collector::Networks => refresh_kind.with_networks(),
+            Collector::Components => refresh_kind.with_components(),
+            // Users are only listed when the system is created
+            Collector::Users => refresh_kind,
+            Collector::Pressure => refresh_kind.with_pressure(),
+            Collector::Io => refresh_kind.with_disks_io(),
+            Collector::Sockets if config.socket_processes => {
+                refresh_kind.with_sockets().with_socket_processes()
+            }
+            Collector::Sockets => refresh_kind.with_sockets(),
+        };
+    }
+
+    refresh_kind
+}
+
+/// Gets the process information to refresh. Only what the API serves is read, and the process
+/// list is always sorted by memory.
+fn process_refresh_kind(config: &AppConfig) -> ProcessRefreshKind {
+    let mut refresh_kind = ProcessRefreshKind::new().with_memory();
+    for &attribute in &config.process_attributes {
+        refresh_kind = match attribute {
+            ProcessAttribute::Cpu => refresh_kind.with_cpu(),
+            ProcessAttribute::DiskUsage => refresh_kind.with_disk_usage(),
+            ProcessAttribute::User => refresh_kind.with_user(),
+            ProcessAttribute::OpenFiles => refresh_kind.with_open_files().with_limits(),
+        };
+    }
+
+    refresh_kind
+}
+
+/// Adds the lists (disks, networks, etc.) for the enabled collectors. These are only gathered
+/// when the system is created, the same as [`sysinfo::SystemExt::refresh_all`].
+fn with_lists(config: &AppConfig, refresh_kind: RefreshKind) -> RefreshKind {
+    let mut refresh_kind = refresh_kind;
+    // The I/O activity is matched with the disks to know where each device is mounted
+    if refresh_kind.disks() || refresh_kind.disks_io() {
+        refresh_kind = refresh_kind.with_disks_list();
+    }
+    if refresh_kind.networks() {
+        refresh_kind = refresh_kind.with_networks_list();
+    }
+    if refresh_kind.components() {
+        refresh_kind = refresh_kind.with_components_list();
+    }
+    if config.collects(Collector::Users) {
+        refresh_kind = refresh_kind.with_users_list();
+    }
+    refresh_kind
+}
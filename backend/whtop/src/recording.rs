@@ -0,0 +1,458 @@
+//! Recording snapshots of the system to a file, and replaying them.
+//!
+//! A recording is a gzip compressed [JSON Lines](https://jsonlines.org/) file. The first line is a
+//! [`RecordingHeader`], and every following line is a [`Frame`]. Each frame is compressed and
+//! written as soon as it is taken, so a recording stays readable if the recorder is killed.
+//!
+//! Every frame is a full snapshot, which keeps replaying simple. The snapshots change little from
+//! one frame to the next, and the compression window spans several frames, so this repetition is
+//! mostly compressed away. Uncompressed recordings made by earlier versions can still be loaded.
+
+use anyhow::{bail, Context};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    io::{Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+use tokio::{
+    fs::File,
+    io::{AsyncWrite, AsyncWriteExt},
+    time::MissedTickBehavior,
+};
+use tracing::{info, warn};
+
+use crate::{
+    config::AppConfig,
+    snapshot::{Snapshot, SnapshotSource},
+};
+
+/// The version of the recording format written by this build.
+const RECORDING_VERSION: u32 = 1;
+
+/// The first bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The first line of a recording.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    /// The version of the recording format.
+    pub version: u32,
+    /// The number of milliseconds between frames when the recording was made.
+    pub interval_ms: u64,
+}
+
+/// A snapshot in a recording.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Frame {
+    /// The number of milliseconds since the recording started.
+    pub elapsed_ms: u64,
+    pub snapshot: Snapshot,
+}
+
+/// Records snapshots from the source to a file every refresh interval until interrupted.
+pub async fn record(
+    config: &AppConfig,
+    mut source: impl SnapshotSource,
+    out: &Path,
+) -> anyhow::Result<()> {
+    let interval = Duration::from_secs_f32(config.refresh_rate_secs);
+    let file = File::create(out)
+        .await
+        .with_context(|| format!("error creating {}", out.display()))?;
+    let mut writer = RecordingWriter::new(file);
+    let header = RecordingHeader {
+        version: RECORDING_VERSION,
+        interval_ms: interval.as_millis() as u64,
+    };
+    writer.write_line(&header).await?;
+
+    let mut ticks = tokio::time::interval(interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // The first tick completes immediately, but usage is measured since the previous refresh
+    ticks.tick().await;
+    let start = Instant::now();
+    let mut frames = 0u64;
+    // The handler is installed once, so that an interrupt between two ticks isn't missed
+    let interrupt = tokio::signal::ctrl_c();
+    tokio::pin!(interrupt);
+    info!("recording to {} until interrupted", out.display());
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {}
+            result = &mut interrupt => {
+                result.context("error waiting for interrupt")?;
+                break;
+            }
+        }
+
+        let Some(snapshot) = source.next_snapshot() else {
+            continue;
+        };
+        let frame = Frame {
+            elapsed_ms: start.elapsed().as_millis() as u64,
+            snapshot,
+        };
+        writer.write_line(&frame).await?;
+        frames += 1;
+    }
+
+    writer.finish().await?;
+    info!(frames, "recording stopped");
+    Ok(())
+}
+
+/// Compresses the lines of a recording as they are written.
+struct RecordingWriter<W> {
+    output: W,
+    encoder: GzEncoder<Vec<u8>>,
+}
+
+impl<W: AsyncWrite + Unpin> RecordingWriter<W> {
+    fn new(output: W) -> Self {
+        RecordingWriter {
+            output,
+            encoder: GzEncoder::new(Vec::new(), Compression::default()),
+        }
+    }
+
+    /// Writes a line. It can be decompressed as soon as this returns, without ending the stream.
+    async fn write_line<T: Serialize>(&mut self, value: &T) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.encoder, value).context("error serializing recording")?;
+        self.encoder
+            .write_all(b"\n")
+            .context("error compressing recording")?;
+        // A sync flush outputs everything written so far but keeps the compression window
+        self.encoder
+            .flush()
+            .context("error compressing recording")?;
+        self.write_compressed().await
+    }
+
+    /// Ends the stream.
+    async fn finish(mut self) -> anyhow::Result<()> {
+        self.encoder
+            .try_finish()
+            .context("error compressing recording")?;
+        self.write_compressed().await
+    }
+
+    async fn write_compressed(&mut self) -> anyhow::Result<()> {
+        let compressed = std::mem::take(self.encoder.get_mut());
+        self.output
+            .write_all(&compressed)
+            .await
+            .context("error writing recording")?;
+        self.output.flush().await.context("error writing recording")
+    }
+}
+
+/// A recording loaded into memory.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Recording {
+    /// The time between the last frame and the first one when looping.
+    interval: Duration,
+    /// The frames, with times relative to the first one.
+    frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Reads a recording from a file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content =
+            std::fs::read(path).with_context(|| format!("error reading {}", path.display()))?;
+        decompress(content)
+            .and_then(|content| Self::parse(&content))
+            .with_context(|| format!("invalid recording {}", path.display()))
+    }
+
+    fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut lines = content.split_terminator('\n').enumerate();
+        let header: RecordingHeader = match lines.next() {
+            Some((_, line)) => parse_line(line, 0)?,
+            None => bail!("the recording is empty"),
+        };
+        if header.version != RECORDING_VERSION {
+            bail!("unsupported recording version {}", header.version);
+        }
+
+        let mut frames = Vec::new();
+        for (index, line) in lines {
+            match parse_line::<Frame>(line, index) {
+                Ok(frame) => frames.push(frame),
+                // The recorder was killed while writing the last frame
+                Err(error) if !content.ends_with('\n') && index == content.lines().count() - 1 => {
+                    warn!("ignoring incomplete last frame: {error:#}");
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        let Some(first) = frames.first().map(|frame| frame.elapsed_ms) else {
+            bail!("the recording has no frames");
+        };
+        for frame in &mut frames {
+            frame.elapsed_ms = frame.elapsed_ms.saturating_sub(first);
+        }
+
+        Ok(Recording {
+            interval: Duration::from_millis(header.interval_ms.max(1)),
+            frames,
+        })
+    }
+
+    /// The time between frames when the recording was made.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// The position of the frame to show at the given time since the replay started. Positions
+    /// keep increasing when the recording loops, and are the index of the frame modulo the
+    /// number of frames.
+    fn position_at(&self, elapsed: Duration) -> u64 {
+        let last = self.frames.last().map_or(0, |frame| frame.elapsed_ms);
+        let duration = last + self.interval.as_millis() as u64;
+        let elapsed = elapsed.as_millis() as u64;
+        let offset = elapsed % duration;
+        let index = self
+            .frames
+            .partition_point(|frame| frame.elapsed_ms <= offset)
+            .saturating_sub(1);
+        elapsed / duration * self.frames.len() as u64 + index as u64
+    }
+
+    fn frame(&self, position: u64) -> &Frame {
+        &self.frames[(position % self.frames.len() as u64) as usize]
+    }
+}
+
+/// Decompresses a recording, unless it was made by a version which didn't compress them.
+fn decompress(content: Vec<u8>) -> anyhow::Result<String> {
+    let content = if content.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        match GzDecoder::new(content.as_slice()).read_to_end(&mut decompressed) {
+            Ok(_) => {}
+            // The recorder was killed before ending the stream, maybe in the middle of a frame.
+            // The frames before it are complete, and an incomplete last frame is ignored when
+            // parsing.
+            Err(error) if !decompressed.is_empty() => {
+                warn!("ignoring the end of the recording: {error}");
+            }
+            Err(error) => return Err(error).context("error decompressing"),
+        }
+        decompressed
+    } else {
+        content
+    };
+    match String::from_utf8(content) {
+        Ok(content) => Ok(content),
+        // The last frame can be cut in the middle of a character
+        Err(error) if error.utf8_error().error_len().is_none() => {
+            let valid = error.utf8_error().valid_up_to();
+            let mut content = error.into_bytes();
+            content.truncate(valid);
+            Ok(String::from_utf8(content).expect("truncated to the valid part"))
+        }
+        Err(error) => Err(error).context("the recording isn't UTF-8"),
+    }
+}
+
+fn parse_line<T: DeserializeOwned>(line: &str, index: usize) -> anyhow::Result<T> {
+    serde_json::from_str(line).with_context(|| format!("invalid line {}", index + 1))
+}
+
+/// Replays a recording in a loop.
+#[derive(Debug)]
+pub struct ReplaySource {
+    recording: Recording,
+    speed: f64,
+    start: Instant,
+    position: Option<u64>,
+}
+
+impl ReplaySource {
+    /// Creates a source which replays the recording from the start, `speed` times faster than
+    /// real time.
+    pub fn new(recording: Recording, speed: f64) -> Self {
+        ReplaySource {
+            recording,
+            speed,
+            start: Instant::now(),
+            position: None,
+        }
+    }
+}
+
+impl SnapshotSource for ReplaySource {
+    fn next_snapshot(&mut self) -> Option<Snapshot> {
+        let position = self
+            .recording
+            .position_at(self.start.elapsed().mul_f64(self.speed));
+        if self.position == Some(position) {
+            return None;
+        }
+
+        self.position = Some(position);
+        Some(self.recording.frame(position).snapshot.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording_content(frames: &[u64]) -> String {
+        let mut content = serde_json::to_string(&RecordingHeader {
+            version: RECORDING_VERSION,
+            interval_ms: 1000,
+        })
+        .unwrap();
+        for &elapsed_ms in frames {
+            let frame = Frame {
+                elapsed_ms,
+                snapshot: Snapshot::default(),
+            };
+            content.push('\n');
+            content.push_str(&serde_json::to_string(&frame).unwrap());
+        }
+        content.push('\n');
+        content
+    }
+
+    async fn compressed_recording(frames: &[u64], finish: bool) -> Vec<u8> {
+        let mut output = Vec::new();
+        let mut writer = RecordingWriter::new(&mut output);
+        writer
+            .write_line(&RecordingHeader {
+                version: RECORDING_VERSION,
+                interval_ms: 1000,
+            })
+            .await
+            .unwrap();
+        for &elapsed_ms in frames {
+            let frame = Frame {
+                elapsed_ms,
+                snapshot: Snapshot::default(),
+            };
+            writer.write_line(&frame).await.unwrap();
+        }
+        if finish {
+            writer.finish().await.unwrap();
+        }
+        output
+    }
+
+    #[tokio::test]
+    async fn test_decompress_round_trip() {
+        // Setup
+        let compressed = compressed_recording(&[1000, 2000], true).await;
+
+        // Execute
+        let content = decompress(compressed.clone()).unwrap();
+
+        // Assert
+        assert_eq!(GZIP_MAGIC, compressed[..2]);
+        assert!(compressed.len() < content.len());
+        assert_eq!(recording_content(&[1000, 2000]), content);
+    }
+
+    #[tokio::test]
+    async fn test_decompress_unfinished_recording() {
+        // Setup
+        let unfinished = compressed_recording(&[1000, 2000], false).await;
+        let mut truncated = compressed_recording(&[1000, 2000, 3000], false).await;
+        // Cut in the middle of the last frame
+        truncated.truncate((unfinished.len() + truncated.len()) / 2);
+
+        // Execute
+        let unfinished = Recording::parse(&decompress(unfinished).unwrap()).unwrap();
+        let truncated = Recording::parse(&decompress(truncated).unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(2, unfinished.frames.len());
+        assert_eq!(2, truncated.frames.len());
+    }
+
+    #[test]
+    fn test_decompress_uncompressed_recording() {
+        // Setup
+        let content = recording_content(&[1000, 2000]);
+
+        // Execute
+        let decompressed = decompress(content.clone().into_bytes()).unwrap();
+
+        // Assert
+        assert_eq!(content, decompressed);
+    }
+
+    #[test]
+    fn test_parse_rebases_frames() {
+        // Execute
+        let recording = Recording::parse(&recording_content(&[1000, 2000, 3500])).unwrap();
+
+        // Assert
+        let elapsed: Vec<_> = recording.frames.iter().map(|f| f.elapsed_ms).collect();
+        assert_eq!(vec![0, 1000, 2500], elapsed);
+        assert_eq!(Duration::from_secs(1), recording.interval());
+    }
+
+    #[test]
+    fn test_parse_ignores_incomplete_last_frame() {
+        // Setup
+        let mut content = recording_content(&[1000, 2000]);
+        content.push_str("{\"elapsed_ms\":3000,\"snap");
+
+        // Execute
+        let recording = Recording::parse(&content).unwrap();
+
+        // Assert
+        assert_eq!(2, recording.frames.len());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_recordings() {
+        // Setup
+        let mut invalid_frame = recording_content(&[1000]);
+        invalid_frame.push_str("{}\n");
+
+        // Execute
+        let empty = Recording::parse("");
+        let no_frames = Recording::parse(&recording_content(&[]));
+        let invalid_frame = Recording::parse(&invalid_frame);
+
+        // Assert
+        assert!(empty.is_err());
+        assert!(no_frames.is_err());
+        assert!(invalid_frame.is_err());
+    }
+
+    #[test]
+    fn test_position_loops() {
+        // Setup
+        let recording = Recording::parse(&recording_content(&[0, 1000, 2000])).unwrap();
+
+        // Execute
+        let positions: Vec<_> = [0, 999, 1000, 2999, 3000, 4500]
+            .into_iter()
+            .map(|ms| recording.position_at(Duration::from_millis(ms)))
+            .collect();
+
+        // Assert
+        assert_eq!(vec![0, 0, 1, 2, 3, 4], positions);
+    }
+
+    #[test]
+    fn test_replay_only_returns_changed_frames() {
+        // Setup
+        let recording = Recording::parse(&recording_content(&[0, 1000])).unwrap();
+        let mut source = ReplaySource::new(recording, 1.0);
+
+        // Execute
+        let first = source.next_snapshot();
+        let second = source.next_snapshot();
+
+        // Assert
+        assert!(first.is_some());
+        assert!(second.is_none());
+    }
+}
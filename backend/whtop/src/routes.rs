@@ -1,5 +1,7 @@
 pub mod api;
 
 mod error;
+mod extract;
 
 pub use error::*;
+pub use extract::*;
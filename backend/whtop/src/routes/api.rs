@@ -1 +1,6 @@
 pub mod system;
+pub mod v1;
+
+mod openapi;
+
+pub use openapi::*;
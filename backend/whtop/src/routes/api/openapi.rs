@@ -0,0 +1,67 @@
+use axum::{body::HttpBody, response::IntoResponse, routing::MethodRouter, Json};
+use utoipa::OpenApi;
+use whtop_common::models::api::ProblemDetails;
+
+use super::{system, v1};
+
+/// OpenAPI document for the whole API.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "whtop",
+        description = "A simple server monitoring tool",
+        license(name = "MIT OR Apache-2.0")
+    ),
+    components(schemas(ProblemDetails))
+)]
+pub struct ApiDoc;
+
+impl ApiDoc {
+    /// Builds the OpenAPI document, including the documents for each group of routes.
+    pub fn build() -> utoipa::openapi::OpenApi {
+        let mut doc = ApiDoc::openapi();
+        doc.merge(v1::system::SystemApi::openapi());
+        doc.merge(system::SystemApi::openapi());
+        doc
+    }
+}
+
+pub fn openapi<B>() -> MethodRouter<(), B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_openapi)
+}
+
+async fn get_openapi() -> impl IntoResponse {
+    Json(ApiDoc::build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The committed OpenAPI document. Set `WHTOP_UPDATE_OPENAPI=1` when running the tests to
+    /// regenerate it.
+    const OPENAPI_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    #[test]
+    fn test_openapi_up_to_date() {
+        // Setup
+        let expected = std::fs::read_to_string(OPENAPI_PATH).unwrap_or_default();
+
+        // Execute
+        let actual = ApiDoc::build().to_pretty_json().unwrap() + "\n";
+
+        // Assert
+        if std::env::var_os("WHTOP_UPDATE_OPENAPI").is_some() {
+            std::fs::write(OPENAPI_PATH, &actual).unwrap();
+        } else {
+            assert!(
+                actual == expected,
+                "openapi.json is out of date, rerun the tests with WHTOP_UPDATE_OPENAPI=1 to \
+                 update it"
+            );
+        }
+    }
+}
@@ -1,9 +1,37 @@
 mod cpu;
+mod disabled;
+mod history;
 mod memory;
 mod processes;
 mod state;
 
 pub use cpu::*;
+pub use disabled::*;
+pub use history::*;
 pub use memory::*;
 pub use processes::*;
 pub use state::*;
+
+use utoipa::OpenApi;
+use whtop_common::models::api::{
+    CpuInfo, GetCpuResponse, GetMemoryResponse, GetProcessesDeltaResponse, GetProcessesResponse,
+    GlobalCpuInfo, ProcessInfo,
+};
+
+/// OpenAPI document for the deprecated `/api/system` routes.
+#[derive(OpenApi)]
+#[openapi(
+    paths(cpu::get_cpu, memory::get_memory, processes::get_processes),
+    components(schemas(
+        CpuInfo,
+        GetCpuResponse,
+        GetMemoryResponse,
+        GetProcessesDeltaResponse,
+        GetProcessesResponse,
+        GlobalCpuInfo,
+        ProcessInfo,
+        ProcessesResponseBody,
+    )),
+    tags((name = "system", description = "Metrics about the host system."))
+)]
+pub struct SystemApi;
@@ -1,6 +1,5 @@
 use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
-use sysinfo::{Cpu, CpuExt, SystemExt};
-use whtop_common::models::api::{CpuInfo, GetCpuResponse, GlobalCpuInfo};
+use whtop_common::models::api::{v1, CpuInfo, GetCpuResponse, GlobalCpuInfo};
 
 use crate::routes::RouteResult;
 
@@ -10,37 +9,59 @@ pub fn cpu<B>() -> MethodRouter<SystemState, B>
 where
     B: HttpBody + Send + 'static,
 {
+    #[allow(deprecated)]
     MethodRouter::new().get(get_cpu)
 }
 
+/// Gets the usage of each CPU.
+#[utoipa::path(
+    get,
+    path = "/api/system/cpu",
+    tag = "system",
+    responses(
+        (status = 200, description = "The CPU usage metrics.", body = GetCpuResponse),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+#[deprecated = "use the `/api/v1` route instead"]
 async fn get_cpu(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
-    let system = state.system.read().await;
-    let global = create_global_cpu_info(system.global_cpu_info());
-    let cpus = system.cpus().iter().map(create_cpu_info).collect();
+    let snapshot = state.snapshot.read().await;
+    let cpus: Vec<CpuInfo> = snapshot.cpu.cpus.iter().map(create_cpu_info).collect();
+    // The global frequency used to be the highest one rather than the average
+    let global = GlobalCpuInfo {
+        usage: snapshot.cpu.global.usage_percent,
+        frequency: cpus
+            .iter()
+            .map(|cpu| cpu.inner.frequency)
+            .max()
+            .unwrap_or(0),
+    };
     let response = GetCpuResponse { global, cpus };
     Ok(Json(response))
 }
 
-fn create_cpu_info(cpu: &Cpu) -> CpuInfo {
+fn create_cpu_info(cpu: &v1::CpuInfo) -> CpuInfo {
     CpuInfo {
-        name: cpu.name().into(),
-        inner: create_global_cpu_info(cpu),
-    }
-}
-
-fn create_global_cpu_info(cpu: &Cpu) -> GlobalCpuInfo {
-    GlobalCpuInfo {
-        usage: cpu.cpu_usage(),
-        frequency: cpu.frequency(),
+        name: cpu.name.clone(),
+        inner: GlobalCpuInfo {
+            usage: cpu.inner.usage_percent,
+            frequency: cpu.inner.frequency_mhz,
+        },
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::snapshot::Snapshot;
     use axum::{body::Bytes, http::StatusCode, response::IntoResponse};
     use std::sync::Arc;
-    use sysinfo::System;
+    use sysinfo::{System, SystemExt};
     use tokio::sync::RwLock;
     use whtop_common::models::api::GetCpuResponse;
 
@@ -61,14 +82,21 @@ mod tests {
     }
 
     #[tokio::test]
+    #[allow(deprecated)]
     async fn test_get_cpu() {
         // Setup
         let mut system = System::new_all();
         system.refresh_all();
-        let system = Arc::new(RwLock::new(system));
+        let snapshot = Arc::new(RwLock::new(Snapshot::capture(&system)));
 
         // Execute
-        let response = get_cpu(State(SystemState { system })).await.unwrap();
+        let state = SystemState {
+            snapshot,
+            generation: Default::default(),
+            process_history: Default::default(),
+            v1_process_history: Default::default(),
+        };
+        let response = get_cpu(State(state)).await.unwrap();
 
         // Assert
         let (parts, body) = response.into_response().into_parts();
@@ -0,0 +1,19 @@
+use axum::{body::HttpBody, routing::MethodRouter};
+use whtop_common::models::api::ErrorKind;
+
+use crate::{config::Collector, routes::RouteError};
+
+/// Responds to every request with `404 Not Found` because the information the route provides is
+/// not being collected.
+pub fn disabled<S, B>(collector: Collector) -> MethodRouter<S, B>
+where
+    S: Clone + Send + Sync + 'static,
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(move || async move {
+        Err::<(), _>(RouteError::new(
+            ErrorKind::NotFound,
+            format!("the {collector} collector is disabled"),
+        ))
+    })
+}
@@ -0,0 +1,131 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::Arc,
+};
+
+/// The number of process list generations to keep for computing deltas.
+const PROCESS_HISTORY_LEN: usize = 8;
+
+/// A process list, keyed by process ID.
+pub type ProcessSnapshot<K, P> = HashMap<K, P>;
+
+/// The most recent process lists sent to clients, by generation.
+#[derive(Debug)]
+pub struct ProcessHistory<K, P> {
+    snapshots: VecDeque<(u64, Arc<ProcessSnapshot<K, P>>)>,
+}
+
+impl<K, P> ProcessHistory<K, P> {
+    /// Gets the process list for the given generation, creating it if it hasn't been captured
+    /// yet.
+    pub fn snapshot<F>(&mut self, generation: u64, create: F) -> Arc<ProcessSnapshot<K, P>>
+    where
+        F: FnOnce() -> ProcessSnapshot<K, P>,
+    {
+        if let Some(snapshot) = self.get(generation) {
+            return snapshot;
+        }
+
+        let snapshot = Arc::new(create());
+        if self.snapshots.len() >= PROCESS_HISTORY_LEN {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back((generation, snapshot.clone()));
+        snapshot
+    }
+
+    /// Gets the process list for the given generation, if it is still known.
+    pub fn get(&self, generation: u64) -> Option<Arc<ProcessSnapshot<K, P>>> {
+        self.snapshots
+            .iter()
+            .find(|(g, _)| *g == generation)
+            .map(|(_, snapshot)| snapshot.clone())
+    }
+}
+
+impl<K, P> Default for ProcessHistory<K, P> {
+    fn default() -> Self {
+        ProcessHistory {
+            snapshots: VecDeque::with_capacity(PROCESS_HISTORY_LEN),
+        }
+    }
+}
+
+/// The changes between two process lists.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProcessDelta<K, P> {
+    pub added: Vec<P>,
+    pub changed: Vec<P>,
+    pub removed: Vec<K>,
+}
+
+impl<K, P> ProcessDelta<K, P>
+where
+    K: Clone + Eq + Hash,
+    P: Clone + PartialEq,
+{
+    pub fn between(previous: &ProcessSnapshot<K, P>, current: &ProcessSnapshot<K, P>) -> Self {
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for (pid, process) in current {
+            match previous.get(pid) {
+                None => added.push(process.clone()),
+                Some(old) if old != process => changed.push(process.clone()),
+                Some(_) => {}
+            }
+        }
+        let removed = previous
+            .keys()
+            .filter(|pid| !current.contains_key(*pid))
+            .cloned()
+            .collect();
+
+        ProcessDelta {
+            added,
+            changed,
+            removed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_between() {
+        // Setup
+        let previous = ProcessSnapshot::from([(1, 10), (2, 20), (3, 30)]);
+        let current = ProcessSnapshot::from([(1, 10), (2, 25), (4, 40)]);
+
+        // Execute
+        let delta = ProcessDelta::between(&previous, &current);
+
+        // Assert
+        assert_eq!(
+            delta,
+            ProcessDelta {
+                added: vec![40],
+                changed: vec![25],
+                removed: vec![3],
+            }
+        );
+    }
+
+    #[test]
+    fn test_history_evicts_old_generations() {
+        // Setup
+        let mut history = ProcessHistory::<u32, u32>::default();
+
+        // Execute
+        for generation in 0..=PROCESS_HISTORY_LEN as u64 {
+            history.snapshot(generation, ProcessSnapshot::new);
+        }
+
+        // Assert
+        assert!(history.get(0).is_none());
+        assert!(history.get(1).is_some());
+        assert!(history.get(PROCESS_HISTORY_LEN as u64).is_some());
+    }
+}
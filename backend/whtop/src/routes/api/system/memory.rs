@@ -1,5 +1,4 @@
 use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
-use sysinfo::SystemExt;
 use whtop_common::models::api::GetMemoryResponse;
 
 use crate::routes::RouteResult;
@@ -10,16 +9,34 @@ pub fn memory<B>() -> MethodRouter<SystemState, B>
 where
     B: HttpBody + Send + 'static,
 {
+    #[allow(deprecated)]
     MethodRouter::new().get(get_memory)
 }
 
+/// Gets the memory usage.
+#[utoipa::path(
+    get,
+    path = "/api/system/memory",
+    tag = "system",
+    responses(
+        (status = 200, description = "The memory usage metrics.", body = GetMemoryResponse),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+#[deprecated = "use the `/api/v1` route instead"]
 async fn get_memory(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
-    let system = state.system.read().await;
+    // These used to be in kilobytes
+    let memory = &state.snapshot.read().await.memory;
     let response = GetMemoryResponse {
-        total: system.total_memory(),
-        used: system.used_memory(),
-        free: system.free_memory(),
-        available: system.available_memory(),
+        total: memory.total_bytes / 1000,
+        used: memory.used_bytes / 1000,
+        free: memory.free_bytes / 1000,
+        available: memory.available_bytes / 1000,
     };
     Ok(Json(response))
 }
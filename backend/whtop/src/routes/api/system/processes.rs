@@ -1,41 +1,139 @@
-use std::cmp::Reverse;
+use std::{cmp::Reverse, sync::atomic::Ordering};
 
 use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
-use sysinfo::{Pid, Process, ProcessExt, System, SystemExt};
-use whtop_common::models::api::{GetProcessesResponse, ProcessInfo};
+use serde::Serialize;
+use utoipa::ToSchema;
+use whtop_common::models::api::{
+    v1, GetProcessesDeltaResponse, GetProcessesQuery, GetProcessesResponse, ProcessInfo,
+};
 
-use crate::routes::RouteResult;
+use crate::{
+    routes::{Query, RouteResult},
+    snapshot,
+};
 
-use super::SystemState;
+use super::{ProcessDelta, ProcessSnapshot, SystemState};
+
+type Snapshot = ProcessSnapshot<String, ProcessInfo>;
 
 pub fn processes<B>() -> MethodRouter<SystemState, B>
 where
     B: HttpBody + Send + 'static,
 {
+    #[allow(deprecated)]
     MethodRouter::new().get(get_processes)
 }
 
-async fn get_processes(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
-    let system = state.system.read().await;
-    let response: GetProcessesResponse = create_response(&system);
+/// Gets the running processes.
+#[utoipa::path(
+    get,
+    path = "/api/system/processes",
+    tag = "system",
+    params(GetProcessesQuery),
+    responses(
+        (
+            status = 200,
+            description = "The full process list, or the changes since `since` if it was set.",
+            body = ProcessesResponseBody,
+        ),
+        (
+            status = 400,
+            description = "The query parameters are invalid.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+#[deprecated = "use the `/api/v1` route instead"]
+async fn get_processes(
+    State(state): State<SystemState>,
+    Query(query): Query<GetProcessesQuery>,
+) -> RouteResult<impl IntoResponse> {
+    // The generation only changes while the snapshot is write locked
+    let latest = state.snapshot.read().await;
+    let generation = state.generation.load(Ordering::Acquire);
+    let (current, previous) = {
+        let mut history = state.process_history.write().await;
+        let current = history.snapshot(generation, || create_snapshot(&latest));
+        let previous = query.since.and_then(|since| history.get(since));
+        (current, previous)
+    };
+    drop(latest);
+
+    let response = match (query.since, previous) {
+        (None, _) => ProcessesResponseBody::Full(create_response(generation, &current)),
+        (Some(_), Some(previous)) => {
+            ProcessesResponseBody::Delta(create_delta_response(generation, &previous, &current))
+        }
+        (Some(_), None) => ProcessesResponseBody::Delta(GetProcessesDeltaResponse::Resync(
+            create_response(generation, &current),
+        )),
+    };
     Ok(Json(response))
 }
 
-fn create_response(system: &System) -> GetProcessesResponse {
-    let mut processes: Vec<ProcessInfo> =
-        system.processes().iter().map(create_process_info).collect();
+/// The response from getting the running processes.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum ProcessesResponseBody {
+    /// The full process list, sent when `since` is not set.
+    Full(GetProcessesResponse),
+    /// The changes to the process list, sent when `since` is set.
+    Delta(GetProcessesDeltaResponse),
+}
+
+fn create_snapshot(latest: &snapshot::Snapshot) -> Snapshot {
+    let cpu_count = latest.cpu_count() as f32;
+    latest
+        .processes
+        .iter()
+        .map(|process| create_process_info(process, cpu_count))
+        .map(|process| (process.pid.clone(), process))
+        .collect()
+}
+
+fn create_response(generation: u64, snapshot: &Snapshot) -> GetProcessesResponse {
+    let mut processes: Vec<ProcessInfo> = snapshot.values().cloned().collect();
     processes.sort_unstable_by_key(|process| Reverse(process.memory));
-    GetProcessesResponse { processes }
+    GetProcessesResponse {
+        generation,
+        processes,
+    }
+}
+
+fn create_delta_response(
+    generation: u64,
+    previous: &Snapshot,
+    current: &Snapshot,
+) -> GetProcessesDeltaResponse {
+    let ProcessDelta {
+        added,
+        changed,
+        removed,
+    } = ProcessDelta::between(previous, current);
+    GetProcessesDeltaResponse::Delta {
+        generation,
+        added,
+        changed,
+        removed,
+    }
 }
 
-fn create_process_info((pid, process): (&Pid, &Process)) -> ProcessInfo {
+fn create_process_info(process: &v1::ProcessInfo, cpu_count: f32) -> ProcessInfo {
+    // The usage used to be of a single CPU, and memory used to be in kilobytes
     ProcessInfo {
-        pid: pid.to_string(),
-        parent_pid: process.parent().map(|pid| pid.to_string()),
-        name: process.name().into(),
-        cpu: process.cpu_usage(),
-        memory: process.memory(),
-        virtual_memory: process.virtual_memory(),
-        run_time: process.run_time(),
+        pid: process.pid.to_string(),
+        parent_pid: process.parent_pid.map(|pid| pid.to_string()),
+        name: process.name.clone(),
+        cpu: process.cpu_percent * cpu_count,
+        memory: process.memory_bytes / 1000,
+        virtual_memory: process.virtual_memory_bytes / 1000,
+        run_time: process.run_time_seconds,
     }
 }
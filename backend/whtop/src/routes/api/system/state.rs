@@ -1,9 +1,19 @@
-use std::sync::Arc;
+use std::sync::{atomic::AtomicU64, Arc};
 
-use sysinfo::System;
 use tokio::sync::RwLock;
+use whtop_common::models::api::{v1, ProcessInfo};
+
+use crate::snapshot::Snapshot;
+
+use super::ProcessHistory;
 
 #[derive(Clone)]
 pub struct SystemState {
-    pub system: Arc<RwLock<System>>,
+    pub snapshot: Arc<RwLock<Snapshot>>,
+    /// The number of times `snapshot` has changed.
+    pub generation: Arc<AtomicU64>,
+    /// Recent process lists, used to send only the changes to clients.
+    pub process_history: Arc<RwLock<ProcessHistory<String, ProcessInfo>>>,
+    /// Recent process lists for the `/api/v1` routes.
+    pub v1_process_history: Arc<RwLock<ProcessHistory<u32, v1::ProcessInfo>>>,
 }
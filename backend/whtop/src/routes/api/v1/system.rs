@@ -0,0 +1,88 @@
+mod cpu;
+mod diagnostics;
+mod disks;
+mod io;
+mod load;
+mod memory;
+mod networks;
+mod pressure;
+mod processes;
+mod sockets;
+
+pub use cpu::*;
+pub use diagnostics::*;
+pub use disks::*;
+pub use io::*;
+pub use load::*;
+pub use memory::*;
+pub use networks::*;
+pub use pressure::*;
+pub use processes::*;
+pub use sockets::*;
+
+use utoipa::OpenApi;
+use whtop_common::models::api::v1::{
+    CpuInfo, CpuUsageBreakdown, DeviceIo, DiskInfo, Duplex, GetCpuResponse, GetDiagnosticsResponse,
+    GetDisksResponse, GetIoResponse, GetLoadResponse, GetMemoryDetailResponse, GetMemoryResponse,
+    GetNetworksResponse, GetPressureResponse, GetProcessesDeltaResponse, GetProcessesResponse,
+    GetSocketsResponse, GlobalCpuInfo, ListeningSocket, LoadAverage, NetworkInterface, OpenFiles,
+    OperationalState, PressureStall, ProcessInfo, RefreshArea, RefreshErrorInfo, ResourcePressure,
+    SocketProcess, SocketProtocol, TcpActivity, TcpStateCounts, UdpActivity,
+};
+
+/// OpenAPI document for the `/api/v1/system` routes.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        cpu::get_cpu,
+        diagnostics::get_diagnostics,
+        disks::get_disks,
+        io::get_io,
+        load::get_load,
+        memory::get_memory,
+        memory::get_memory_detail,
+        networks::get_networks,
+        pressure::get_pressure,
+        processes::get_processes,
+        processes::get_process,
+        sockets::get_sockets
+    ),
+    components(schemas(
+        CpuInfo,
+        CpuUsageBreakdown,
+        DeviceIo,
+        DiskInfo,
+        Duplex,
+        GetCpuResponse,
+        GetDiagnosticsResponse,
+        GetDisksResponse,
+        GetIoResponse,
+        GetLoadResponse,
+        GetMemoryDetailResponse,
+        GetMemoryResponse,
+        GetNetworksResponse,
+        GetPressureResponse,
+        GetProcessesDeltaResponse,
+        GetProcessesResponse,
+        GetSocketsResponse,
+        GlobalCpuInfo,
+        ListeningSocket,
+        LoadAverage,
+        NetworkInterface,
+        OpenFiles,
+        OperationalState,
+        PressureStall,
+        ProcessInfo,
+        ProcessesResponseBody,
+        RefreshArea,
+        RefreshErrorInfo,
+        ResourcePressure,
+        SocketProcess,
+        SocketProtocol,
+        TcpActivity,
+        TcpStateCounts,
+        UdpActivity,
+    )),
+    tags((name = "system", description = "Metrics about the host system."))
+)]
+pub struct SystemApi;
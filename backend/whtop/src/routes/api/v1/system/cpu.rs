@@ -0,0 +1,30 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn cpu<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_cpu)
+}
+
+/// Gets the usage of each CPU.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/cpu",
+    tag = "system",
+    responses(
+        (status = 200, description = "The CPU usage metrics.", body = v1::GetCpuResponse),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_cpu(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.cpu.clone()))
+}
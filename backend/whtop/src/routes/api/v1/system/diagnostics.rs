@@ -0,0 +1,34 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn diagnostics<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_diagnostics)
+}
+
+/// Gets the errors which happened while collecting the metrics.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/diagnostics",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The errors of the latest refresh.",
+            body = v1::GetDiagnosticsResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_diagnostics(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.diagnostics.clone()))
+}
@@ -0,0 +1,34 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn disks<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_disks)
+}
+
+/// Gets the mounted file systems and their usage.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/disks",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The usage of each mounted file system.",
+            body = v1::GetDisksResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_disks(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.disks.clone()))
+}
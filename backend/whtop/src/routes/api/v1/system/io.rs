@@ -0,0 +1,34 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn io<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_io)
+}
+
+/// Gets the I/O activity of the block devices.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/io",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The I/O activity of each block device.",
+            body = v1::GetIoResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_io(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.io.clone()))
+}
@@ -0,0 +1,34 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn load<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_load)
+}
+
+/// Gets the load average and the activity of the kernel scheduler.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/load",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The load average and kernel activity.",
+            body = v1::GetLoadResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_load(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.load.clone()))
+}
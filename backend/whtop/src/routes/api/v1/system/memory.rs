@@ -0,0 +1,61 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn memory<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_memory)
+}
+
+pub fn memory_detail<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_memory_detail)
+}
+
+/// Gets the memory usage.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/memory",
+    tag = "system",
+    responses(
+        (status = 200, description = "The memory usage metrics.", body = v1::GetMemoryResponse),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_memory(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.memory.clone()))
+}
+
+/// Gets the details of the memory usage and the activity of the virtual memory.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/memory/detail",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The details of the memory usage and the virtual memory activity.",
+            body = v1::GetMemoryDetailResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_memory_detail(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.memory_detail.clone()))
+}
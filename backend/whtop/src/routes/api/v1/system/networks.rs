@@ -0,0 +1,34 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn networks<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_networks)
+}
+
+/// Gets the network interfaces, with their addresses, link state and traffic.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/networks",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The network interfaces.",
+            body = v1::GetNetworksResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_networks(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.networks.clone()))
+}
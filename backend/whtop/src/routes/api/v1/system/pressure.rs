@@ -0,0 +1,34 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn pressure<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_pressure)
+}
+
+/// Gets how much time tasks were stalled waiting for the CPU, memory or I/O.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/pressure",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The pressure stall information.",
+            body = v1::GetPressureResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_pressure(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.pressure.clone()))
+}
@@ -0,0 +1,176 @@
+use std::{cmp::Reverse, sync::atomic::Ordering};
+
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+use whtop_common::models::api::{
+    v1::{GetProcessesDeltaResponse, GetProcessesQuery, GetProcessesResponse, ProcessInfo},
+    ErrorKind,
+};
+
+use crate::{
+    routes::{
+        api::system::{ProcessDelta, ProcessSnapshot, SystemState},
+        Path, Query, RouteError, RouteResult,
+    },
+    snapshot,
+};
+
+type Snapshot = ProcessSnapshot<u32, ProcessInfo>;
+
+pub fn processes<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_processes)
+}
+
+pub fn process<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_process)
+}
+
+/// Gets the running processes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/processes",
+    tag = "system",
+    params(GetProcessesQuery),
+    responses(
+        (
+            status = 200,
+            description = "The full process list, or the changes since `since` if it was set.",
+            body = v1::ProcessesResponseBody,
+        ),
+        (
+            status = 400,
+            description = "The query parameters are invalid.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_processes(
+    State(state): State<SystemState>,
+    Query(query): Query<GetProcessesQuery>,
+) -> RouteResult<impl IntoResponse> {
+    // The generation only changes while the snapshot is write locked
+    let latest = state.snapshot.read().await;
+    let generation = state.generation.load(Ordering::Acquire);
+    let (current, previous) = {
+        let mut history = state.v1_process_history.write().await;
+        let current = history.snapshot(generation, || create_snapshot(&latest));
+        let previous = query.since.and_then(|since| history.get(since));
+        (current, previous)
+    };
+    drop(latest);
+
+    let response = match (query.since, previous) {
+        (None, _) => ProcessesResponseBody::Full(create_response(generation, &current)),
+        (Some(_), Some(previous)) => {
+            ProcessesResponseBody::Delta(create_delta_response(generation, &previous, &current))
+        }
+        (Some(_), None) => ProcessesResponseBody::Delta(GetProcessesDeltaResponse::Resync(
+            create_response(generation, &current),
+        )),
+    };
+    Ok(Json(response))
+}
+
+/// Gets a single running process.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/processes/{pid}",
+    tag = "system",
+    params(("pid" = u32, Path, description = "The ID of the process.")),
+    responses(
+        (status = 200, description = "The process.", body = v1::ProcessInfo),
+        (
+            status = 400,
+            description = "The process ID is invalid.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+        (
+            status = 404,
+            description = "There is no running process with that ID.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_process(
+    State(state): State<SystemState>,
+    Path(pid): Path<u32>,
+) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    let process = snapshot.process(pid).ok_or_else(|| {
+        RouteError::new(
+            ErrorKind::NotFound,
+            format!("no running process with ID {pid}"),
+        )
+    })?;
+    Ok(Json(process.clone()))
+}
+
+/// The response from getting the running processes.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[schema(as = v1::ProcessesResponseBody)]
+#[serde(untagged)]
+pub enum ProcessesResponseBody {
+    /// The full process list, sent when `since` is not set.
+    #[schema(value_type = v1::GetProcessesResponse)]
+    Full(GetProcessesResponse),
+    /// The changes to the process list, sent when `since` is set.
+    #[schema(value_type = v1::GetProcessesDeltaResponse)]
+    Delta(GetProcessesDeltaResponse),
+}
+
+fn create_snapshot(latest: &snapshot::Snapshot) -> Snapshot {
+    latest
+        .processes
+        .iter()
+        .map(|process| (process.pid, process.clone()))
+        .collect()
+}
+
+fn create_response(generation: u64, snapshot: &Snapshot) -> GetProcessesResponse {
+    let mut processes: Vec<ProcessInfo> = snapshot.values().cloned().collect();
+    processes.sort_unstable_by_key(|process| Reverse(process.memory_bytes));
+    GetProcessesResponse {
+        generation,
+        processes,
+    }
+}
+
+fn create_delta_response(
+    generation: u64,
+    previous: &Snapshot,
+    current: &Snapshot,
+) -> GetProcessesDeltaResponse {
+    let ProcessDelta {
+        added,
+        changed,
+        removed,
+    } = ProcessDelta::between(previous, current);
+    GetProcessesDeltaResponse::Delta {
+        generation,
+        added,
+        changed,
+        removed,
+    }
+}
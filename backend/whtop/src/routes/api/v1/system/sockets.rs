@@ -0,0 +1,35 @@
+use axum::{body::HttpBody, extract::State, response::IntoResponse, routing::MethodRouter, Json};
+
+use crate::routes::{api::system::SystemState, RouteResult};
+
+pub fn sockets<B>() -> MethodRouter<SystemState, B>
+where
+    B: HttpBody + Send + 'static,
+{
+    MethodRouter::new().get(get_sockets)
+}
+
+/// Gets the number of sockets in each state, the listening sockets with the processes which have
+/// them open, and the activity of TCP and UDP.
+#[utoipa::path(
+    get,
+    path = "/api/v1/system/sockets",
+    tag = "system",
+    responses(
+        (
+            status = 200,
+            description = "The sockets.",
+            body = v1::GetSocketsResponse,
+        ),
+        (
+            status = 500,
+            description = "An internal error occurred.",
+            body = ProblemDetails,
+            content_type = "application/problem+json",
+        ),
+    )
+)]
+async fn get_sockets(State(state): State<SystemState>) -> RouteResult<impl IntoResponse> {
+    let snapshot = state.snapshot.read().await;
+    Ok(Json(snapshot.sockets.clone()))
+}
@@ -1,59 +1,170 @@
 use std::{
     error::Error,
     fmt::{Display, Formatter},
+    time::Duration,
 };
 
 use axum::{
-    http::StatusCode,
+    http::{
+        header::{CONTENT_TYPE, RETRY_AFTER},
+        HeaderValue, StatusCode,
+    },
     response::{IntoResponse, Response},
     Json,
 };
-use serde::{Deserialize, Serialize};
+use tracing::error;
+use whtop_common::models::api::{ErrorKind, ProblemDetails};
 
 pub type RouteResult<T> = Result<T, RouteError>;
 
+/// An error returned from a route. These are sent to the client as [`ProblemDetails`].
 #[derive(Debug)]
-pub enum RouteError {
-    InternalError(anyhow::Error),
+pub struct RouteError {
+    kind: ErrorKind,
+    detail: Option<String>,
+    source: Option<anyhow::Error>,
+    retry_after: Option<Duration>,
+}
+
+impl RouteError {
+    /// Creates a new error with a message explaining what went wrong.
+    pub fn new(kind: ErrorKind, detail: impl Into<String>) -> Self {
+        RouteError {
+            kind,
+            detail: Some(detail.into()),
+            source: None,
+            retry_after: None,
+        }
+    }
+
+    /// Tells the client how long to wait before retrying the request with the `retry-after`
+    /// header.
+    pub fn with_retry_after(self, retry_after: Duration) -> Self {
+        RouteError {
+            retry_after: Some(retry_after),
+            ..self
+        }
+    }
+
+    /// The HTTP status code for the error.
+    pub fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.kind.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
 }
 
 impl Display for RouteError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            RouteError::InternalError(_) => write!(f, "internal error"),
+        match &self.detail {
+            Some(detail) => write!(f, "{}: {detail}", self.kind.title()),
+            None => write!(f, "{}", self.kind.title()),
         }
     }
 }
 
 impl Error for RouteError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match self {
-            RouteError::InternalError(err) => Some(&**err),
-        }
+        self.source.as_ref().map(|source| &**source as _)
     }
 }
 
 impl From<anyhow::Error> for RouteError {
     fn from(error: anyhow::Error) -> Self {
-        RouteError::InternalError(error)
+        RouteError {
+            kind: ErrorKind::Internal,
+            detail: Some(format!("{error:#}")),
+            source: Some(error),
+            retry_after: None,
+        }
     }
 }
 
 impl IntoResponse for RouteError {
     fn into_response(self) -> Response {
-        match self {
-            RouteError::InternalError(inner) => {
-                let body = RouteErrorResponseBody::InternalError {
-                    message: format!("{inner:?}"),
-                };
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(body)).into_response()
-            }
+        if let Some(source) = &self.source {
+            error!(?source, "error handling request");
+        }
+
+        let status = self.status();
+        let body = ProblemDetails::new(self.kind, self.detail);
+        let mut response = (status, Json(body)).into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(ProblemDetails::CONTENT_TYPE),
+        );
+        if let Some(retry_after) = self.retry_after {
+            // Round up so clients never retry too early
+            let seconds = retry_after
+                .as_secs()
+                .saturating_add(u64::from(retry_after.subsec_nanos() > 0));
+            response.headers_mut().insert(RETRY_AFTER, seconds.into());
         }
+        response
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "camelCase")]
-enum RouteErrorResponseBody {
-    InternalError { message: String },
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::HttpBody;
+
+    #[tokio::test]
+    async fn test_into_response_problem_details() {
+        // Setup
+        let error = RouteError::new(ErrorKind::NotFound, "no process with ID 1");
+
+        // Execute
+        let response = error.into_response();
+
+        // Assert
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+        assert_eq!(
+            Some(ProblemDetails::CONTENT_TYPE),
+            response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+        );
+        let body = response.into_body().data().await.unwrap().unwrap();
+        let body: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(Some(ErrorKind::NotFound), body.kind());
+        assert_eq!(404, body.status);
+        assert_eq!(Some("no process with ID 1"), body.detail.as_deref());
+    }
+
+    #[test]
+    fn test_into_response_retry_after() {
+        // Setup
+        let rounded = RouteError::new(ErrorKind::TooManyRequests, "slow down")
+            .with_retry_after(Duration::from_millis(1500));
+        let forever = RouteError::new(ErrorKind::TooManyRequests, "slow down")
+            .with_retry_after(Duration::MAX);
+
+        // Execute
+        let rounded = rounded.into_response();
+        let forever = forever.into_response();
+
+        // Assert
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, forever.status());
+        assert_eq!(
+            Some(&HeaderValue::from(2)),
+            rounded.headers().get(RETRY_AFTER)
+        );
+        assert_eq!(
+            Some(&HeaderValue::from(u64::MAX)),
+            forever.headers().get(RETRY_AFTER)
+        );
+    }
+
+    #[test]
+    fn test_anyhow_is_internal() {
+        // Setup
+        let error = anyhow::anyhow!("inner").context("outer");
+
+        // Execute
+        let error = RouteError::from(error);
+
+        // Assert
+        assert_eq!(ErrorKind::Internal, error.kind);
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, error.status());
+    }
 }
@@ -0,0 +1,58 @@
+use axum::{
+    async_trait,
+    extract::{rejection::PathRejection, rejection::QueryRejection, FromRequestParts},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+use whtop_common::models::api::ErrorKind;
+
+use super::RouteError;
+
+/// Extracts the query parameters of a request. This is the same as [`axum::extract::Query`], but
+/// rejects invalid parameters with a [`RouteError`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = RouteError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) = axum::extract::Query::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection: QueryRejection| {
+                RouteError::new(ErrorKind::BadRequest, rejection.body_text())
+            })?;
+        Ok(Query(value))
+    }
+}
+
+/// Extracts the path parameters of a request. This is the same as [`axum::extract::Path`], but
+/// rejects invalid parameters with a [`RouteError`].
+#[derive(Clone, Copy, Debug)]
+pub struct Path<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = RouteError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Path(value) = axum::extract::Path::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection: PathRejection| match rejection {
+                PathRejection::MissingPathParams(_) => {
+                    RouteError::from(anyhow::Error::new(rejection))
+                }
+                rejection => RouteError::new(ErrorKind::BadRequest, rejection.body_text()),
+            })?;
+        Ok(Path(value))
+    }
+}
@@ -0,0 +1,533 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use sysinfo::{
+    Cpu, CpuExt, DiskExt, NetworkData, NetworkExt, NetworksExt, OpenFile, OpenFileKind, Pid,
+    PidExt, Process, ProcessExt, RefreshError, RefreshKind, SocketStats, System, SystemExt,
+    TcpState,
+};
+use whtop_common::models::api::v1::{
+    CpuInfo, CpuUsageBreakdown, DeviceIo, DiskInfo, Duplex, GetCpuResponse, GetDiagnosticsResponse,
+    GetDisksResponse, GetIoResponse, GetLoadResponse, GetMemoryDetailResponse, GetMemoryResponse,
+    GetNetworksResponse, GetPressureResponse, GetSocketsResponse, GlobalCpuInfo, ListeningSocket,
+    LoadAverage, NetworkInterface, OpenFiles, OperationalState, PressureStall, ProcessInfo,
+    RefreshArea, RefreshErrorInfo, ResourcePressure, SocketProcess, SocketProtocol, TcpActivity,
+    TcpStateCounts, UdpActivity,
+};
+
+/// The information about the system served by the API at one point in time. Routes only read
+/// snapshots, so they can be served from a recording the same as from the live system.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub cpu: GetCpuResponse,
+    pub memory: GetMemoryResponse,
+    /// The details of the memory usage. Recordings made before they were collected have zeros.
+    #[serde(default)]
+    pub memory_detail: GetMemoryDetailResponse,
+    /// The load of the system. Recordings made before it was collected have zeros.
+    #[serde(default)]
+    pub load: GetLoadResponse,
+    /// The pressure stall information. Recordings made before it was collected have none.
+    #[serde(default)]
+    pub pressure: GetPressureResponse,
+    /// The mounted file systems. Recordings made before they were collected have none.
+    #[serde(default)]
+    pub disks: GetDisksResponse,
+    /// The I/O activity of the block devices. Recordings made before it was collected have none.
+    #[serde(default)]
+    pub io: GetIoResponse,
+    /// The network interfaces. Recordings made before they were collected have none.
+    #[serde(default)]
+    pub networks: GetNetworksResponse,
+    /// The sockets. Recordings made before they were collected have none.
+    #[serde(default)]
+    pub sockets: GetSocketsResponse,
+    /// The running processes, in no particular order.
+    pub processes: Vec<ProcessInfo>,
+    /// The errors of the refresh. Recordings made before errors were collected have none.
+    #[serde(default)]
+    pub diagnostics: GetDiagnosticsResponse,
+}
+
+impl Snapshot {
+    /// Captures the information from a refreshed system.
+    pub fn capture(system: &System) -> Self {
+        let cpus: Vec<CpuInfo> = system.cpus().iter().map(create_cpu_info).collect();
+        let frequency_mhz = cpus
+            .iter()
+            .map(|cpu| cpu.inner.frequency_mhz)
+            .sum::<u64>()
+            .checked_div(cpus.len() as u64)
+            .unwrap_or(0);
+        let cpu = GetCpuResponse {
+            global: GlobalCpuInfo {
+                usage_percent: system.global_cpu_info().cpu_usage(),
+                frequency_mhz,
+                breakdown: create_cpu_usage_breakdown(system.global_cpu_info()),
+            },
+            cpus,
+        };
+
+        // sysinfo reports memory in kilobytes, except for the size of a huge page
+        let memory = GetMemoryResponse {
+            total_bytes: system.total_memory().saturating_mul(1000),
+            used_bytes: system.used_memory().saturating_mul(1000),
+            free_bytes: system.free_memory().saturating_mul(1000),
+            available_bytes: system.available_memory().saturating_mul(1000),
+        };
+        let details = system.memory_details();
+        let activity = system.memory_activity();
+        let memory_detail = GetMemoryDetailResponse {
+            dirty_bytes: details.dirty.saturating_mul(1000),
+            writeback_bytes: details.writeback.saturating_mul(1000),
+            shared_bytes: details.shared.saturating_mul(1000),
+            committed_bytes: details.committed.saturating_mul(1000),
+            huge_pages_total: details.huge_pages_total,
+            huge_pages_free: details.huge_pages_free,
+            huge_page_size_bytes: details.huge_page_size_bytes,
+            page_faults_per_second: activity.page_faults,
+            major_page_faults_per_second: activity.major_page_faults,
+            pages_swapped_in_per_second: activity.pages_swapped_in,
+            pages_swapped_out_per_second: activity.pages_swapped_out,
+            pages_scanned_per_second: activity.pages_scanned,
+            pages_stolen_per_second: activity.pages_stolen,
+            oom_kills: activity.oom_kills,
+        };
+
+        let load_average = system.load_average();
+        let activity = system.kernel_activity();
+        let load = GetLoadResponse {
+            load_average: LoadAverage {
+                one_minute: load_average.one,
+                five_minutes: load_average.five,
+                fifteen_minutes: load_average.fifteen,
+            },
+            context_switches_per_second: activity.context_switches,
+            interrupts_per_second: activity.interrupts,
+            forks_per_second: activity.forks,
+            running_processes: activity.running_processes,
+            blocked_processes: activity.blocked_processes,
+        };
+
+        let pressure = system.pressure();
+        let pressure = GetPressureResponse {
+            cpu: pressure.cpu.as_ref().map(create_resource_pressure),
+            memory: pressure.memory.as_ref().map(create_resource_pressure),
+            io: pressure.io.as_ref().map(create_resource_pressure),
+        };
+
+        let disks = GetDisksResponse {
+            disks: system.disks().iter().map(create_disk_info).collect(),
+        };
+
+        let io = GetIoResponse {
+            devices: system.disks_io().iter().map(create_device_io).collect(),
+        };
+
+        let mut interfaces: Vec<NetworkInterface> = system
+            .networks()
+            .iter()
+            .map(|(name, network)| create_network_interface(name, network))
+            .collect();
+        interfaces.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        let networks = GetNetworksResponse { interfaces };
+
+        let sockets = create_sockets_response(system, system.sockets());
+
+        let cpu_count = system.cpus().len().max(1) as f32;
+        let processes = system
+            .processes()
+            .iter()
+            .map(|process| create_process_info(process, cpu_count))
+            .collect();
+
+        let diagnostics = GetDiagnosticsResponse {
+            errors: system
+                .refresh_errors()
+                .iter()
+                .map(create_refresh_error_info)
+                .collect(),
+        };
+
+        Snapshot {
+            cpu,
+            memory,
+            memory_detail,
+            load,
+            pressure,
+            disks,
+            io,
+            networks,
+            sockets,
+            processes,
+            diagnostics,
+        }
+    }
+
+    /// The number of CPUs, or 1 if they weren't collected.
+    pub fn cpu_count(&self) -> usize {
+        self.cpu.cpus.len().max(1)
+    }
+
+    /// Gets a running process by its ID.
+    pub fn process(&self, pid: u32) -> Option<&ProcessInfo> {
+        self.processes.iter().find(|process| process.pid == pid)
+    }
+}
+
+fn create_cpu_info(cpu: &Cpu) -> CpuInfo {
+    CpuInfo {
+        name: cpu.name().into(),
+        inner: GlobalCpuInfo {
+            usage_percent: cpu.cpu_usage(),
+            frequency_mhz: cpu.frequency(),
+            breakdown: create_cpu_usage_breakdown(cpu),
+        },
+    }
+}
+
+fn create_cpu_usage_breakdown(cpu: &Cpu) -> CpuUsageBreakdown {
+    let usage = cpu.usage_breakdown();
+    CpuUsageBreakdown {
+        user_percent: usage.user,
+        nice_percent: usage.nice,
+        system_percent: usage.system,
+        idle_percent: usage.idle,
+        iowait_percent: usage.iowait,
+        irq_percent: usage.irq,
+        softirq_percent: usage.softirq,
+        steal_percent: usage.steal,
+    }
+}
+
+fn create_process_info((pid, process): (&Pid, &Process), cpu_count: f32) -> ProcessInfo {
+    // sysinfo reports memory in kilobytes
+    ProcessInfo {
+        pid: pid.as_u32(),
+        parent_pid: process.parent().map(Pid::as_u32),
+        name: process.name().into(),
+        cpu_percent: process.cpu_usage() / cpu_count,
+        memory_bytes: process.memory().saturating_mul(1000),
+        virtual_memory_bytes: process.virtual_memory().saturating_mul(1000),
+        run_time_seconds: process.run_time(),
+        open_files: process
+            .open_files()
+            .map(|files| create_open_files(files, process)),
+    }
+}
+
+fn create_open_files(files: &[OpenFile], process: &Process) -> OpenFiles {
+    let mut open_files = OpenFiles {
+        count: files.len(),
+        ..OpenFiles::default()
+    };
+    for file in files {
+        let count = match file.kind {
+            OpenFileKind::File => &mut open_files.files,
+            OpenFileKind::Socket => &mut open_files.sockets,
+            OpenFileKind::Pipe => &mut open_files.pipes,
+            OpenFileKind::AnonInode => &mut open_files.anon_inodes,
+            OpenFileKind::Other => &mut open_files.other,
+        };
+        *count += 1;
+    }
+    if let Some(limits) = process.limits() {
+        open_files.soft_limit = limits.open_files.soft;
+        open_files.hard_limit = limits.open_files.hard;
+    }
+    open_files
+}
+
+fn create_network_interface(name: &str, network: &NetworkData) -> NetworkInterface {
+    let received = network.received_per_second();
+    let transmitted = network.transmitted_per_second();
+    let duplex = match network.duplex() {
+        sysinfo::Duplex::Unknown => Duplex::Unknown,
+        sysinfo::Duplex::Half => Duplex::Half,
+        sysinfo::Duplex::Full => Duplex::Full,
+    };
+    let utilization_percent =
+        link_utilization_percent(duplex, received, transmitted, network.speed());
+    let mac_address = network.mac_address();
+    NetworkInterface {
+        name: name.to_owned(),
+        mac_address: (!mac_address.is_unspecified()).then(|| mac_address.to_string()),
+        ip_addresses: network
+            .ip_networks()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        mtu_bytes: network.mtu(),
+        operational_state: match network.operational_state() {
+            sysinfo::OperationalState::Unknown => OperationalState::Unknown,
+            sysinfo::OperationalState::NotPresent => OperationalState::NotPresent,
+            sysinfo::OperationalState::Down => OperationalState::Down,
+            sysinfo::OperationalState::LowerLayerDown => OperationalState::LowerLayerDown,
+            sysinfo::OperationalState::Testing => OperationalState::Testing,
+            sysinfo::OperationalState::Dormant => OperationalState::Dormant,
+            sysinfo::OperationalState::Up => OperationalState::Up,
+        },
+        carrier: network.carrier(),
+        duplex,
+        speed_mbps: network.speed(),
+        received_bytes_per_second: received,
+        transmitted_bytes_per_second: transmitted,
+        total_received_bytes: network.total_received(),
+        total_transmitted_bytes: network.total_transmitted(),
+        utilization_percent,
+    }
+}
+
+/// The percentage of the link speed, in Mbit/s, used by the traffic, in bytes per second.
+fn link_utilization_percent(
+    duplex: Duplex,
+    received: f64,
+    transmitted: f64,
+    speed_mbps: Option<u64>,
+) -> Option<f64> {
+    // The two directions share the speed of a half duplex link
+    let used_bits = match duplex {
+        Duplex::Half => (received + transmitted) * 8.0,
+        Duplex::Full | Duplex::Unknown => received.max(transmitted) * 8.0,
+    };
+    speed_mbps
+        .filter(|&speed| speed > 0)
+        .map(|speed| (used_bits / (speed as f64 * 1e6) * 100.0).min(100.0))
+}
+
+fn create_sockets_response(system: &System, sockets: &SocketStats) -> GetSocketsResponse {
+    let mut tcp_states = TcpStateCounts::default();
+    for (&state, &count) in &sockets.tcp_states {
+        let state_count = match state {
+            TcpState::Established => &mut tcp_states.established,
+            TcpState::SynSent => &mut tcp_states.syn_sent,
+            TcpState::SynReceived => &mut tcp_states.syn_received,
+            TcpState::FinWait1 => &mut tcp_states.fin_wait1,
+            TcpState::FinWait2 => &mut tcp_states.fin_wait2,
+            TcpState::TimeWait => &mut tcp_states.time_wait,
+            TcpState::Close => &mut tcp_states.close,
+            TcpState::CloseWait => &mut tcp_states.close_wait,
+            TcpState::LastAck => &mut tcp_states.last_ack,
+            TcpState::Listen => &mut tcp_states.listen,
+            TcpState::Closing => &mut tcp_states.closing,
+            TcpState::NewSynReceived => &mut tcp_states.new_syn_received,
+        };
+        *state_count = count;
+    }
+    let listening = sockets
+        .listening
+        .iter()
+        .map(|socket| ListeningSocket {
+            protocol: match socket.protocol {
+                sysinfo::SocketProtocol::Tcp => SocketProtocol::Tcp,
+                sysinfo::SocketProtocol::Udp => SocketProtocol::Udp,
+                sysinfo::SocketProtocol::Unix => SocketProtocol::Unix,
+            },
+            address: socket.address.map(|address| address.ip().to_string()),
+            port: socket.address.map(|address| address.port()),
+            path: socket.path.clone(),
+            processes: socket
+                .pids
+                .iter()
+                .map(|&pid| SocketProcess {
+                    pid: pid.as_u32(),
+                    name: system.process(pid).map(|process| process.name().to_owned()),
+                })
+                .collect(),
+        })
+        .collect();
+    let tcp = &sockets.tcp;
+    let udp = &sockets.udp;
+    GetSocketsResponse {
+        tcp_states,
+        udp_sockets: sockets.udp_sockets,
+        unix_sockets: sockets.unix_sockets,
+        listening,
+        tcp: TcpActivity {
+            active_opens_per_second: tcp.active_opens,
+            passive_opens_per_second: tcp.passive_opens,
+            failed_connection_attempts_per_second: tcp.failed_connection_attempts,
+            established_resets_per_second: tcp.established_resets,
+            segments_sent_per_second: tcp.segments_sent,
+            segments_retransmitted_per_second: tcp.segments_retransmitted,
+            retransmitted_percent: if tcp.segments_sent > 0.0 {
+                (tcp.segments_retransmitted / tcp.segments_sent * 100.0).min(100.0)
+            } else {
+                0.0
+            },
+            receive_errors_per_second: tcp.receive_errors,
+            resets_sent_per_second: tcp.resets_sent,
+        },
+        udp: UdpActivity {
+            datagrams_received_per_second: udp.datagrams_received,
+            datagrams_sent_per_second: udp.datagrams_sent,
+            no_port_per_second: udp.no_port,
+            receive_errors_per_second: udp.receive_errors,
+            receive_buffer_errors_per_second: udp.receive_buffer_errors,
+            send_buffer_errors_per_second: udp.send_buffer_errors,
+        },
+    }
+}
+
+fn create_refresh_error_info(error: &RefreshError) -> RefreshErrorInfo {
+    let area = match error.area {
+        sysinfo::RefreshArea::Cpu => RefreshArea::Cpu,
+        sysinfo::RefreshArea::Memory => RefreshArea::Memory,
+        sysinfo::RefreshArea::Processes => RefreshArea::Processes,
+        sysinfo::RefreshArea::Disks => RefreshArea::Disks,
+        sysinfo::RefreshArea::Networks => RefreshArea::Networks,
+        sysinfo::RefreshArea::Users => RefreshArea::Users,
+        sysinfo::RefreshArea::System => RefreshArea::System,
+        sysinfo::RefreshArea::Pressure => RefreshArea::Pressure,
+        sysinfo::RefreshArea::Sockets => RefreshArea::Sockets,
+        sysinfo::RefreshArea::DisksIo => RefreshArea::DisksIo,
+        sysinfo::RefreshArea::MemoryActivity => RefreshArea::MemoryActivity,
+    };
+    RefreshErrorInfo {
+        area,
+        path: error.path.to_string_lossy().into_owned(),
+        pid: error.pid.map(Pid::as_u32),
+        errno: error.raw_os_error,
+        message: error.to_string(),
+    }
+}
+
+fn create_disk_info(disk: &sysinfo::Disk) -> DiskInfo {
+    DiskInfo {
+        name: disk.name().to_string_lossy().into_owned(),
+        mount_point: disk.mount_point().to_string_lossy().into_owned(),
+        file_system: String::from_utf8_lossy(disk.file_system()).into_owned(),
+        mount_options: disk.mount_options().to_vec(),
+        is_read_only: disk.is_read_only(),
+        is_removable: disk.is_removable(),
+        total_bytes: disk.total_space(),
+        available_bytes: disk.available_space(),
+        total_inodes: disk.total_inodes(),
+        available_inodes: disk.available_inodes(),
+        is_unreachable: disk.is_unreachable(),
+    }
+}
+
+fn create_device_io(io: &sysinfo::DiskIo) -> DeviceIo {
+    DeviceIo {
+        name: io.name.clone(),
+        mount_points: io
+            .mount_points
+            .iter()
+            .map(|mount_point| mount_point.to_string_lossy().into_owned())
+            .collect(),
+        reads_per_second: io.reads,
+        writes_per_second: io.writes,
+        read_bytes_per_second: io.read_bytes,
+        written_bytes_per_second: io.written_bytes,
+        await_milliseconds: io.await_time,
+        utilization_percent: io.utilization,
+    }
+}
+
+fn create_resource_pressure(pressure: &sysinfo::ResourcePressure) -> ResourcePressure {
+    ResourcePressure {
+        some: create_pressure_stall(&pressure.some),
+        full: pressure.full.as_ref().map(create_pressure_stall),
+    }
+}
+
+fn create_pressure_stall(stall: &sysinfo::PressureStall) -> PressureStall {
+    PressureStall {
+        avg10_percent: stall.avg10,
+        avg60_percent: stall.avg60,
+        avg300_percent: stall.avg300,
+        total_microseconds: stall.total,
+    }
+}
+
+/// Produces the snapshots served by the API.
+pub trait SnapshotSource: Send + Debug {
+    /// Gets the latest snapshot, or `None` if it hasn't changed since the previous call.
+    fn next_snapshot(&mut self) -> Option<Snapshot>;
+}
+
+/// Takes snapshots of the live system.
+#[derive(Debug)]
+pub struct LiveSource {
+    system: System,
+    refresh_kind: RefreshKind,
+}
+
+impl LiveSource {
+    /// Creates a source which refreshes the given information about the system before taking
+    /// each snapshot.
+    pub fn new(system: System, refresh_kind: RefreshKind) -> Self {
+        LiveSource {
+            system,
+            refresh_kind,
+        }
+    }
+}
+
+impl SnapshotSource for LiveSource {
+    fn next_snapshot(&mut self) -> Option<Snapshot> {
+        self.system.refresh_specifics(self.refresh_kind);
+        Some(Snapshot::capture(&self.system))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        // Setup
+        let mut system = System::new_all();
+        system.refresh_all();
+
+        // Execute
+        let snapshot = Snapshot::capture(&system);
+
+        // Assert
+        assert_eq!(system.cpus().len(), snapshot.cpu.cpus.len());
+        assert_eq!(system.total_memory() * 1000, snapshot.memory.total_bytes);
+        let pid = std::process::id();
+        assert_eq!(Some(pid), snapshot.process(pid).map(|process| process.pid));
+    }
+
+    #[test]
+    fn test_link_utilization_percent() {
+        // Setup: 100 Mbit/s is 12.5 MB/s
+        let speed = Some(100);
+
+        // Execute
+        let half = link_utilization_percent(Duplex::Half, 2_500_000.0, 1_250_000.0, speed);
+        let full = link_utilization_percent(Duplex::Full, 2_500_000.0, 1_250_000.0, speed);
+        let unknown = link_utilization_percent(Duplex::Unknown, 1_250_000.0, 2_500_000.0, speed);
+        let idle = link_utilization_percent(Duplex::Full, 0.0, 0.0, speed);
+
+        // Assert
+        assert_eq!(Some(30.0), half);
+        assert_eq!(Some(20.0), full);
+        assert_eq!(Some(20.0), unknown);
+        assert_eq!(Some(0.0), idle);
+    }
+
+    #[test]
+    fn test_link_utilization_percent_is_capped() {
+        // Execute
+        let half = link_utilization_percent(Duplex::Half, 10_000_000.0, 10_000_000.0, Some(100));
+        let full = link_utilization_percent(Duplex::Full, 20_000_000.0, 0.0, Some(100));
+
+        // Assert
+        assert_eq!(Some(100.0), half);
+        assert_eq!(Some(100.0), full);
+    }
+
+    #[test]
+    fn test_link_utilization_percent_without_speed() {
+        // Execute
+        let unknown = link_utilization_percent(Duplex::Full, 1_000.0, 1_000.0, None);
+        let zero = link_utilization_percent(Duplex::Full, 1_000.0, 1_000.0, Some(0));
+
+        // Assert
+        assert_eq!(None, unknown);
+        assert_eq!(None, zero);
+    }
+}
@@ -1,18 +1,31 @@
+use std::net::SocketAddr;
+
 use anyhow::Context;
-use axum::{body::HttpBody, Router, Server};
+use axum::{body::HttpBody, BoxError, Router, Server};
+use clap::Parser;
 use tower::ServiceBuilder;
 use tower_http::{
     compression::{predicate::SizeAbove, CompressionLayer},
+    limit::RequestBodyLimitLayer,
     trace::TraceLayer,
 };
 use tracing::{debug, info};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-use crate::config::AppConfig;
+use crate::{
+    cli::{Cli, Command, ServeArgs},
+    config::AppConfig,
+    layers::{RateLimitLayer, RateLimitOptions},
+    recording::{Recording, ReplaySource},
+    snapshot::SnapshotSource,
+};
 
 const DEFAULT_ENV_FILTER: &str = "info";
 
 pub async fn start() -> anyhow::Result<()> {
+    // Parse the command line before tracing so `--help` isn't mixed with logs
+    let cli = Cli::parse();
+
     // Setup tracing
     tracing_subscriber::registry()
         .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| DEFAULT_ENV_FILTER.into()))
@@ -23,28 +36,65 @@ pub async fn start() -> anyhow::Result<()> {
     let config = load_config()?;
     debug!(?config, "config loaded");
 
+    match cli.command() {
+        Command::Serve(args) => serve(config, args).await,
+        Command::Record(args) => {
+            let source = crate::modules::live_source(&config);
+            crate::recording::record(&config, source, &args.out).await
+        }
+    }
+}
+
+async fn serve(mut config: AppConfig, args: ServeArgs) -> anyhow::Result<()> {
     // Create app
-    let app = build_app(&config).await?;
+    let app = match args.replay {
+        Some(path) => {
+            let recording = Recording::load(&path)?;
+            info!("replaying {} at {}x speed", path.display(), args.speed);
+            // Refresh as often as the recording changes
+            config.refresh_rate_secs = recording.interval().as_secs_f32() / args.speed as f32;
+            build_app(&config, ReplaySource::new(recording, args.speed)).await?
+        }
+        None => {
+            let source = crate::modules::live_source(&config);
+            build_app(&config, source).await?
+        }
+    };
     info!("listening on {}", config.address);
     Server::try_bind(&config.address)
         .context("error binding to address")?
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .context("error running server")
 }
 
 fn load_config() -> anyhow::Result<AppConfig> {
-    envy::prefixed("WHTOP_")
+    let config: AppConfig = envy::prefixed("WHTOP_")
         .from_env()
-        .context("error reading config")
+        .context("error reading config")?;
+    config.validate().context("invalid config")?;
+    Ok(config)
 }
 
-async fn build_app<B>(config: &AppConfig) -> anyhow::Result<Router<(), B>>
+async fn build_app<B>(
+    config: &AppConfig,
+    source: impl SnapshotSource + 'static,
+) -> anyhow::Result<Router<(), B>>
 where
     B: HttpBody + Send + 'static,
+    B::Error: Into<BoxError>,
 {
     // Backend API
-    let api_router = Router::new().nest("/system", crate::modules::system(config));
+    let mut api_router = Router::new()
+        .route("/openapi.json", crate::routes::api::openapi())
+        .merge(crate::modules::system(config, source));
+    if config.rate_limit_per_sec > 0.0 {
+        api_router = api_router.layer(RateLimitLayer::new(RateLimitOptions {
+            requests_per_sec: config.rate_limit_per_sec,
+            burst: config.rate_limit_burst,
+        }));
+    }
+    let api_router = api_router.layer(RequestBodyLimitLayer::new(config.max_request_body_bytes));
 
     // Frontend
     let frontend_router = crate::modules::frontend(config);
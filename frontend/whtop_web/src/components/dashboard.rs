@@ -1,9 +1,21 @@
 mod cpu;
 mod dashboard;
+mod disks;
+mod io;
+mod load;
 mod memory;
+mod networks;
+mod pressure;
 mod processes;
+mod sockets;
 
 pub use cpu::*;
 pub use dashboard::*;
+pub use disks::*;
+pub use io::*;
+pub use load::*;
 pub use memory::*;
+pub use networks::*;
+pub use pressure::*;
 pub use processes::*;
+pub use sockets::*;
@@ -1,4 +1,5 @@
-use crate::components::Meter;
+use crate::components::{MeterSegment, StackedMeter};
+use whtop_common::models::api::v1::CpuUsageBreakdown;
 use yew::prelude::*;
 
 #[derive(Clone, PartialEq, Properties)]
@@ -8,11 +9,13 @@ pub struct CpuUsageProps {
     #[prop_or_default]
     pub cpu_frequency: Option<u64>,
     pub cpu_usage: f32,
+    pub cpu_breakdown: CpuUsageBreakdown,
 }
 
 #[function_component(CpuUsage)]
 pub fn cpu_usage(props: &CpuUsageProps) -> Html {
-    let progress = props.cpu_usage / 100.0;
+    let segments = breakdown_segments(&props.cpu_breakdown, props.cpu_usage);
+    let breakdown = &props.cpu_breakdown;
     html! {
         <div class={"cpu-usage"}>
             {
@@ -34,11 +37,44 @@ pub fn cpu_usage(props: &CpuUsageProps) -> Html {
                 }
             }
             <div class={"cpu-usage-bar"}>
-                <Meter
-                    progress={progress as f64}
-                />
+                <StackedMeter {segments} />
+            </div>
+            <div class={"cpu-usage-usage"}>
+                {format!("{:.2}%", props.cpu_usage)}
+                {
+                    if breakdown.iowait_percent > 0.0 || breakdown.steal_percent > 0.0 {
+                        html! {
+                            <span class={"cpu-usage-waiting"}>
+                                {format!(
+                                    " (iowait {:.2}%, steal {:.2}%)",
+                                    breakdown.iowait_percent, breakdown.steal_percent,
+                                )}
+                            </span>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
             </div>
-            <div class={"cpu-usage-usage"}>{format!("{:.2}%", props.cpu_usage)}</div>
         </div>
     }
 }
+
+/// Splits the usage into the segments of the meter. Without a breakdown, which is only reported on
+/// Linux, the whole usage is shown as a single segment. The time waiting for I/O is idle time, not
+/// part of the usage, so it is only shown in the text.
+fn breakdown_segments(breakdown: &CpuUsageBreakdown, usage: f32) -> Vec<MeterSegment> {
+    let segment = |name, percent: f32| MeterSegment {
+        name,
+        progress: percent as f64 / 100.0,
+    };
+    if *breakdown == CpuUsageBreakdown::default() {
+        return vec![segment("user", usage)];
+    }
+    vec![
+        segment("user", breakdown.user_percent + breakdown.nice_percent),
+        segment("system", breakdown.system_percent),
+        segment("irq", breakdown.irq_percent + breakdown.softirq_percent),
+        segment("steal", breakdown.steal_percent),
+    ]
+}
@@ -1,13 +1,24 @@
 use crate::{
-    components::dashboard::{CpuUsage, MemoryUsage, ProcessList},
+    components::dashboard::{
+        CpuUsage, DiskIoList, DiskList, LoadSummary, MemoryUsage, NetworkList, PressureSummary,
+        ProcessList, SocketList,
+    },
     contexts::HttpClient,
 };
 use anyhow::Context as _;
 use futures::{join, FutureExt};
 use gloo::net::http::Request;
 use serde::de::DeserializeOwned;
-use std::rc::Rc;
-use whtop_common::models::api::{GetCpuResponse, GetMemoryResponse, GetProcessesResponse};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+use whtop_common::models::api::v1::{
+    GetCpuResponse, GetDisksResponse, GetIoResponse, GetLoadResponse, GetMemoryResponse,
+    GetNetworksResponse, GetPressureResponse, GetProcessesDeltaResponse, GetProcessesResponse,
+    GetSocketsResponse, ProcessInfo,
+};
 use yew::prelude::*;
 use yew_hooks::use_interval;
 
@@ -16,6 +27,12 @@ struct DashboardState {
     errors: Vec<Rc<anyhow::Error>>,
     memory_stats: GetMemoryResponse,
     cpu_stats: GetCpuResponse,
+    load_stats: GetLoadResponse,
+    pressure_stats: GetPressureResponse,
+    disk_stats: GetDisksResponse,
+    io_stats: GetIoResponse,
+    network_stats: GetNetworksResponse,
+    socket_stats: GetSocketsResponse,
     process_stats: GetProcessesResponse,
 }
 
@@ -68,9 +85,14 @@ fn render_state(state: &DashboardState) -> Html {
                                     for state.errors.iter().map(|error| {
                                         html! {
                                             <>
-                                                <pre>
-                                                    {format!("{error:?}")}
-                                                </pre>
+                                                <p>{error.to_string()}</p>
+                                                <ul>
+                                                    {
+                                                        for error.chain().skip(1).map(|cause| {
+                                                            html! { <li>{cause.to_string()}</li> }
+                                                        })
+                                                    }
+                                                </ul>
                                                 <hr />
                                             </>
                                         }
@@ -86,25 +108,51 @@ fn render_state(state: &DashboardState) -> Html {
             <h2>{"Memory"}</h2>
             <section class={"memory"}>
                 <MemoryUsage
-                    memory_total={state.memory_stats.total}
-                    memory_used={state.memory_stats.used}
-                    memory_available={state.memory_stats.available}
+                    memory_total={state.memory_stats.total_bytes}
+                    memory_used={state.memory_stats.used_bytes}
+                    memory_available={state.memory_stats.available_bytes}
                 />
             </section>
+            <h2>{"Load"}</h2>
+            <section class={"load"}>
+                <LoadSummary load={state.load_stats.clone()} />
+            </section>
+            <h2>{"Pressure"}</h2>
+            <section class={"pressure"}>
+                <PressureSummary pressure={state.pressure_stats.clone()} />
+            </section>
+            <h2>{"Disks"}</h2>
+            <section class={"disks"}>
+                <DiskList disks={state.disk_stats.clone()} />
+            </section>
+            <h2>{"Disk I/O"}</h2>
+            <section class={"io"}>
+                <DiskIoList io={state.io_stats.clone()} />
+            </section>
+            <h2>{"Network"}</h2>
+            <section class={"networks"}>
+                <NetworkList networks={state.network_stats.clone()} />
+            </section>
+            <h2>{"Sockets"}</h2>
+            <section class={"sockets"}>
+                <SocketList sockets={state.socket_stats.clone()} />
+            </section>
             <h2>{"CPU"}</h2>
             <section class={"cpu"}>
                 <CpuUsage
                     cpu_name={"Average"}
-                    cpu_usage={state.cpu_stats.global.usage}
-                    cpu_frequency={state.cpu_stats.global.frequency}
+                    cpu_usage={state.cpu_stats.global.usage_percent}
+                    cpu_frequency={state.cpu_stats.global.frequency_mhz}
+                    cpu_breakdown={state.cpu_stats.global.breakdown.clone()}
                 />
                 {
                     for state.cpu_stats.cpus.iter().map(|cpu| {
                         html! {
                             <CpuUsage
                                 cpu_name={cpu.name.clone()}
-                                cpu_usage={cpu.inner.usage}
-                                cpu_frequency={cpu.inner.frequency}
+                                cpu_usage={cpu.inner.usage_percent}
+                                cpu_frequency={cpu.inner.frequency_mhz}
+                                cpu_breakdown={cpu.inner.breakdown.clone()}
                             />
                         }
                     })
@@ -114,81 +162,88 @@ fn render_state(state: &DashboardState) -> Html {
             <section class={"processes"}>
                 <ProcessList
                     process_list={state.process_stats.processes.clone()}
-                    total_memory={state.memory_stats.total}
+                    total_memory={state.memory_stats.total_bytes}
                 />
             </section>
         </main>
     }
 }
 
+const BASE_URL: &str = "/api/v1/system";
+
 async fn update_state(client: HttpClient, last_state: Option<&DashboardState>) -> DashboardState {
     // Fetch stats
-    const BASE_URL: &str = "/api/system";
     let memory_url = format!("{BASE_URL}/memory");
     let cpu_url = format!("{BASE_URL}/cpu");
-    let processes_url = format!("{BASE_URL}/processes");
-    let (memory_stats, cpu_stats, process_stats) = join!(
+    let load_url = format!("{BASE_URL}/load");
+    let pressure_url = format!("{BASE_URL}/pressure");
+    let disk_url = format!("{BASE_URL}/disks");
+    let io_url = format!("{BASE_URL}/io");
+    let network_url = format!("{BASE_URL}/networks");
+    let socket_url = format!("{BASE_URL}/sockets");
+    let prev_process_stats = last_state.map(|state| &state.process_stats);
+    let (
+        memory_stats,
+        cpu_stats,
+        load_stats,
+        pressure_stats,
+        disk_stats,
+        io_stats,
+        network_stats,
+        socket_stats,
+        process_stats,
+    ) = join!(
         get_stats(client.clone(), &memory_url)
             .map(|stats| stats.context("failed to get memory stats")),
         get_stats(client.clone(), &cpu_url).map(|stats| stats.context("failed to get CPU stats")),
-        get_stats(client.clone(), &processes_url)
+        get_stats(client.clone(), &load_url).map(|stats| stats.context("failed to get load stats")),
+        get_stats(client.clone(), &pressure_url)
+            .map(|stats| stats.context("failed to get pressure stats")),
+        get_stats(client.clone(), &disk_url).map(|stats| stats.context("failed to get disk stats")),
+        get_stats(client.clone(), &io_url).map(|stats| stats.context("failed to get I/O stats")),
+        get_stats(client.clone(), &network_url)
+            .map(|stats| stats.context("failed to get network stats")),
+        get_stats(client.clone(), &socket_url)
+            .map(|stats| stats.context("failed to get socket stats")),
+        get_process_stats(client.clone(), prev_process_stats)
             .map(|stats| stats.context("failed to get process stats")),
     );
 
-    // Update CPU global frequency
-    let cpu_stats = cpu_stats.map(|mut stats: GetCpuResponse| {
-        stats.global.frequency = stats
-            .cpus
-            .iter()
-            .map(|cpu| cpu.inner.frequency)
-            .sum::<u64>()
-            .checked_div(stats.cpus.len() as u64)
-            .unwrap_or(0);
-        stats
-    });
-
-    let (prev_memory_stats, prev_cpu_stats, prev_process_stats) = match &last_state {
-        Some(DashboardState {
-            memory_stats,
-            cpu_stats,
-            process_stats,
-            ..
-        }) => (Some(memory_stats), Some(cpu_stats), Some(process_stats)),
-        _ => (None, None, None),
-    };
-
+    // Keeps the previous stats, if any, when they couldn't be fetched
     macro_rules! try_stats {
-        ($result:expr, $else:expr, $errors:expr) => {
+        ($result:ident, $errors:expr) => {
             match $result {
                 Ok(stats) => stats,
                 Err(error) => {
                     $errors.push(Rc::new(error));
-                    $else
+                    last_state
+                        .map(|state| state.$result.clone())
+                        .unwrap_or_default()
                 }
             }
         };
     }
-    let mut errors = Vec::with_capacity(3);
-    let memory_stats = try_stats!(
-        memory_stats,
-        prev_memory_stats.cloned().unwrap_or_default(),
-        errors
-    );
-    let cpu_stats = try_stats!(
-        cpu_stats,
-        prev_cpu_stats.cloned().unwrap_or_default(),
-        errors
-    );
-    let process_stats = try_stats!(
-        process_stats,
-        prev_process_stats.cloned().unwrap_or_default(),
-        errors
-    );
+    let mut errors = Vec::with_capacity(9);
+    let memory_stats = try_stats!(memory_stats, errors);
+    let cpu_stats = try_stats!(cpu_stats, errors);
+    let load_stats = try_stats!(load_stats, errors);
+    let pressure_stats = try_stats!(pressure_stats, errors);
+    let disk_stats = try_stats!(disk_stats, errors);
+    let io_stats = try_stats!(io_stats, errors);
+    let network_stats = try_stats!(network_stats, errors);
+    let socket_stats = try_stats!(socket_stats, errors);
+    let process_stats = try_stats!(process_stats, errors);
 
     DashboardState {
         errors,
         memory_stats,
         cpu_stats,
+        load_stats,
+        pressure_stats,
+        disk_stats,
+        io_stats,
+        network_stats,
+        socket_stats,
         process_stats,
     }
 }
@@ -198,11 +253,59 @@ where
     T: DeserializeOwned,
 {
     let request = Request::get(endpoint);
-    client
-        .send(request)
-        .await?
-        .inner()
-        .json()
-        .await
-        .context("error parsing response")
+    client.send(request).await?.json().await
+}
+
+/// Gets the process list, only fetching the changes since the previous process list if there is
+/// one.
+async fn get_process_stats(
+    client: HttpClient,
+    previous: Option<&GetProcessesResponse>,
+) -> anyhow::Result<GetProcessesResponse> {
+    let Some(previous) = previous else {
+        return get_stats(client, &format!("{BASE_URL}/processes")).await;
+    };
+
+    let url = format!("{BASE_URL}/processes?since={}", previous.generation);
+    let delta = get_stats(client, &url).await?;
+    Ok(merge_process_stats(previous, delta))
+}
+
+fn merge_process_stats(
+    previous: &GetProcessesResponse,
+    delta: GetProcessesDeltaResponse,
+) -> GetProcessesResponse {
+    let (generation, added, changed, removed) = match delta {
+        GetProcessesDeltaResponse::Delta {
+            generation,
+            added,
+            changed,
+            removed,
+        } => (generation, added, changed, removed),
+        GetProcessesDeltaResponse::Resync(stats) => return stats,
+    };
+
+    let removed: HashSet<u32> = removed.into_iter().collect();
+    let mut changed: HashMap<u32, ProcessInfo> = changed
+        .into_iter()
+        .map(|process| (process.pid, process))
+        .collect();
+    let mut processes: Vec<ProcessInfo> = previous
+        .processes
+        .iter()
+        .filter(|process| !removed.contains(&process.pid))
+        .map(|process| {
+            changed
+                .remove(&process.pid)
+                .unwrap_or_else(|| process.clone())
+        })
+        .collect();
+    processes.extend(added);
+    processes.extend(changed.into_values());
+    processes.sort_unstable_by_key(|process| Reverse(process.memory_bytes));
+
+    GetProcessesResponse {
+        generation,
+        processes,
+    }
 }
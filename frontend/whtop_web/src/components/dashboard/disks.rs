@@ -0,0 +1,84 @@
+use super::memory::format_unit;
+use crate::components::Meter;
+use whtop_common::models::api::v1::{DiskInfo, GetDisksResponse};
+use yew::prelude::*;
+
+/// The usage above which a disk is shown as almost full.
+const FULL_PERCENT: f64 = 90.0;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DiskListProps {
+    pub disks: GetDisksResponse,
+}
+
+#[function_component(DiskList)]
+pub fn disk_list(props: &DiskListProps) -> Html {
+    html! {
+        <div class={"disk-list"}>
+            { for props.disks.disks.iter().map(render_disk) }
+        </div>
+    }
+}
+
+fn render_disk(disk: &DiskInfo) -> Html {
+    let space_used = disk.total_bytes.saturating_sub(disk.available_bytes);
+    let space_progress = progress(space_used, disk.total_bytes);
+    let inodes_used = disk.total_inodes.saturating_sub(disk.available_inodes);
+    let inodes_progress = progress(inodes_used, disk.total_inodes);
+    // A disk is full when it runs out of either space or inodes
+    let full = space_progress.max(inodes_progress) * 100.0 >= FULL_PERCENT;
+    let class = if full || disk.is_unreachable {
+        "disk warning"
+    } else {
+        "disk"
+    };
+    // The usage of an unreachable disk is the last known one
+    let status = if disk.is_unreachable {
+        " (unreachable)"
+    } else if disk.is_read_only {
+        " (read-only)"
+    } else {
+        ""
+    };
+    html! {
+        <div {class}>
+            <div class={"disk-name"}>{format!("{}{status}", disk.mount_point)}</div>
+            <div class={"disk-device"}>{format!("{} ({})", disk.name, disk.file_system)}</div>
+            <div class={"disk-space-bar"}>
+                <Meter progress={space_progress} />
+            </div>
+            <div class={"disk-space"}>
+                {format!(
+                    "{} of {} used",
+                    format_unit(space_used),
+                    format_unit(disk.total_bytes),
+                )}
+            </div>
+            {
+                // File systems which allocate inodes dynamically have no inode count
+                if disk.total_inodes > 0 {
+                    html! {
+                        <>
+                            <div class={"disk-inodes-bar"}>
+                                <Meter progress={inodes_progress} />
+                            </div>
+                            <div class={"disk-inodes"}>
+                                {format!("{inodes_used} of {} inodes used", disk.total_inodes)}
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
+
+fn progress(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 / total as f64
+    }
+}
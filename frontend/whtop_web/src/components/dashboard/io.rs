@@ -0,0 +1,62 @@
+use super::memory::format_unit;
+use crate::components::Meter;
+use whtop_common::models::api::v1::{DeviceIo, GetIoResponse};
+use yew::prelude::*;
+
+/// The utilization above which a device is shown as saturated.
+const SATURATED_PERCENT: f64 = 90.0;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DiskIoListProps {
+    pub io: GetIoResponse,
+}
+
+#[function_component(DiskIoList)]
+pub fn disk_io_list(props: &DiskIoListProps) -> Html {
+    // The busiest devices come first, since they are the ones slowing the system down
+    let mut devices: Vec<&DeviceIo> = props.io.devices.iter().collect();
+    devices.sort_by(|a, b| b.utilization_percent.total_cmp(&a.utilization_percent));
+    html! {
+        <div class={"disk-io-list"}>
+            { for devices.into_iter().map(render_device) }
+        </div>
+    }
+}
+
+fn render_device(device: &DeviceIo) -> Html {
+    let class = if device.utilization_percent >= SATURATED_PERCENT {
+        "disk-io warning"
+    } else {
+        "disk-io"
+    };
+    let progress = device.utilization_percent / 100.0;
+    html! {
+        <div {class}>
+            <div class={"disk-io-name"}>{device.name.clone()}</div>
+            <div class={"disk-io-mount-points"}>{device.mount_points.join(", ")}</div>
+            <div class={"disk-io-bar"}>
+                <Meter {progress} />
+            </div>
+            <div class={"disk-io-utilization"}>
+                {format!(
+                    "{:.1}% busy, {:.2} ms/op",
+                    device.utilization_percent, device.await_milliseconds,
+                )}
+            </div>
+            <div class={"disk-io-read"}>
+                {format!(
+                    "Read: {:.1}/s, {}/s",
+                    device.reads_per_second,
+                    format_unit(device.read_bytes_per_second as u64),
+                )}
+            </div>
+            <div class={"disk-io-write"}>
+                {format!(
+                    "Write: {:.1}/s, {}/s",
+                    device.writes_per_second,
+                    format_unit(device.written_bytes_per_second as u64),
+                )}
+            </div>
+        </div>
+    }
+}
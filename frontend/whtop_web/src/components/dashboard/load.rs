@@ -0,0 +1,43 @@
+use whtop_common::models::api::v1::GetLoadResponse;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct LoadSummaryProps {
+    pub load: GetLoadResponse,
+}
+
+#[function_component(LoadSummary)]
+pub fn load_summary(props: &LoadSummaryProps) -> Html {
+    let load = &props.load;
+    let load_average = &load.load_average;
+    // Blocked processes are waiting on I/O, which usually means that storage is struggling
+    let blocked_class = if load.blocked_processes > 0 {
+        "load-summary-blocked warning"
+    } else {
+        "load-summary-blocked"
+    };
+    html! {
+        <div class={"load-summary"}>
+            <div class={"load-summary-average"}>
+                {format!(
+                    "Load average: {:.2}, {:.2}, {:.2}",
+                    load_average.one_minute,
+                    load_average.five_minutes,
+                    load_average.fifteen_minutes,
+                )}
+            </div>
+            <div class={"load-summary-running"}>
+                {format!("{} running", load.running_processes)}
+            </div>
+            <div class={blocked_class}>{format!("{} blocked", load.blocked_processes)}</div>
+            <div class={"load-summary-rates"}>
+                {format!(
+                    "{:.0} context switches/s, {:.0} interrupts/s, {:.1} forks/s",
+                    load.context_switches_per_second,
+                    load.interrupts_per_second,
+                    load.forks_per_second,
+                )}
+            </div>
+        </div>
+    }
+}
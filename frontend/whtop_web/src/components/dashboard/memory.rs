@@ -3,8 +3,11 @@ use yew::prelude::*;
 
 #[derive(Clone, PartialEq, Debug, Properties)]
 pub struct MemoryUsageProps {
+    /// Total memory in bytes.
     pub memory_total: u64,
+    /// Used memory in bytes.
     pub memory_used: u64,
+    /// Available memory in bytes.
     pub memory_available: u64,
 }
 
@@ -26,12 +29,14 @@ pub fn memory_usage(props: &MemoryUsageProps) -> Html {
     }
 }
 
-fn format_unit(kilobytes: u64) -> String {
-    if kilobytes > 1000000 {
-        format!("{:.2} GB", kilobytes as f64 / 1000000.0)
-    } else if kilobytes > 1000 {
-        format!("{:.2} MB", kilobytes as f64 / 1000.0)
+pub(super) fn format_unit(bytes: u64) -> String {
+    if bytes > 1_000_000_000 {
+        format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
+    } else if bytes > 1_000_000 {
+        format!("{:.2} MB", bytes as f64 / 1_000_000.0)
+    } else if bytes > 1_000 {
+        format!("{:.2} KB", bytes as f64 / 1_000.0)
     } else {
-        format!("{} KB", kilobytes)
+        format!("{} B", bytes)
     }
 }
@@ -0,0 +1,91 @@
+use super::memory::format_unit;
+use crate::components::Meter;
+use whtop_common::models::api::v1::{
+    Duplex, GetNetworksResponse, NetworkInterface, OperationalState,
+};
+use yew::prelude::*;
+
+/// The utilization above which an interface is shown as saturated.
+const SATURATED_PERCENT: f64 = 90.0;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct NetworkListProps {
+    pub networks: GetNetworksResponse,
+}
+
+#[function_component(NetworkList)]
+pub fn network_list(props: &NetworkListProps) -> Html {
+    html! {
+        <div class={"network-list"}>
+            { for props.networks.interfaces.iter().map(render_interface) }
+        </div>
+    }
+}
+
+fn render_interface(interface: &NetworkInterface) -> Html {
+    // Virtual interfaces, like the loopback, have an unknown state rather than being up
+    let is_down = !matches!(
+        interface.operational_state,
+        OperationalState::Up | OperationalState::Unknown
+    );
+    let utilization = interface.utilization_percent.unwrap_or(0.0);
+    let class = if is_down || utilization >= SATURATED_PERCENT {
+        "network warning"
+    } else {
+        "network"
+    };
+    let link = match interface.speed_mbps {
+        Some(speed) => {
+            let duplex = match interface.duplex {
+                Duplex::Full => ", full duplex",
+                Duplex::Half => ", half duplex",
+                Duplex::Unknown => "",
+            };
+            format!("{speed} Mb/s{duplex}")
+        }
+        None => "Unknown speed".to_owned(),
+    };
+    let state = match interface.operational_state {
+        OperationalState::Unknown => "unknown",
+        OperationalState::NotPresent => "not present",
+        OperationalState::Down => "down",
+        OperationalState::LowerLayerDown => "lower layer down",
+        OperationalState::Testing => "testing",
+        OperationalState::Dormant => "dormant",
+        OperationalState::Up => "up",
+    };
+    html! {
+        <div {class}>
+            <div class={"network-name"}>{format!("{} ({state})", interface.name)}</div>
+            <div class={"network-link"}>
+                {format!("{link}, MTU {}", interface.mtu_bytes)}
+                {
+                    match &interface.mac_address {
+                        Some(mac_address) => format!(", {mac_address}"),
+                        None => String::new(),
+                    }
+                }
+            </div>
+            <div class={"network-addresses"}>{interface.ip_addresses.join(", ")}</div>
+            {
+                // The utilization is only known along with the speed
+                if interface.utilization_percent.is_some() {
+                    html! {
+                        <div class={"network-bar"}>
+                            <Meter progress={utilization / 100.0} />
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            <div class={"network-traffic"}>
+                {format!(
+                    "In: {}/s, out: {}/s",
+                    format_unit(interface.received_bytes_per_second as u64),
+                    format_unit(interface.transmitted_bytes_per_second as u64),
+                )}
+            </div>
+        </div>
+    }
+}
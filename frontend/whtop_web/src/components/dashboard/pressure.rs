@@ -0,0 +1,59 @@
+use crate::components::Meter;
+use whtop_common::models::api::v1::{GetPressureResponse, PressureStall, ResourcePressure};
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct PressureSummaryProps {
+    pub pressure: GetPressureResponse,
+}
+
+#[function_component(PressureSummary)]
+pub fn pressure_summary(props: &PressureSummaryProps) -> Html {
+    let pressure = &props.pressure;
+    html! {
+        <div class={"pressure-summary"}>
+            {render_resource("CPU", pressure.cpu.as_ref())}
+            {render_resource("Memory", pressure.memory.as_ref())}
+            {render_resource("I/O", pressure.io.as_ref())}
+        </div>
+    }
+}
+
+fn render_resource(name: &str, pressure: Option<&ResourcePressure>) -> Html {
+    let Some(pressure) = pressure else {
+        return html! {
+            <div class={"pressure-resource"}>
+                <div class={"pressure-resource-name"}>{name}</div>
+                <div class={"pressure-resource-some"}>{"Not reported by the kernel"}</div>
+            </div>
+        };
+    };
+
+    // The meter shows the share of the last 10 seconds during which some tasks were stalled
+    let progress = pressure.some.avg10_percent as f64 / 100.0;
+    html! {
+        <div class={"pressure-resource"}>
+            <div class={"pressure-resource-name"}>{name}</div>
+            <div class={"pressure-resource-bar"}>
+                <Meter {progress} />
+            </div>
+            <div class={"pressure-resource-some"}>{format_stall("some", &pressure.some)}</div>
+            {
+                if let Some(full) = pressure.full.as_ref() {
+                    html! {
+                        <div class={"pressure-resource-full"}>{format_stall("full", full)}</div>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
+
+fn format_stall(kind: &str, stall: &PressureStall) -> String {
+    format!(
+        "{kind}: {:.2}% 10s, {:.2}% 1min, {:.2}% 5min",
+        stall.avg10_percent, stall.avg60_percent, stall.avg300_percent,
+    )
+}
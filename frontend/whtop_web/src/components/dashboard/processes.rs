@@ -1,7 +1,11 @@
 use crate::components::dashboard::{CpuUsage, MemoryUsage};
-use whtop_common::models::api::ProcessInfo;
+use whtop_common::models::api::v1::{OpenFiles, ProcessInfo};
 use yew::prelude::*;
 
+/// The share of the open files limit above which a process is shown as close to failing with
+/// `EMFILE`.
+const NEAR_LIMIT_PERCENT: f64 = 80.0;
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct ProcessListProps {
     pub process_list: Vec<ProcessInfo>,
@@ -18,16 +22,17 @@ pub fn process_list(props: &ProcessListProps) -> Html {
                     <CpuUsage
                         cpu_name={format!("{name} ({pid})", name=process.name, pid=process.pid)}
                         cpu_frequency={None}
-                        cpu_usage={process.cpu}
+                        cpu_usage={process.cpu_percent}
                     />
                 </div>
                 <div class={"process-list-row-memory"}>
                     <MemoryUsage
                         memory_total={props.total_memory}
-                        memory_available={props.total_memory.saturating_sub(process.virtual_memory)}
-                        memory_used={process.memory}
+                        memory_available={props.total_memory.saturating_sub(process.virtual_memory_bytes)}
+                        memory_used={process.memory_bytes}
                     />
                 </div>
+                { for process.open_files.as_ref().map(render_open_files) }
             </div>
         }
     });
@@ -37,3 +42,32 @@ pub fn process_list(props: &ProcessListProps) -> Html {
         </div>
     }
 }
+
+fn render_open_files(open_files: &OpenFiles) -> Html {
+    let near_limit = matches!(
+        open_files.soft_limit,
+        Some(limit) if open_files.count as f64 >= limit as f64 * NEAR_LIMIT_PERCENT / 100.0
+    );
+    let class = if near_limit {
+        "process-list-row-files warning"
+    } else {
+        "process-list-row-files"
+    };
+    let limit = match open_files.soft_limit {
+        Some(limit) => limit.to_string(),
+        None => "unlimited".to_owned(),
+    };
+    let title = format!(
+        "{} files, {} sockets, {} pipes, {} anonymous inodes, {} other",
+        open_files.files,
+        open_files.sockets,
+        open_files.pipes,
+        open_files.anon_inodes,
+        open_files.other,
+    );
+    html! {
+        <div {class} {title}>
+            {format!("fds: {} / {limit}", open_files.count)}
+        </div>
+    }
+}
@@ -0,0 +1,115 @@
+use whtop_common::models::api::v1::{
+    GetSocketsResponse, ListeningSocket, SocketProtocol, TcpActivity, UdpActivity,
+};
+use yew::prelude::*;
+
+/// The share of retransmitted TCP segments above which the network is shown as lossy.
+const LOSSY_RETRANSMITTED_PERCENT: f64 = 1.0;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct SocketListProps {
+    pub sockets: GetSocketsResponse,
+}
+
+#[function_component(SocketList)]
+pub fn socket_list(props: &SocketListProps) -> Html {
+    let sockets = &props.sockets;
+    let states = &sockets.tcp_states;
+    html! {
+        <div class={"socket-list"}>
+            <div class={"socket-counts"}>
+                {format!(
+                    "TCP: {} established, {} time wait, {} close wait, {} listening. UDP: {}. Unix: {}.",
+                    states.established,
+                    states.time_wait,
+                    states.close_wait,
+                    states.listen,
+                    sockets.udp_sockets,
+                    sockets.unix_sockets,
+                )}
+            </div>
+            { render_tcp_activity(&sockets.tcp) }
+            { render_udp_activity(&sockets.udp) }
+            <table class={"socket-table"}>
+                <thead>
+                    <tr>
+                        <th>{"Protocol"}</th>
+                        <th>{"Address"}</th>
+                        <th>{"Processes"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for sockets.listening.iter().map(render_listening_socket) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+fn render_tcp_activity(tcp: &TcpActivity) -> Html {
+    let class = if tcp.retransmitted_percent >= LOSSY_RETRANSMITTED_PERCENT {
+        "socket-activity warning"
+    } else {
+        "socket-activity"
+    };
+    html! {
+        <div {class}>
+            {format!(
+                "TCP retransmits: {:.1}/s ({:.2}%), failed connections: {:.1}/s, resets: {:.1}/s, errors: {:.1}/s",
+                tcp.segments_retransmitted_per_second,
+                tcp.retransmitted_percent,
+                tcp.failed_connection_attempts_per_second,
+                tcp.resets_sent_per_second,
+                tcp.receive_errors_per_second,
+            )}
+        </div>
+    }
+}
+
+fn render_udp_activity(udp: &UdpActivity) -> Html {
+    let class = if udp.receive_buffer_errors_per_second > 0.0 {
+        "socket-activity warning"
+    } else {
+        "socket-activity"
+    };
+    html! {
+        <div {class}>
+            {format!(
+                "UDP errors: {:.1}/s, full buffers: {:.1}/s, no port: {:.1}/s",
+                udp.receive_errors_per_second,
+                udp.receive_buffer_errors_per_second + udp.send_buffer_errors_per_second,
+                udp.no_port_per_second,
+            )}
+        </div>
+    }
+}
+
+fn render_listening_socket(socket: &ListeningSocket) -> Html {
+    let protocol = match socket.protocol {
+        SocketProtocol::Tcp => "TCP",
+        SocketProtocol::Udp => "UDP",
+        SocketProtocol::Unix => "Unix",
+    };
+    let address = match (&socket.address, socket.port, &socket.path) {
+        // IPv6 addresses are bracketed to separate them from the port
+        (Some(address), Some(port), _) if address.contains(':') => format!("[{address}]:{port}"),
+        (Some(address), Some(port), _) => format!("{address}:{port}"),
+        (_, _, Some(path)) => path.clone(),
+        _ => String::new(),
+    };
+    let processes: Vec<String> = socket
+        .processes
+        .iter()
+        .map(|process| match &process.name {
+            Some(name) => format!("{name} ({})", process.pid),
+            None => process.pid.to_string(),
+        })
+        .collect();
+    html! {
+        <tr>
+            <td>{protocol}</td>
+            <td>{address}</td>
+            <td>{processes.join(", ")}</td>
+        </tr>
+    }
+}
@@ -14,3 +14,40 @@ pub fn meter(props: &MeterProps) -> Html {
         </div>
     }
 }
+
+/// A part of a [`StackedMeter`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct MeterSegment {
+    /// The label shown when hovering the segment, and its CSS class.
+    pub name: &'static str,
+    pub progress: f64,
+}
+
+#[derive(Clone, PartialEq, Debug, Properties)]
+pub struct StackedMeterProps {
+    pub segments: Vec<MeterSegment>,
+}
+
+/// A meter split into segments drawn one after the other.
+#[function_component(StackedMeter)]
+pub fn stacked_meter(props: &StackedMeterProps) -> Html {
+    let mut remaining = 1.0;
+    html! {
+        <div class={"meter stacked"}>
+            {
+                for props.segments.iter().map(|segment| {
+                    // The segments can't overflow the meter together
+                    let progress = segment.progress.clamp(0.0, remaining);
+                    remaining -= progress;
+                    html! {
+                        <span
+                            class={segment.name}
+                            title={format!("{} {:.2}%", segment.name, 100.0 * progress)}
+                            style={format!("width: {}%", 100.0 * progress)}
+                        ></span>
+                    }
+                })
+            }
+        </div>
+    }
+}
@@ -2,13 +2,17 @@ use crate::layers::TimeoutLayer;
 use anyhow::Context as _;
 use futures::{future::LocalBoxFuture, lock::Mutex, FutureExt};
 use gloo::net::http::Request;
+use serde::de::DeserializeOwned;
 use std::{
+    error::Error,
+    fmt::{Display, Formatter},
     rc::Rc,
     task::{Context, Poll},
     time::Duration,
 };
 use tower::{util::UnsyncBoxService, Service, ServiceBuilder, ServiceExt};
 use web_sys::AbortController;
+use whtop_common::models::api::ProblemDetails;
 
 #[derive(Clone)]
 pub struct HttpClient {
@@ -120,7 +124,34 @@ pub struct Response {
 }
 
 impl Response {
-    pub fn inner(&self) -> &gloo::net::http::Response {
-        &self.response
+    /// Parses the body of a successful response. If the server responded with an error, the
+    /// returned error contains the [`ApiError`] instead.
+    pub async fn json<T>(&self) -> anyhow::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        if !self.response.ok() {
+            let problem = self.response.json().await.with_context(|| {
+                format!("server responded with status {}", self.response.status())
+            })?;
+            return Err(ApiError(problem).into());
+        }
+
+        self.response.json().await.context("error parsing response")
     }
 }
+
+/// An error response from the server.
+#[derive(Clone, Debug)]
+pub struct ApiError(pub ProblemDetails);
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0.detail {
+            Some(detail) => write!(f, "{} ({})", detail, self.0.title),
+            None => write!(f, "{}", self.0.title),
+        }
+    }
+}
+
+impl Error for ApiError {}
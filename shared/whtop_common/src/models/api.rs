@@ -1,7 +1,11 @@
+pub mod v1;
+
 mod cpu;
+mod error;
 mod memory;
 mod processes;
 
 pub use cpu::*;
+pub use error::*;
 pub use memory::*;
 pub use processes::*;
@@ -1,20 +1,31 @@
 use serde::{Deserialize, Serialize};
 
+/// Response from getting the CPU usage metrics.
 #[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GetCpuResponse {
+    /// The usage across all CPUs.
     pub global: GlobalCpuInfo,
+    /// The usage of each individual CPU.
     pub cpus: Vec<CpuInfo>,
 }
 
+/// Information about a single CPU.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CpuInfo {
+    /// The name of the CPU, for example `cpu0`.
     pub name: String,
     #[serde(flatten)]
     pub inner: GlobalCpuInfo,
 }
 
+/// Usage information for one or more CPUs.
 #[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GlobalCpuInfo {
+    /// The CPU usage as a percentage from 0 to 100.
     pub usage: f32,
+    /// The CPU frequency in MHz. For the global CPU, this is the highest frequency of any CPU.
     pub frequency: u64,
 }
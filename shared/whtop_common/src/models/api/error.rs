@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+/// The kinds of errors returned by the API.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ErrorKind {
+    /// The request was malformed, for example because of an invalid query parameter.
+    BadRequest,
+    /// The request requires authentication.
+    Unauthorized,
+    /// The client is not allowed to perform the request.
+    Forbidden,
+    /// The requested resource does not exist.
+    NotFound,
+    /// The client sent too many requests and should try again later.
+    TooManyRequests,
+    /// The requested information cannot be provided right now, for example because its collector
+    /// is disabled or its data is stale.
+    Unavailable,
+    /// An unexpected error occurred on the server.
+    Internal,
+}
+
+impl ErrorKind {
+    /// All the error kinds.
+    pub const ALL: [ErrorKind; 7] = [
+        ErrorKind::BadRequest,
+        ErrorKind::Unauthorized,
+        ErrorKind::Forbidden,
+        ErrorKind::NotFound,
+        ErrorKind::TooManyRequests,
+        ErrorKind::Unavailable,
+        ErrorKind::Internal,
+    ];
+
+    /// The HTTP status code for this kind of error.
+    pub fn status(self) -> u16 {
+        match self {
+            ErrorKind::BadRequest => 400,
+            ErrorKind::Unauthorized => 401,
+            ErrorKind::Forbidden => 403,
+            ErrorKind::NotFound => 404,
+            ErrorKind::TooManyRequests => 429,
+            ErrorKind::Unavailable => 503,
+            ErrorKind::Internal => 500,
+        }
+    }
+
+    /// A short, human-readable summary of this kind of error.
+    pub fn title(self) -> &'static str {
+        match self {
+            ErrorKind::BadRequest => "Bad request",
+            ErrorKind::Unauthorized => "Unauthorized",
+            ErrorKind::Forbidden => "Forbidden",
+            ErrorKind::NotFound => "Not found",
+            ErrorKind::TooManyRequests => "Too many requests",
+            ErrorKind::Unavailable => "Unavailable",
+            ErrorKind::Internal => "Internal error",
+        }
+    }
+
+    /// The stable URI identifying this kind of error. This is used as the `type` of the
+    /// [`ProblemDetails`].
+    pub fn type_uri(self) -> &'static str {
+        match self {
+            ErrorKind::BadRequest => "urn:whtop:problem:bad-request",
+            ErrorKind::Unauthorized => "urn:whtop:problem:unauthorized",
+            ErrorKind::Forbidden => "urn:whtop:problem:forbidden",
+            ErrorKind::NotFound => "urn:whtop:problem:not-found",
+            ErrorKind::TooManyRequests => "urn:whtop:problem:too-many-requests",
+            ErrorKind::Unavailable => "urn:whtop:problem:unavailable",
+            ErrorKind::Internal => "urn:whtop:problem:internal",
+        }
+    }
+
+    /// Gets the kind of error identified by the given URI, if it is known.
+    pub fn from_type_uri(uri: &str) -> Option<Self> {
+        ErrorKind::ALL
+            .into_iter()
+            .find(|kind| kind.type_uri() == uri)
+    }
+}
+
+/// An error response body, as described by
+/// [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807). These are sent with the
+/// `application/problem+json` content type.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProblemDetails {
+    /// A URI identifying the kind of error. See [`ErrorKind::type_uri`] for the possible values.
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    /// A short, human-readable summary of the kind of error.
+    pub title: String,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// A human-readable explanation specific to this occurrence of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ProblemDetails {
+    /// The content type of serialized problem details.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    /// Creates the problem details for an error.
+    pub fn new(kind: ErrorKind, detail: Option<String>) -> Self {
+        ProblemDetails {
+            type_uri: kind.type_uri().into(),
+            title: kind.title().into(),
+            status: kind.status(),
+            detail,
+        }
+    }
+
+    /// The kind of error, if it is known.
+    pub fn kind(&self) -> Option<ErrorKind> {
+        ErrorKind::from_type_uri(&self.type_uri)
+    }
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 /// Response from getting the memory usage metrics.
 #[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GetMemoryResponse {
     /// Total memory in kilobytes.
     pub total: u64,
@@ -1,12 +1,53 @@
 use serde::{Deserialize, Serialize};
 
+/// Query parameters for getting the process list.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct GetProcessesQuery {
+    /// The generation the client already has. If set, only the changes since that generation are
+    /// returned.
+    pub since: Option<u64>,
+}
+
+/// Response from getting the full process list.
 #[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GetProcessesResponse {
+    /// The generation of the process list. This can be passed as `since` to get only the changes
+    /// made after this response.
+    pub generation: u64,
+    /// The running processes, sorted by memory usage in descending order.
     pub processes: Vec<ProcessInfo>,
 }
 
+/// Response from getting the changes to the process list since a previous generation.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GetProcessesDeltaResponse {
+    /// The processes which changed since the requested generation.
+    Delta {
+        /// The generation of the process list after applying these changes.
+        generation: u64,
+        /// Processes which did not exist in the requested generation.
+        added: Vec<ProcessInfo>,
+        /// Processes whose information changed since the requested generation.
+        changed: Vec<ProcessInfo>,
+        /// The IDs of processes which no longer exist.
+        removed: Vec<String>,
+    },
+    /// The requested generation is no longer known to the server. The full process list is sent
+    /// instead, and should replace the client's copy.
+    Resync(GetProcessesResponse),
+}
+
 /// Information about a running process.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProcessInfo {
     /// The unique ID for the process.
     pub pid: String,
@@ -14,11 +55,12 @@ pub struct ProcessInfo {
     pub parent_pid: Option<String>,
     /// The name of the process.
     pub name: String,
-    /// The CPU usage of the process.
+    /// The CPU usage of the process as a percentage. This is not normalized by the number of CPUs,
+    /// so it can go up to 100 times the number of CPUs.
     pub cpu: f32,
-    /// The amount of memory in use by the process.
+    /// The amount of memory in use by the process in kilobytes.
     pub memory: u64,
-    /// The amount of virtual memory allocated for the process.
+    /// The amount of virtual memory allocated for the process in kilobytes.
     pub virtual_memory: u64,
     /// The number of seconds the process has been executing for.
     pub run_time: u64,
@@ -0,0 +1,26 @@
+//! Models for the `/api/v1` routes.
+//!
+//! All amounts of memory are in bytes, and field names include their unit where it isn't obvious
+//! from the type (for example `_percent`, `_mhz` or `_seconds`).
+
+mod cpu;
+mod diagnostics;
+mod disks;
+mod io;
+mod load;
+mod memory;
+mod networks;
+mod pressure;
+mod processes;
+mod sockets;
+
+pub use cpu::*;
+pub use diagnostics::*;
+pub use disks::*;
+pub use io::*;
+pub use load::*;
+pub use memory::*;
+pub use networks::*;
+pub use pressure::*;
+pub use processes::*;
+pub use sockets::*;
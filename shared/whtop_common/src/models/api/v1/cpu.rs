@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the CPU usage metrics.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetCpuResponse))]
+pub struct GetCpuResponse {
+    /// The usage across all CPUs.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::GlobalCpuInfo))]
+    pub global: GlobalCpuInfo,
+    /// The usage of each individual CPU.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::CpuInfo>))]
+    pub cpus: Vec<CpuInfo>,
+}
+
+/// Information about a single CPU.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::CpuInfo))]
+pub struct CpuInfo {
+    /// The name of the CPU, for example `cpu0`.
+    pub name: String,
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::GlobalCpuInfo))]
+    pub inner: GlobalCpuInfo,
+}
+
+/// Usage information for one or more CPUs.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GlobalCpuInfo))]
+pub struct GlobalCpuInfo {
+    /// The percentage of time the CPU was busy since the previous refresh, from 0 to 100. For the
+    /// global CPU, this is the average over all CPUs, so 100 means every CPU was fully busy.
+    pub usage_percent: f32,
+    /// The CPU frequency in MHz. For the global CPU, this is the average frequency of all CPUs.
+    pub frequency_mhz: u64,
+    /// How the time since the previous refresh was spent. Only reported on Linux.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::CpuUsageBreakdown))]
+    pub breakdown: CpuUsageBreakdown,
+}
+
+/// How the time of one or more CPUs was spent since the previous refresh, in percent from 0 to 100.
+/// `user_percent` and `nice_percent` include the time spent running guest virtual machines.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::CpuUsageBreakdown))]
+pub struct CpuUsageBreakdown {
+    /// Running processes in user mode.
+    pub user_percent: f32,
+    /// Running niced processes in user mode.
+    pub nice_percent: f32,
+    /// Running in kernel mode.
+    pub system_percent: f32,
+    /// Idle, without waiting for I/O.
+    pub idle_percent: f32,
+    /// Idle while waiting for I/O to complete.
+    pub iowait_percent: f32,
+    /// Servicing hardware interrupts.
+    pub irq_percent: f32,
+    /// Servicing software interrupts.
+    pub softirq_percent: f32,
+    /// Stolen by the hypervisor to run other virtual machines.
+    pub steal_percent: f32,
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the errors which happened while collecting the metrics.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetDiagnosticsResponse))]
+pub struct GetDiagnosticsResponse {
+    /// The errors of the latest refresh of each area, sorted by area and path. Metrics affected by
+    /// an error are reported as zero or missing.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::RefreshErrorInfo>))]
+    pub errors: Vec<RefreshErrorInfo>,
+}
+
+/// An error which happened while refreshing the metrics.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::RefreshErrorInfo))]
+pub struct RefreshErrorInfo {
+    /// The area of the metrics which were being refreshed.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::RefreshArea))]
+    pub area: RefreshArea,
+    /// The path of the file which couldn't be read.
+    pub path: String,
+    /// The ID of the process the file belongs to, if any.
+    pub pid: Option<u32>,
+    /// The OS error number, if the error came from the OS.
+    pub errno: Option<i32>,
+    /// A description of the error.
+    pub message: String,
+}
+
+/// The areas of the metrics which are refreshed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::RefreshArea))]
+#[serde(rename_all = "camelCase")]
+pub enum RefreshArea {
+    Cpu,
+    Memory,
+    Processes,
+    Disks,
+    Networks,
+    Users,
+    System,
+    Pressure,
+    Sockets,
+    DisksIo,
+    MemoryActivity,
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the mounted file systems.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetDisksResponse))]
+pub struct GetDisksResponse {
+    /// The mounted file systems, without pseudo file systems and duplicate mounts.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::DiskInfo>))]
+    pub disks: Vec<DiskInfo>,
+}
+
+/// A mounted file system.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::DiskInfo))]
+pub struct DiskInfo {
+    /// The mounted device, like `/dev/sda1`.
+    pub name: String,
+    /// Where the file system is mounted.
+    pub mount_point: String,
+    /// The type of the file system, like `ext4`.
+    pub file_system: String,
+    /// The options the file system is mounted with, like `rw` or `noexec`.
+    pub mount_options: Vec<String>,
+    /// Whether the file system is mounted read-only.
+    pub is_read_only: bool,
+    /// Whether the device is removable, like a USB drive.
+    pub is_removable: bool,
+    /// The size of the file system in bytes.
+    pub total_bytes: u64,
+    /// The space which can still be used in bytes.
+    pub available_bytes: u64,
+    /// The number of inodes of the file system. It is 0 for file systems which allocate them
+    /// dynamically.
+    pub total_inodes: u64,
+    /// The number of inodes which can still be allocated. Files can't be created once there are
+    /// none left, even if there is space available.
+    pub available_inodes: u64,
+    /// Whether the file system didn't answer in time, like a network file system whose server is
+    /// down. The space and inodes are then the last known ones.
+    #[serde(default)]
+    pub is_unreachable: bool,
+}
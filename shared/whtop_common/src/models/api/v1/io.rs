@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the I/O activity of the block devices.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetIoResponse))]
+pub struct GetIoResponse {
+    /// The block devices which were used since boot, whole disks and partitions alike.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::DeviceIo>))]
+    pub devices: Vec<DeviceIo>,
+}
+
+/// The I/O activity of a block device, since the previous refresh.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::DeviceIo))]
+pub struct DeviceIo {
+    /// The name of the device, like `sda`, `nvme0n1p1` or `dm-0`.
+    pub name: String,
+    /// Where the device is mounted, if it is.
+    pub mount_points: Vec<String>,
+    /// Read operations completed per second.
+    pub reads_per_second: f64,
+    /// Write operations completed per second.
+    pub writes_per_second: f64,
+    /// Bytes read per second.
+    pub read_bytes_per_second: f64,
+    /// Bytes written per second.
+    pub written_bytes_per_second: f64,
+    /// The average time an operation took to complete, including the time it waited in the queue.
+    pub await_milliseconds: f64,
+    /// The percentage of time the device was busy. A device close to 100% is saturated.
+    pub utilization_percent: f64,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the load of the system.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetLoadResponse))]
+pub struct GetLoadResponse {
+    /// The average number of threads running or waiting to run.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::LoadAverage))]
+    pub load_average: LoadAverage,
+    /// Context switches per second since the previous refresh.
+    pub context_switches_per_second: f64,
+    /// Interrupts serviced per second since the previous refresh.
+    pub interrupts_per_second: f64,
+    /// Processes and threads created per second since the previous refresh.
+    pub forks_per_second: f64,
+    /// The number of threads currently running or ready to run.
+    pub running_processes: u64,
+    /// The number of threads currently blocked waiting for I/O to complete.
+    pub blocked_processes: u64,
+}
+
+/// The load average over several periods.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::LoadAverage))]
+pub struct LoadAverage {
+    /// The load average over the last minute.
+    pub one_minute: f64,
+    /// The load average over the last five minutes.
+    pub five_minutes: f64,
+    /// The load average over the last fifteen minutes.
+    pub fifteen_minutes: f64,
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the memory usage metrics.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetMemoryResponse))]
+pub struct GetMemoryResponse {
+    /// Total memory in bytes.
+    pub total_bytes: u64,
+    /// Used memory in bytes. This excludes buffers, the page cache and reclaimable slab memory.
+    pub used_bytes: u64,
+    /// Free (unallocated) memory in bytes.
+    pub free_bytes: u64,
+    /// Available (reusable) memory in bytes.
+    pub available_bytes: u64,
+}
+
+/// Response from getting the details of the memory usage and the activity of the virtual memory.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::ToSchema),
+    schema(as = v1::GetMemoryDetailResponse)
+)]
+pub struct GetMemoryDetailResponse {
+    /// Memory waiting to be written back to disk, in bytes.
+    pub dirty_bytes: u64,
+    /// Memory being written back to disk, in bytes.
+    pub writeback_bytes: u64,
+    /// Memory used by shared memory and `tmpfs`, in bytes.
+    pub shared_bytes: u64,
+    /// Memory allocated by processes, even if it isn't backed by RAM yet, in bytes.
+    pub committed_bytes: u64,
+    /// The number of huge pages in the pool.
+    pub huge_pages_total: u64,
+    /// The number of huge pages in the pool which aren't allocated.
+    pub huge_pages_free: u64,
+    /// The size of a huge page in bytes.
+    pub huge_page_size_bytes: u64,
+    /// Page faults per second since the previous refresh, major or not.
+    pub page_faults_per_second: f64,
+    /// Page faults which required reading from disk, per second since the previous refresh.
+    pub major_page_faults_per_second: f64,
+    /// Pages read from swap per second since the previous refresh.
+    pub pages_swapped_in_per_second: f64,
+    /// Pages written to swap per second since the previous refresh.
+    pub pages_swapped_out_per_second: f64,
+    /// Pages scanned for reclaim per second since the previous refresh.
+    pub pages_scanned_per_second: f64,
+    /// Pages reclaimed per second since the previous refresh.
+    pub pages_stolen_per_second: f64,
+    /// The number of processes killed because the system ran out of memory, since boot.
+    pub oom_kills: u64,
+}
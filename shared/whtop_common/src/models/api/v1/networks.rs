@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the network interfaces.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetNetworksResponse))]
+pub struct GetNetworksResponse {
+    /// The network interfaces, including virtual ones like the loopback.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::NetworkInterface>))]
+    pub interfaces: Vec<NetworkInterface>,
+}
+
+/// A network interface, with its traffic since the previous refresh.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::NetworkInterface))]
+pub struct NetworkInterface {
+    /// The name of the interface, like `eth0`.
+    pub name: String,
+    /// The MAC address of the interface, like `52:54:00:12:34:56`. Interfaces without one, like
+    /// the loopback, have none.
+    pub mac_address: Option<String>,
+    /// The IPv4 and IPv6 addresses of the interface with their prefix length, like
+    /// `192.168.1.10/24`. They are those of the network namespace whtop runs in.
+    pub ip_addresses: Vec<String>,
+    /// The largest packet the interface can transmit, in bytes.
+    pub mtu_bytes: u64,
+    /// Whether the interface can pass packets.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::OperationalState))]
+    pub operational_state: OperationalState,
+    /// Whether the interface detects a link, like a plugged cable. It isn't known while the
+    /// interface is administratively down.
+    pub carrier: Option<bool>,
+    /// The duplex mode negotiated by the interface.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::Duplex))]
+    pub duplex: Duplex,
+    /// The speed negotiated by the interface in Mb/s. It isn't known for most virtual interfaces,
+    /// or while the link is down.
+    pub speed_mbps: Option<u64>,
+    /// Bytes received per second.
+    pub received_bytes_per_second: f64,
+    /// Bytes transmitted per second.
+    pub transmitted_bytes_per_second: f64,
+    /// Bytes received since boot.
+    pub total_received_bytes: u64,
+    /// Bytes transmitted since boot.
+    pub total_transmitted_bytes: u64,
+    /// The percentage of the speed of the interface which is used, from 0 to 100. A full duplex
+    /// interface sends and receives at its full speed at the same time, so the busiest direction
+    /// is used, while the two directions share the speed of a half duplex interface. It is only
+    /// known when the speed is.
+    pub utilization_percent: Option<f64>,
+}
+
+/// The operational state of a network interface, as defined by RFC 2863.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::OperationalState))]
+#[serde(rename_all = "camelCase")]
+pub enum OperationalState {
+    /// The state isn't known, which is usual for virtual interfaces like the loopback.
+    #[default]
+    Unknown,
+    /// A component of the interface, like its driver, is missing.
+    NotPresent,
+    /// The interface can't pass packets.
+    Down,
+    /// The interface is down because an interface it runs on is.
+    LowerLayerDown,
+    /// The interface is in test mode.
+    Testing,
+    /// The interface is waiting for an external event, like an authentication.
+    Dormant,
+    /// The interface can pass packets.
+    Up,
+}
+
+/// Whether a network interface can send and receive at the same time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::Duplex))]
+#[serde(rename_all = "camelCase")]
+pub enum Duplex {
+    /// The duplex mode isn't known, like for virtual interfaces or interfaces which are down.
+    #[default]
+    Unknown,
+    /// The interface sends and receives in turn.
+    Half,
+    /// The interface sends and receives at the same time.
+    Full,
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the pressure stall information. A resource is `null` when the kernel
+/// doesn't report its pressure.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetPressureResponse))]
+pub struct GetPressureResponse {
+    /// Tasks waiting for a CPU.
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<v1::ResourcePressure>))]
+    pub cpu: Option<ResourcePressure>,
+    /// Tasks waiting for memory, for example while reclaiming pages or swapping.
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<v1::ResourcePressure>))]
+    pub memory: Option<ResourcePressure>,
+    /// Tasks waiting for I/O.
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<v1::ResourcePressure>))]
+    pub io: Option<ResourcePressure>,
+}
+
+/// The pressure on one resource.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::ResourcePressure))]
+pub struct ResourcePressure {
+    /// The time at least one task was stalled.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::PressureStall))]
+    pub some: PressureStall,
+    /// The time all non-idle tasks were stalled at once. Older kernels don't report it for the CPU.
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<v1::PressureStall>))]
+    pub full: Option<PressureStall>,
+}
+
+/// How long tasks were stalled.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::PressureStall))]
+pub struct PressureStall {
+    /// The percentage of the last 10 seconds, from 0 to 100.
+    pub avg10_percent: f32,
+    /// The percentage of the last 60 seconds, from 0 to 100.
+    pub avg60_percent: f32,
+    /// The percentage of the last 300 seconds, from 0 to 100.
+    pub avg300_percent: f32,
+    /// The total stall time since boot, in microseconds.
+    pub total_microseconds: u64,
+}
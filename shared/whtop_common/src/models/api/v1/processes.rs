@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+pub use crate::models::api::GetProcessesQuery;
+
+/// Response from getting the full process list.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetProcessesResponse))]
+pub struct GetProcessesResponse {
+    /// The generation of the process list. This can be passed as `since` to get only the changes
+    /// made after this response.
+    pub generation: u64,
+    /// The running processes, sorted by memory usage in descending order.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::ProcessInfo>))]
+    pub processes: Vec<ProcessInfo>,
+}
+
+/// Response from getting the changes to the process list since a previous generation.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::ToSchema),
+    schema(as = v1::GetProcessesDeltaResponse)
+)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GetProcessesDeltaResponse {
+    /// The processes which changed since the requested generation.
+    Delta {
+        /// The generation of the process list after applying these changes.
+        generation: u64,
+        /// Processes which did not exist in the requested generation.
+        #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::ProcessInfo>))]
+        added: Vec<ProcessInfo>,
+        /// Processes whose information changed since the requested generation.
+        #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::ProcessInfo>))]
+        changed: Vec<ProcessInfo>,
+        /// The IDs of processes which no longer exist.
+        removed: Vec<u32>,
+    },
+    /// The requested generation is no longer known to the server. The full process list is sent
+    /// instead, and should replace the client's copy.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::GetProcessesResponse))]
+    Resync(GetProcessesResponse),
+}
+
+/// Information about a running process.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::ProcessInfo))]
+pub struct ProcessInfo {
+    /// The unique ID for the process.
+    pub pid: u32,
+    /// The unique ID of the parent process, if any.
+    pub parent_pid: Option<u32>,
+    /// The name of the process.
+    pub name: String,
+    /// The share of the total CPU time used by the process since the previous refresh, from 0 to
+    /// 100. This is normalized by the number of CPUs, so 100 means the process kept every CPU
+    /// fully busy.
+    pub cpu_percent: f32,
+    /// The amount of memory in use by the process (its resident set size) in bytes.
+    pub memory_bytes: u64,
+    /// The amount of virtual memory allocated for the process in bytes.
+    pub virtual_memory_bytes: u64,
+    /// The number of seconds the process has been executing for.
+    pub run_time_seconds: u64,
+    /// The file descriptors opened by the process, if open files are collected and they could be
+    /// read. The processes of other users are only read when whtop runs as root.
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<v1::OpenFiles>))]
+    pub open_files: Option<OpenFiles>,
+}
+
+/// The file descriptors opened by a process.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::OpenFiles))]
+pub struct OpenFiles {
+    /// The number of open file descriptors.
+    pub count: usize,
+    /// File descriptors of files, directories and devices.
+    pub files: usize,
+    /// File descriptors of sockets.
+    pub sockets: usize,
+    /// File descriptors of pipes and FIFOs.
+    pub pipes: usize,
+    /// File descriptors without inode on a file system, like `eventfd`, `epoll` or `timerfd`.
+    pub anon_inodes: usize,
+    /// File descriptors of anything else, like namespaces.
+    pub other: usize,
+    /// The number of file descriptors the process can open before failing with `EMFILE`, or
+    /// `None` if it is unlimited or the limits couldn't be read.
+    pub soft_limit: Option<u64>,
+    /// The highest value the process can raise its soft limit to, or `None` if it is unlimited
+    /// or the limits couldn't be read.
+    pub hard_limit: Option<u64>,
+}
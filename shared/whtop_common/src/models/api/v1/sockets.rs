@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from getting the sockets.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::GetSocketsResponse))]
+pub struct GetSocketsResponse {
+    /// The number of IPv4 and IPv6 TCP sockets in each state.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::TcpStateCounts))]
+    pub tcp_states: TcpStateCounts,
+    /// The number of IPv4 and IPv6 UDP sockets.
+    pub udp_sockets: usize,
+    /// The number of Unix domain sockets.
+    pub unix_sockets: usize,
+    /// The sockets waiting for connections or datagrams: listening TCP sockets, UDP sockets which
+    /// aren't connected and Unix stream sockets accepting connections.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::ListeningSocket>))]
+    pub listening: Vec<ListeningSocket>,
+    /// The activity of TCP since the previous refresh.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::TcpActivity))]
+    pub tcp: TcpActivity,
+    /// The activity of UDP since the previous refresh.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::UdpActivity))]
+    pub udp: UdpActivity,
+}
+
+/// The number of TCP sockets in each state.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::TcpStateCounts))]
+pub struct TcpStateCounts {
+    /// Open connections.
+    pub established: usize,
+    /// Connection requests sent and waiting for an answer.
+    pub syn_sent: usize,
+    /// Connection requests received and waiting for an acknowledgment.
+    pub syn_received: usize,
+    /// Connections closed locally and waiting for the remote end to acknowledge it.
+    pub fin_wait1: usize,
+    /// Connections closed locally and waiting for the remote end to close too.
+    pub fin_wait2: usize,
+    /// Connections closed by both ends and waiting for the delayed packets to be dropped.
+    pub time_wait: usize,
+    /// Closed sockets.
+    pub close: usize,
+    /// Connections closed by the remote end and waiting for the socket to be closed.
+    pub close_wait: usize,
+    /// Connections closed by both ends and waiting for the last acknowledgment.
+    pub last_ack: usize,
+    /// Sockets waiting for connections.
+    pub listen: usize,
+    /// Connections closed by both ends at the same time.
+    pub closing: usize,
+    /// Connection requests received, before a socket is created for them.
+    pub new_syn_received: usize,
+}
+
+/// A socket waiting for connections or datagrams.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::ListeningSocket))]
+pub struct ListeningSocket {
+    /// The protocol of the socket.
+    #[cfg_attr(feature = "openapi", schema(value_type = v1::SocketProtocol))]
+    pub protocol: SocketProtocol,
+    /// The local IP address of a TCP or UDP socket, like `0.0.0.0` or `::` when it listens on
+    /// every address.
+    pub address: Option<String>,
+    /// The local port of a TCP or UDP socket.
+    pub port: Option<u16>,
+    /// The path of a Unix socket, which starts with `@` for an abstract socket.
+    pub path: Option<String>,
+    /// The processes which have the socket open, if they are looked for. The processes of other
+    /// users are only found when whtop runs as root.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<v1::SocketProcess>))]
+    pub processes: Vec<SocketProcess>,
+}
+
+/// The protocol of a socket.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::SocketProtocol))]
+#[serde(rename_all = "camelCase")]
+pub enum SocketProtocol {
+    /// TCP, over IPv4 or IPv6.
+    #[default]
+    Tcp,
+    /// UDP, over IPv4 or IPv6.
+    Udp,
+    /// A Unix domain socket.
+    Unix,
+}
+
+/// A process which has a socket open.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::SocketProcess))]
+pub struct SocketProcess {
+    /// The process ID.
+    pub pid: u32,
+    /// The name of the process, if processes are collected.
+    pub name: Option<String>,
+}
+
+/// The activity of TCP, over IPv4 and IPv6.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::TcpActivity))]
+pub struct TcpActivity {
+    /// Connections opened by the system per second.
+    pub active_opens_per_second: f64,
+    /// Connections accepted by the system per second.
+    pub passive_opens_per_second: f64,
+    /// Connection attempts which failed per second.
+    pub failed_connection_attempts_per_second: f64,
+    /// Established connections which were reset per second.
+    pub established_resets_per_second: f64,
+    /// Segments sent per second.
+    pub segments_sent_per_second: f64,
+    /// Segments sent again per second, because they weren't acknowledged in time.
+    pub segments_retransmitted_per_second: f64,
+    /// The percentage of the segments sent which were retransmissions, from 0 to 100.
+    pub retransmitted_percent: f64,
+    /// Segments received with an error, like a bad checksum, per second.
+    pub receive_errors_per_second: f64,
+    /// Segments sent with the reset flag per second.
+    pub resets_sent_per_second: f64,
+}
+
+/// The activity of UDP, over IPv4 and IPv6.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema), schema(as = v1::UdpActivity))]
+pub struct UdpActivity {
+    /// Datagrams delivered to sockets per second.
+    pub datagrams_received_per_second: f64,
+    /// Datagrams sent per second.
+    pub datagrams_sent_per_second: f64,
+    /// Datagrams received for a port without socket per second.
+    pub no_port_per_second: f64,
+    /// Datagrams which couldn't be delivered per second, including those of the full buffers.
+    pub receive_errors_per_second: f64,
+    /// Datagrams dropped per second because the receive buffer of their socket was full.
+    pub receive_buffer_errors_per_second: f64,
+    /// Datagrams dropped per second because the send buffer of their socket was full.
+    pub send_buffer_errors_per_second: f64,
+}